@@ -0,0 +1,67 @@
+//! Compares the throughput of formatting, parsing and the
+//! cached current timestamp with that of the common
+//! alternatives, i.e. httpdate, chrono and time. Run with
+//! `cargo bench --features bench-compare --bench compare`.
+
+use httpdt::{Datetime, DateService};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use time::{OffsetDateTime, PrimitiveDateTime};
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant, SystemTime};
+
+const RUNS: u32 = 200_000;
+const NOV_06_1994_08_49_37: u64 = 784_111_777;
+const IMF_FIXDATE: &str = "Sun, 06 Nov 1994 08:49:37 GMT";
+
+const CHRONO_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+const TIME_FORMAT: &[BorrowedFormatItem] = format_description!(
+  "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
+
+fn time<T>(f: impl Fn() -> T) -> Duration {
+  let start = Instant::now();
+  for _ in 0..RUNS {
+    black_box(f());
+  }
+  start.elapsed() / RUNS
+}
+
+fn report(task: &str, results: [(&str, Duration); 4]) {
+  println!("{task}");
+  for (name, duration) in results {
+    println!("  {name:<10}  {duration:>8.0?}");
+  }
+}
+
+fn main() {
+
+  let secs = NOV_06_1994_08_49_37;
+  let st = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+  report("format", [
+    ("httpdt",   time(|| Datetime::from(black_box(secs)).for_header_bytes())),
+    ("httpdate", time(|| httpdate::fmt_http_date(black_box(st)))),
+    ("chrono",   time(|| DateTime::<Utc>::from_timestamp(black_box(secs) as i64, 0).unwrap().format(CHRONO_FORMAT).to_string())),
+    ("time",     time(|| OffsetDateTime::from_unix_timestamp(black_box(secs) as i64).unwrap().format(TIME_FORMAT).unwrap()))
+  ]);
+
+  report("parse", [
+    ("httpdt",   time(|| Datetime::parse(black_box(IMF_FIXDATE)).unwrap())),
+    ("httpdate", time(|| httpdate::parse_http_date(black_box(IMF_FIXDATE)).unwrap())),
+    ("chrono",   time(|| NaiveDateTime::parse_from_str(black_box(IMF_FIXDATE), CHRONO_FORMAT).unwrap())),
+    ("time",     time(|| PrimitiveDateTime::parse(black_box(IMF_FIXDATE), TIME_FORMAT).unwrap()))
+  ]);
+
+  let service = DateService::new().unwrap();
+
+  report("now", [
+    ("httpdt",   time(|| service.for_header_bytes().unwrap())),
+    ("httpdate", time(|| httpdate::fmt_http_date(SystemTime::now()))),
+    ("chrono",   time(|| Utc::now().format(CHRONO_FORMAT).to_string())),
+    ("time",     time(|| OffsetDateTime::now_utc().format(TIME_FORMAT).unwrap()))
+  ]);
+}
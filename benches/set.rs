@@ -0,0 +1,35 @@
+//! Compares the two ways in which `Datetime::set` can
+//! advance the date, i.e. skipping day by day from the
+//! current date and computing it afresh from the seconds
+//! since the epoch, over jumps of increasing size, to show
+//! the crossover. Run with `cargo bench`.
+
+use httpdt::{Date, Datetime};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: u32 = 100_000;
+const D_AS_S: u64 = 86_400;
+
+fn time(f: impl Fn() -> Date) -> Duration {
+  let start = Instant::now();
+  for _ in 0..RUNS {
+    black_box(f());
+  }
+  start.elapsed() / RUNS
+}
+
+fn main() {
+
+  let base = Datetime::parse("Wed, 01 Jan 2025 00:00:00 GMT").unwrap();
+
+  println!("{:>6}  {:>10}  {:>10}  {:>10}", "days", "skip", "from", "set");
+  for diff_d in [0, 1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024] {
+    let secs = base.secs() + diff_d * D_AS_S;
+    let skip = time(|| black_box(base.date()).skip_days(black_box(diff_d)));
+    let from = time(|| Date::from(black_box(secs)));
+    let set  = time(|| black_box(base).set(black_box(secs)).date());
+    println!("{diff_d:>6}  {skip:>10.0?}  {from:>10.0?}  {set:>10.0?}");
+  }
+}
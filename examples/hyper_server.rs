@@ -0,0 +1,49 @@
+//! An HTTP/1.1 server on hyper, with hyper's own Date
+//! header disabled in favour of that from a `DateService`
+//! shared across connection tasks. Run with `cargo run
+//! --example hyper_server` and request with e.g. `curl -i
+//! localhost:8080`.
+
+use httpdt::DateService;
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{HeaderValue, DATE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::io;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Box<dyn Error>> {
+  let listener = TcpListener::bind("127.0.0.1:8080").await?;
+  Ok (serve(listener, DateService::new()?).await?)
+}
+
+pub async fn serve(listener: TcpListener, service: DateService) -> io::Result<()> {
+  loop {
+    let (stream, _) = listener.accept().await?;
+    let service = service.clone();
+    tokio::spawn(async move {
+      let respond = service_fn(move |req| respond(req, service.clone()));
+      let _ = http1::Builder::new()
+        .auto_date_header(false)
+        .serve_connection(TokioIo::new(stream), respond)
+        .await;
+    });
+  }
+}
+
+pub async fn respond<B>(_req: Request<B>, service: DateService) -> Result<Response<Full<Bytes>>, Infallible> {
+  let mut res = Response::new(Full::new(Bytes::from_static(b"Hello\n")));
+  if let Ok (bytes) = service.for_header_bytes() {
+    let value = HeaderValue::from_bytes(&bytes).expect("IMF-fixdate timestamp is visible ASCII");
+    res.headers_mut().insert(DATE, value);
+  }
+  Ok (res)
+}
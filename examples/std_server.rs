@@ -0,0 +1,43 @@
+//! A minimal HTTP/1.1 server on the standard library's
+//! `TcpListener`, stamping each response with the Date
+//! header from a `DateService` shared across connection
+//! threads. Run with `cargo run --example std_server` and
+//! request with e.g. `curl -i localhost:8080`.
+
+use httpdt::DateService;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+  let listener = TcpListener::bind("127.0.0.1:8080")?;
+  Ok (serve(listener, DateService::new()?)?)
+}
+
+pub fn serve(listener: TcpListener, service: DateService) -> io::Result<()> {
+  for stream in listener.incoming() {
+    let (stream, service) = (stream?, service.clone());
+    thread::spawn(move || handle(stream, &service));
+  }
+  Ok (())
+}
+
+// reads the request head, disregarded, then responds and
+// closes the connection
+pub fn handle(mut stream: TcpStream, service: &DateService) -> io::Result<()> {
+  let mut reader = BufReader::new(&stream);
+  let mut line = String::new();
+  while reader.read_line(&mut line)? > 2 {
+    line.clear();
+  }
+  let date = service
+    .for_header_bytes()
+    .map_err(|e| io::Error::other(e.to_string()))?;
+  let body = b"Hello\n";
+  stream.write_all(b"HTTP/1.1 200 OK\r\nDate: ")?;
+  stream.write_all(&date)?;
+  write!(stream, "\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len())?;
+  stream.write_all(body)
+}
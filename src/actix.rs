@@ -0,0 +1,146 @@
+//! # actix
+//!
+//! Integration with actix-web, available with the
+//! `actix-web` feature.
+
+use crate::service::{self, DateService};
+
+use actix_web::Error;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::http::header::{DATE, HeaderValue};
+
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+
+/// Applies `DateHeaderMiddleware`, stamping each response
+/// with a Date header from the shared cache of a
+/// `DateService`, unless the response already has one,
+/// in which case actix-web's own Date header is omitted.
+///
+/// # Example
+///
+/// ```
+/// use actix_web::{App, web};
+/// use httpdt::{DateService, actix::DateHeader};
+///
+/// let service = DateService::new()
+///   .unwrap();
+///
+/// let app = App::new()
+///   .wrap(DateHeader::new(service))
+///   .route("/", web::get().to(|| async { "Hello" }));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DateHeader {
+  service: DateService
+}
+
+impl DateHeader {
+
+  pub fn new(service: DateService) -> Self {
+    Self { service }
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for DateHeader
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  S::Future: 'static
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Transform = DateHeaderMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, inner: S) -> Self::Future {
+    future::ready(Ok (DateHeaderMiddleware { inner, service: self.service.clone() }))
+  }
+}
+
+// DateHeaderMiddleware
+
+/// Wraps an inner service, stamping each response with a
+/// Date header once the inner service has responded.
+#[derive(Debug)]
+pub struct DateHeaderMiddleware<S> {
+    inner: S,
+  service: DateService
+}
+
+impl<S, B> Service<ServiceRequest> for DateHeaderMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  S::Future: 'static
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(inner);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let future = self.inner.call(req);
+    let service = self.service.clone();
+    Box::pin(async move {
+      let mut res = future.await?;
+      if let (false, Ok (bytes)) = (res.headers().contains_key(DATE), service.for_header_bytes()) {
+        let value: HeaderValue = service::header_value(&bytes);
+        res.headers_mut().insert(DATE, value);
+      }
+      Ok (res)
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::DateHeader;
+  use crate::datetime::Datetime;
+  use crate::service::DateService;
+
+  use actix_web::{App, HttpResponse, web, test};
+  use actix_web::http::header::DATE;
+
+  use std::future::Future;
+  use std::pin::pin;
+  use std::task::{Context, Poll, Waker};
+
+  fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+      Poll::Ready(output) => output,
+      Poll::Pending       => panic!("app is always ready")
+    }
+  }
+
+  #[test]
+  fn actix_date_header() {
+
+    let app = block_on(test::init_service(App::new()
+      .wrap(DateHeader::new(DateService::new().unwrap()))
+      .route("/", web::get().to(HttpResponse::Ok))
+    ));
+    let res = block_on(test::call_service(&app, test::TestRequest::get().uri("/").to_request()));
+    let value = res.headers().get(DATE).unwrap().to_str().unwrap();
+
+    assert!(Datetime::parse(value).is_ok());
+  }
+
+  #[test]
+  fn actix_date_header_existing() {
+
+    let app = block_on(test::init_service(App::new()
+      .wrap(DateHeader::new(DateService::new().unwrap()))
+      .route("/", web::get().to(|| async {
+        HttpResponse::Ok()
+          .insert_header((DATE, "Thu, 01 Jan 1970 00:00:00 GMT"))
+          .finish()
+      }))
+    ));
+    let res = block_on(test::call_service(&app, test::TestRequest::get().uri("/").to_request()));
+
+    assert_eq!("Thu, 01 Jan 1970 00:00:00 GMT", res.headers().get(DATE).unwrap());
+  }
+}
@@ -0,0 +1,182 @@
+//! # axum
+//!
+//! Integration with axum, available with the `axum` feature.
+
+use crate::datetime::Datetime;
+use crate::service::{self, DateService};
+
+use ::axum::http::{Request, Response, HeaderValue};
+use ::axum::http::header::{DATE, LAST_MODIFIED, EXPIRES};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Applies `DateMiddleware`, stamping each response with
+/// a Date header from the shared cache of a `DateService`,
+/// unless the response already has one.
+///
+/// # Example
+///
+/// ```
+/// use axum::{Router, routing::get};
+/// use httpdt::{DateService, axum::DateLayer};
+///
+/// let service = DateService::new()
+///   .unwrap();
+///
+/// let app: Router = Router::new()
+///   .route("/", get(|| async { "Hello" }))
+///   .layer(DateLayer::new(service));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DateLayer {
+  service: DateService
+}
+
+impl DateLayer {
+
+  pub fn new(service: DateService) -> Self {
+    Self { service }
+  }
+}
+
+impl<S> Layer<S> for DateLayer {
+  type Service = DateMiddleware<S>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    DateMiddleware { inner, service: self.service.clone() }
+  }
+}
+
+// DateMiddleware
+
+/// Wraps an inner service, stamping each response with a
+/// Date header once the inner service has responded.
+#[derive(Clone, Debug)]
+pub struct DateMiddleware<S> {
+    inner: S,
+  service: DateService
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for DateMiddleware<S>
+where
+  S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+  S::Future: Send + 'static
+{
+  type Response = S::Response;
+  type Error = S::Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+    let future = self.inner.call(req);
+    let service = self.service.clone();
+    Box::pin(async move {
+      let mut res = future.await?;
+      if let Ok (bytes) = service.for_header_bytes() {
+        res.headers_mut()
+          .entry(DATE)
+          .or_insert_with(|| service::header_value(&bytes));
+      }
+      Ok (res)
+    })
+  }
+}
+
+// helpers
+
+/// Sets the Last-Modified header of a response.
+pub fn set_last_modified<B>(res: &mut Response<B>, dt: &Datetime) {
+  res.headers_mut().insert(LAST_MODIFIED, header_value(dt));
+}
+
+/// Sets the Expires header of a response.
+pub fn set_expires<B>(res: &mut Response<B>, dt: &Datetime) {
+  res.headers_mut().insert(EXPIRES, header_value(dt));
+}
+
+/// Provides the HTTP Date header timestamp as a header value.
+pub fn header_value(dt: &Datetime) -> HeaderValue {
+  service::header_value(&dt.for_header_bytes())
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{DateLayer, set_last_modified, set_expires};
+  use crate::datetime::Datetime;
+  use crate::service::DateService;
+
+  use ::axum::http::{Request, Response, HeaderValue};
+  use ::axum::http::header::{DATE, LAST_MODIFIED, EXPIRES};
+  use tower_layer::Layer;
+  use tower_service::Service;
+
+  use std::future::{self, Ready};
+  use std::convert::Infallible;
+  use std::task::{Context, Poll, Waker};
+
+  struct Respond(Option<HeaderValue>);
+
+  impl Service<Request<()>> for Respond {
+    type Response = Response<()>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok (()))
+    }
+
+    fn call(&mut self, _: Request<()>) -> Self::Future {
+      let mut res = Response::new(());
+      if let Some (value) = self.0.clone() {
+        res.headers_mut().insert(DATE, value);
+      }
+      future::ready(Ok (res))
+    }
+  }
+
+  fn respond(inner: Respond) -> Response<()> {
+    let mut middleware = DateLayer::new(DateService::new().unwrap()).layer(inner);
+    let mut future = middleware.call(Request::new(()));
+    match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+      Poll::Ready(res) => res.unwrap(),
+      Poll::Pending    => panic!("inner service is always ready")
+    }
+  }
+
+  #[test]
+  fn axum_date_layer() {
+
+    let res = respond(Respond(None));
+    let value = res.headers().get(DATE).unwrap().to_str().unwrap();
+
+    assert!(Datetime::parse(value).is_ok());
+  }
+
+  #[test]
+  fn axum_date_layer_existing() {
+
+    let value = HeaderValue::from_static("Thu, 01 Jan 1970 00:00:00 GMT");
+    let res = respond(Respond(Some (value.clone())));
+
+    assert_eq!(Some (&value), res.headers().get(DATE));
+  }
+
+  #[test]
+  fn axum_helpers() {
+
+    let mut res = Response::new(());
+    set_last_modified(&mut res, &Datetime::default());
+    set_expires(&mut res, &Datetime::default().set(1));
+
+    assert_eq!("Thu, 01 Jan 1970 00:00:00 GMT", res.headers().get(LAST_MODIFIED).unwrap());
+    assert_eq!("Thu, 01 Jan 1970 00:00:01 GMT", res.headers().get(EXPIRES).unwrap());
+  }
+}
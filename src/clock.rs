@@ -0,0 +1,202 @@
+//! # Clock
+//!
+//! Overriding of the source of the current time, for the
+//! testing of code using the crate, available with the
+//! `clock-override` feature.
+
+use crate::datetime::Datetime;
+
+use std::sync::RwLock;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::time::SystemTime;
+use std::error::Error;
+
+static GLOBAL: RwLock<Option<Box<dyn Clock>>> = RwLock::new(None);
+
+thread_local! {
+  static FROZEN: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Provides the current number of seconds since the epoch,
+/// implemented for the system clock (`SystemClock`) and for
+/// any closure returning the number of seconds.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Clock, SystemClock};
+///
+/// let fixed = || 784_111_777;
+///
+/// assert_eq!(784_111_777, fixed.raw().unwrap());
+/// assert!(SystemClock.raw().unwrap() > 784_111_777);
+/// ```
+pub trait Clock: Send + Sync {
+  fn raw(&self) -> Result<u64, Box<dyn Error>>;
+}
+
+/// Reads the system clock, as used by default.
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+
+  fn raw(&self) -> Result<u64, Box<dyn Error>> {
+    let raw = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)?
+      .as_secs();
+    Ok (raw)
+  }
+}
+
+impl<F: Fn() -> u64 + Send + Sync> Clock for F {
+
+  fn raw(&self) -> Result<u64, Box<dyn Error>> {
+    Ok (self())
+  }
+}
+
+/// Redirects `Datetime::raw`, and so `Datetime::new`, the
+/// `now` method and `DateService`, to `clock` across all
+/// threads, until replaced or reset via `reset_global_clock`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, set_global_clock, reset_global_clock};
+///
+/// set_global_clock(|| 784_111_777);
+///
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", Datetime::new().unwrap().for_header());
+///
+/// reset_global_clock();
+/// ```
+pub fn set_global_clock(clock: impl Clock + 'static) {
+  let mut global = GLOBAL.write().unwrap_or_else(|e| e.into_inner());
+  *global = Some (Box::new(clock));
+}
+
+pub fn reset_global_clock() {
+  let mut global = GLOBAL.write().unwrap_or_else(|e| e.into_inner());
+  *global = None;
+}
+
+// FreezeGuard
+
+/// Pins the current time as read by `Datetime::raw` on the
+/// current thread to a given datetime for the lifetime of
+/// the guard, taking precedence over any global clock, with
+/// the time movable via `set` and `advance` and restored
+/// to its previous source on drop. Guards can be nested.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, FreezeGuard};
+///
+/// let guard = FreezeGuard::new(&Datetime::default());
+///
+/// assert_eq!(0, Datetime::raw().unwrap());
+///
+/// guard.advance(60);
+///
+/// assert_eq!("Thu, 01 Jan 1970 00:01:00 GMT", Datetime::new().unwrap().for_header());
+/// ```
+#[derive(Debug)]
+pub struct FreezeGuard {
+  previous: Option<u64>,
+  // pinned to the thread
+  _thread: PhantomData<*const ()>
+}
+
+impl FreezeGuard {
+
+  pub fn new(dt: &Datetime) -> Self {
+    let previous = FROZEN.replace(Some (dt.secs));
+    Self { previous, _thread: PhantomData }
+  }
+
+  pub fn set(&self, dt: &Datetime) {
+    FROZEN.set(Some (dt.secs));
+  }
+
+  pub fn advance(&self, secs: u64) {
+    FROZEN.set(FROZEN.get().map(|frozen| frozen + secs));
+  }
+}
+
+impl Drop for FreezeGuard {
+
+  fn drop(&mut self) {
+    FROZEN.set(self.previous);
+  }
+}
+
+pub(crate) fn raw() -> Result<u64, Box<dyn Error>> {
+  if let Some (frozen) = FROZEN.get() {
+    return Ok (frozen)
+  }
+  let global = GLOBAL.read().unwrap_or_else(|e| e.into_inner());
+  match global.as_ref() {
+    Some (clock) => clock.raw(),
+    None         => SystemClock.raw()
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::FreezeGuard;
+  use crate::datetime::Datetime;
+  use crate::freshness::StoredResponse;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn clock_freeze_guard() {
+
+    let guard = FreezeGuard::new(&Datetime::from(NOV_06_1994_08_49_37));
+    assert_eq!(NOV_06_1994_08_49_37, Datetime::raw().unwrap());
+
+    guard.advance(1);
+    assert_eq!(NOV_06_1994_08_49_37 + 1, Datetime::raw().unwrap());
+
+    guard.set(&Datetime::default());
+    assert_eq!(0, Datetime::raw().unwrap());
+
+    // nested
+    {
+      let _inner = FreezeGuard::new(&Datetime::from(60));
+      assert_eq!(60, Datetime::raw().unwrap());
+    }
+    assert_eq!(0, Datetime::raw().unwrap());
+
+    // other threads unaffected, where threads are supported
+    #[cfg(not(target_os = "wasi"))]
+    {
+      let raw = std::thread::spawn(|| Datetime::raw().unwrap()).join().unwrap();
+      assert!(raw > NOV_06_1994_08_49_37);
+    }
+
+    drop(guard);
+    assert!(Datetime::raw().unwrap() > NOV_06_1994_08_49_37);
+  }
+
+  #[test]
+  fn clock_freeze_guard_expiry() {
+
+    let guard = FreezeGuard::new(&Datetime::from(NOV_06_1994_08_49_37));
+
+    let now = Datetime::new().unwrap();
+    let stored = StoredResponse::new(now, now)
+      .date(now)
+      .max_age(60);
+
+    guard.advance(59);
+    assert!( stored.freshness(&Datetime::new().unwrap(), false).is_fresh());
+
+    guard.advance(1);
+    assert!(!stored.freshness(&Datetime::new().unwrap(), false).is_fresh());
+  }
+}
@@ -0,0 +1,247 @@
+//! # Conditional
+//!
+//! Evaluation of date-based conditional request headers,
+//! per RFC 9110 section 13, and a weak entity tag derived
+//! from the Last-Modified datetime.
+
+use crate::datetime::Datetime;
+
+/// Evaluates the date form of an If-Range header value
+/// against the Last-Modified datetime of the selected
+/// representation and the Date of the response, returning
+/// `true` where the Range header is to be honored (206) and
+/// `false` where the full representation is to be sent
+/// (200). Per RFC 9110 section 13.1.5, the Last-Modified
+/// datetime is a strong validator only where it is earlier
+/// than the Date, so the condition is true only for an
+/// exact match with a Last-Modified datetime earlier than
+/// the Date, and any earlier or later value, or any value
+/// which is not a valid HTTP-date, including an entity tag,
+/// results in `false`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, if_range_date};
+///
+/// let last_modified = Datetime::default();
+/// let date = last_modified.set(1);
+///
+/// assert!( if_range_date("Thu, 01 Jan 1970 00:00:00 GMT", &last_modified, &date));
+/// assert!(!if_range_date("Thu, 01 Jan 1970 00:00:00 GMT", &last_modified, &last_modified));
+/// assert!(!if_range_date("Thu, 01 Jan 1970 00:00:01 GMT", &last_modified, &date));
+/// assert!(!if_range_date("\"xyzzy\"", &last_modified, &date));
+/// ```
+pub fn if_range_date(value: &str, last_modified: &Datetime, date: &Datetime) -> bool {
+  if last_modified.secs >= date.secs {
+    return false
+  }
+  match Datetime::parse(value) {
+    Ok (dt) => dt.secs == last_modified.secs,
+    Err(_)  => false
+  }
+}
+
+/// Provides a weak entity tag for an ETag header value
+/// derived from the Last-Modified datetime and any content
+/// length, e.g. for a static file server, each in lower-case
+/// hexadecimal, as `W/"<secs>-<len>"` or `W/"<secs>"`, so
+/// changing with either.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, weak_etag};
+///
+/// let last_modified = Datetime::from(784_111_777);
+///
+/// assert_eq!("W/\"2ebc98a1-1c8\"", weak_etag(&last_modified, Some (456)));
+/// assert_eq!("W/\"2ebc98a1\"", weak_etag(&last_modified, None));
+/// ```
+pub fn weak_etag(last_modified: &Datetime, len: Option<u64>) -> String {
+  match len {
+    Some (len) => format!("W/\"{:x}-{len:x}\"", last_modified.secs),
+    None       => format!("W/\"{:x}\"", last_modified.secs)
+  }
+}
+
+// Preconditions
+
+/// Stores the conditional request headers present, with
+/// the If-Unmodified-Since and If-Modified-Since values as
+/// received, whether any entity tag in an If-Match or
+/// If-None-Match value matched, as determined by the
+/// caller, and whether the request method is GET or HEAD.
+/// The `evaluate` method determines the outcome per RFC
+/// 9110 section 13.2.2, applying the date-based headers
+/// only in the absence of the corresponding entity tag
+/// headers and ignoring any value which is not a valid
+/// HTTP-date.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, Preconditions, PreconditionOutcome};
+///
+/// let last_modified = Datetime::default();
+///
+/// let outcome = Preconditions::new(true)
+///   .if_modified_since("Thu, 01 Jan 1970 00:00:00 GMT")
+///   .evaluate(&last_modified);
+///
+/// assert_eq!(PreconditionOutcome::NotModified, outcome);
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Preconditions {
+  pub is_get_or_head: bool,
+  pub if_match: Option<bool>,
+  pub if_unmodified_since: Option<Datetime>,
+  pub if_none_match: Option<bool>,
+  pub if_modified_since: Option<Datetime>
+}
+
+impl Preconditions {
+
+  pub fn new(is_get_or_head: bool) -> Self {
+    Self {
+      is_get_or_head,
+      if_match: None,
+      if_unmodified_since: None,
+      if_none_match: None,
+      if_modified_since: None
+    }
+  }
+
+  pub fn if_match(mut self, is_match: bool) -> Self {
+    self.if_match = Some (is_match);
+    self
+  }
+
+  pub fn if_unmodified_since(mut self, value: &str) -> Self {
+    self.if_unmodified_since = Datetime::parse(value).ok();
+    self
+  }
+
+  pub fn if_none_match(mut self, is_match: bool) -> Self {
+    self.if_none_match = Some (is_match);
+    self
+  }
+
+  pub fn if_modified_since(mut self, value: &str) -> Self {
+    self.if_modified_since = Datetime::parse(value).ok();
+    self
+  }
+
+  pub fn evaluate(&self, last_modified: &Datetime) -> PreconditionOutcome {
+
+    use PreconditionOutcome::*;
+    let lm = last_modified.secs;
+
+    // steps 1 and 2
+    match (self.if_match, self.if_unmodified_since) {
+      (Some (false), _)                   => return PreconditionFailed,
+      (None, Some (ius)) if lm > ius.secs => return PreconditionFailed,
+      _                                   => ()
+    };
+
+    // steps 3 and 4
+    match (self.if_none_match, self.if_modified_since, self.is_get_or_head) {
+      (Some (true), _, true)                      => NotModified,
+      (Some (true), _, false)                     => PreconditionFailed,
+      (None, Some (ims), true) if lm <= ims.secs  => NotModified,
+      _                                           => Proceed
+    }
+  }
+}
+
+// PreconditionOutcome
+
+/// Indicates whether to proceed with the request or to
+/// respond with 304 (Not Modified) or 412 (Precondition
+/// Failed).
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PreconditionOutcome {
+  Proceed,
+  NotModified,
+  PreconditionFailed
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{if_range_date, weak_etag, Preconditions, PreconditionOutcome};
+  use crate::datetime::Datetime;
+
+  #[test]
+  fn conditional_if_range_date() {
+
+    let last_modified = Datetime::default().set(784_111_777);
+    let date          = last_modified.set(784_111_778);
+
+    assert!( if_range_date("Sun, 06 Nov 1994 08:49:37 GMT",  &last_modified, &date));
+    assert!( if_range_date("Sunday, 06-Nov-94 08:49:37 GMT", &last_modified, &date));
+    assert!( if_range_date("Sun Nov  6 08:49:37 1994",       &last_modified, &date));
+    assert!(!if_range_date("Sun, 06 Nov 1994 08:49:36 GMT",  &last_modified, &date));
+    assert!(!if_range_date("Sun, 06 Nov 1994 08:49:38 GMT",  &last_modified, &date));
+    assert!(!if_range_date("W/\"xyzzy\"",                    &last_modified, &date));
+    assert!(!if_range_date("",                               &last_modified, &date));
+
+    // weak validator, Last-Modified not earlier than Date
+    assert!(!if_range_date("Sun, 06 Nov 1994 08:49:37 GMT",  &last_modified, &last_modified));
+    assert!(!if_range_date("Sun, 06 Nov 1994 08:49:37 GMT",  &last_modified, &last_modified.set(784_111_776)));
+  }
+
+  #[test]
+  fn conditional_weak_etag() {
+
+    let last_modified = Datetime::default().set(784_111_777);
+
+    assert_eq!("W/\"2ebc98a1-0\"",   weak_etag(&last_modified, Some (0)));
+    assert_eq!("W/\"2ebc98a1-1c8\"", weak_etag(&last_modified, Some (456)));
+    assert_eq!("W/\"2ebc98a1\"",     weak_etag(&last_modified, None));
+    assert_eq!("W/\"0-ffff\"",       weak_etag(&Datetime::default(), Some (65_535)));
+    assert_ne!(weak_etag(&last_modified, Some (456)), weak_etag(&last_modified.set(784_111_778), Some (456)));
+
+    // not taken for a date by If-Range
+    assert!(!if_range_date(&weak_etag(&last_modified, None), &last_modified, &last_modified.set(784_111_778)));
+  }
+
+  #[test]
+  fn conditional_preconditions() {
+
+    let last_modified = Datetime::default().set(784_111_777);
+
+    let before = "Sun, 06 Nov 1994 08:49:36 GMT";
+    let same   = "Sun, 06 Nov 1994 08:49:37 GMT";
+    let after  = "Sun, 06 Nov 1994 08:49:38 GMT";
+
+    use PreconditionOutcome::*;
+
+    // none
+    assert_eq!(Proceed,            Preconditions::new(true).evaluate(&last_modified));
+
+    // If-Unmodified-Since
+    assert_eq!(PreconditionFailed, Preconditions::new(false).if_unmodified_since(before).evaluate(&last_modified));
+    assert_eq!(Proceed,            Preconditions::new(false).if_unmodified_since(same  ).evaluate(&last_modified));
+    assert_eq!(Proceed,            Preconditions::new(false).if_unmodified_since("now" ).evaluate(&last_modified));
+
+    // If-Unmodified-Since ignored with If-Match
+    assert_eq!(Proceed,            Preconditions::new(false).if_match(true ).if_unmodified_since(before).evaluate(&last_modified));
+    assert_eq!(PreconditionFailed, Preconditions::new(false).if_match(false).if_unmodified_since(after ).evaluate(&last_modified));
+
+    // If-Modified-Since
+    assert_eq!(Proceed,            Preconditions::new(true ).if_modified_since(before).evaluate(&last_modified));
+    assert_eq!(NotModified,        Preconditions::new(true ).if_modified_since(same  ).evaluate(&last_modified));
+    assert_eq!(NotModified,        Preconditions::new(true ).if_modified_since(after ).evaluate(&last_modified));
+    assert_eq!(Proceed,            Preconditions::new(false).if_modified_since(after ).evaluate(&last_modified));
+    assert_eq!(Proceed,            Preconditions::new(true ).if_modified_since("now" ).evaluate(&last_modified));
+
+    // If-Modified-Since ignored with If-None-Match
+    assert_eq!(Proceed,            Preconditions::new(true ).if_none_match(false).if_modified_since(after ).evaluate(&last_modified));
+    assert_eq!(NotModified,        Preconditions::new(true ).if_none_match(true ).if_modified_since(before).evaluate(&last_modified));
+    assert_eq!(PreconditionFailed, Preconditions::new(false).if_none_match(true ).evaluate(&last_modified));
+
+    // If-Unmodified-Since before If-Modified-Since
+    assert_eq!(PreconditionFailed, Preconditions::new(true ).if_unmodified_since(before).if_modified_since(after).evaluate(&last_modified));
+  }
+}
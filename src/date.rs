@@ -1,16 +1,21 @@
 use crate::time::{H_AS_S, D_AS_H};
+use crate::datetime::MAX_SECS;
+use crate::parse::{self, ParseError};
 
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
 pub const D_AS_S: u64 = H_AS_S * D_AS_H;
+// the days from the epoch to 31 Dec 9999
+const MAX_DAYS: u64 = MAX_SECS / D_AS_S;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Date {
   pub  d: u8,
   pub wd: Weekday,
   pub  m: Month,
-  pub  y: Year,
-  pub xs: u64
+  pub  y: Year
 }
 
 impl Default for Date {
@@ -20,25 +25,55 @@ impl Default for Date {
        d: 1,
       wd: Weekday::default(),
        m: Month::default(),
-       y: Year::default(),
-      xs: 0
+       y: Year::default()
+    }
+  }
+}
+
+// clamped as for the `From<u64>` implementation for
+// `Datetime`, bounding the loop by year
+impl From<u64> for Date {
+
+  fn from(init_s: u64) -> Self {
+    let mut days = init_s.min(MAX_SECS) / D_AS_S;
+    let wd = Weekday::from_days_since_epoch(days);
+    let mut y = Year::default();
+    while days >= y.days() {
+      days -= y.days();
+      y     = y.skip(1);
+    }
+    let mut m = Month::default();
+    while days >= m.len(y.is_leap()) as u64 {
+      days -= m.len(y.is_leap()) as u64;
+      m     = m.skip(1);
     }
+    Self { d: days as u8 + 1, wd, m, y }
   }
 }
 
 impl Date {
 
+  /// Skips forward by the whole days in `diff_s` seconds,
+  /// the date being taken as at midnight, the time of day
+  /// formerly held in the `xs` field no longer being held,
+  /// so that any such time must be added to `diff_s` for
+  /// the result formerly given.
+  #[deprecated(note = "the time of day is no longer held by Date; use skip_days, or Datetime::set for seconds")]
   pub fn skip(&self, diff_s: u64) -> Self {
+    self.skip_days(diff_s / D_AS_S)
+  }
+
+  pub fn skip_days(&self, diff_d: u64) -> Self {
 
-    let Date { mut d, mut wd, mut m, mut y, xs: today_s } = self;
-    let mut xs = diff_s + today_s;
+    let Date { mut d, mut wd, mut m, mut y } = self;
+    let mut days = diff_d;
 
-    if xs >= D_AS_S {
+    if days > 0 {
       'months: loop {
         let this_m_as_d = m.len(y.is_leap());
           'days: loop {
-            if      xs  < D_AS_S { break 'months }
-                    xs -= D_AS_S;
+            if      days == 0 { break 'months }
+                    days -= 1;
                     wd  = wd.skip(1);
             if  d != this_m_as_d {
                      d += 1
@@ -48,47 +83,269 @@ impl Date {
                          m = m.skip(1);
       }
     };
-    Self { d, wd, m, y, xs }
+    Self { d, wd, m, y }
+  }
+
+  /// Provides the following day, rolling over into the
+  /// next month and year as needed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Date;
+  ///
+  /// let date: Date = "Wed, 31 Dec 1997".parse().unwrap();
+  ///
+  /// assert_eq!("Thu, 01 Jan 1998", date.succ_day().for_header());
+  /// ```
+  pub fn succ_day(&self) -> Self {
+    let Date { d, wd, m, y } = *self;
+    let wd = wd.skip(1);
+    match d == m.len(y.is_leap()) {
+      true  => {
+        let y = if m.is_last() { y.skip(1) } else { y };
+        Self { d: 1, wd, m: m.skip(1), y }
+      },
+      false => Self { d: d + 1, wd, m, y }
+    }
+  }
+
+  /// Provides the preceding day, rolling back into the
+  /// previous month and year as needed, or `None` for the
+  /// first day of 1970, the earliest supported.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Date;
+  ///
+  /// let date: Date = "Sun, 01 Mar 1992".parse().unwrap();
+  ///
+  /// assert_eq!("Sat, 29 Feb 1992", date.pred_day().unwrap().for_header());
+  /// ```
+  pub fn pred_day(&self) -> Option<Self> {
+    let Date { d, wd, m, y } = *self;
+    let wd = wd.skip(6);
+    if d > 1 {
+      return Some (Self { d: d - 1, wd, m, y })
+    }
+    let y = match m == Month::Jan {
+      true  if y == Year::default() => return None,
+      true  => Year(y.0 - 1),
+      false => y
+    };
+    let m = m.skip(11);
+    Some (Self { d: m.len(y.is_leap()), wd, m, y })
+  }
+
+  /// Provides the date the given number of days later, or
+  /// `None` where beyond 9999, the latest year supported.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Date;
+  ///
+  /// let date: Date = "Sun, 06 Nov 1994".parse().unwrap();
+  ///
+  /// assert_eq!("Sat, 31 Dec 1994", date.add_days(55).unwrap().for_header());
+  /// assert_eq!("Sun, 04 Dec 1994", date.add_weeks(4).unwrap().for_header());
+  /// assert_eq!("Sat, 01 Oct 1994", date.sub_days(36).unwrap().for_header());
+  /// ```
+  pub fn add_days(&self, days: u64) -> Option<Self> {
+    let days = self.days_since_epoch().checked_add(days)?;
+    match days <= MAX_DAYS {
+      true  => Some (Self::from(days * D_AS_S)),
+      false => None
+    }
+  }
+
+  /// Provides the date the given number of days earlier,
+  /// or `None` where before 1970, the earliest supported.
+  pub fn sub_days(&self, days: u64) -> Option<Self> {
+    let days = self.days_since_epoch().checked_sub(days)?;
+    Some (Self::from(days * D_AS_S))
+  }
+
+  pub fn add_weeks(&self, weeks: u64) -> Option<Self> {
+    self.add_days(weeks.checked_mul(7)?)
+  }
+
+  /// Provides the date of the nth occurrence of the weekday
+  /// in the month, counting from 1, e.g. the second Tuesday
+  /// for a monthly maintenance window, or `None` where `n`
+  /// is 0 or beyond the occurrences in the month.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Date, Weekday, Month, Year};
+  ///
+  /// let date = Date::nth_weekday(Year(1994), Month::Nov, Weekday::Tue, 2).unwrap();
+  ///
+  /// assert_eq!("Tue, 08 Nov 1994", date.for_header());
+  /// assert!(date.is_nth_weekday(Weekday::Tue, 2));
+  /// ```
+  pub fn nth_weekday(y: Year, m: Month, wd: Weekday, n: u8) -> Option<Self> {
+    if n == 0 { return None };
+    let first = Self { d: 1, wd: Weekday::default(), m, y }.computed_weekday();
+    let offset = (iso_weekday(wd) + 7 - iso_weekday(first)) % 7;
+    let d = 1 + offset + (n as u64 - 1) * 7;
+    match d <= m.len(y.is_leap()) as u64 {
+      true  => Some (Self { d: d as u8, wd, m, y }),
+      false => None
+    }
+  }
+
+  /// Indicates whether the date is the nth occurrence of
+  /// the weekday in its month, counting from 1.
+  pub fn is_nth_weekday(&self, wd: Weekday, n: u8) -> bool {
+    self.wd == wd && n > 0 && self.d.checked_sub(1).is_some_and(|d| d / 7 + 1 == n)
   }
 
   pub fn for_header(&self) -> String {
     ImfFixdateDate(self).to_string()
   }
+
+  /// Sets the weekday to that of the year, month and day,
+  /// e.g. to repair a date constructed externally or
+  /// deserialized, using Sakamoto's algorithm.
+  pub fn recompute_weekday(&mut self) {
+    self.wd = self.computed_weekday();
+  }
+
+  /// Indicates whether the weekday is that of the year,
+  /// month and day, as for `recompute_weekday`.
+  pub fn is_weekday_valid(&self) -> bool {
+    self.wd == self.computed_weekday()
+  }
+
+  fn computed_weekday(&self) -> Weekday {
+    const T: [u64; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let m = Month::iter().position(|m| m == self.m).unwrap_or_default();
+    let Year(y) = self.y;
+    let y = if m < 2 { y.saturating_sub(1) } else { y };
+    let from_sun = (y + y / 4 - y / 100 + y / 400 + T[m] + self.d as u64) % 7;
+    // Sunday being three days after Thursday, the epoch
+    Weekday::from_days_since_epoch(from_sun + 3)
+  }
+
+  /// Provides the number of whole days from the Unix epoch
+  /// to the date, e.g. as a key for daily partitions.
+  pub fn days_since_epoch(&self) -> u64 {
+    self.y.days_before() + self.m.days_before(self.y.is_leap()) + self.d as u64 - 1
+  }
+
+  /// Provides the number of seconds from the epoch to the
+  /// start of the day, derived from the date in place of
+  /// the `xs` field formerly held, such that `date.xs()`
+  /// and `time.xs()` sum to the seconds of the datetime.
+  pub fn xs(&self) -> u64 {
+    self.days_since_epoch() * D_AS_S
+  }
+}
+
+impl FromStr for Date {
+  type Err = ParseError;
+
+  /// Parses a date as in the IMF-fixdate, e.g. `06 Nov 1994`,
+  /// optionally preceded by the weekday, e.g. `Sun, `,
+  /// which is then checked against the date.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse::parse_date(s)
+  }
 }
 
 // ImfFixdateDate
 
-pub struct ImfFixdateDate<'a>(&'a Date);
+pub(crate) struct ImfFixdateDate<'a>(pub(crate) &'a Date);
 
 impl Display for ImfFixdateDate<'_> {
 
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     let ImfFixdateDate(d) = self;
-    write!(f, "{:?}, {:02} {:?} {}", d.wd, d.d, d.m, d.y)
+    write!(f, "{}, {:02} {} {}", d.wd.abbrev(), d.d, d.m.abbrev(), d.y)
   }
 }
 
 // Weekday
 
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub enum Weekday {
   Mon,
   Tue,
   Wed,
+  #[default]
   Thu,
   Fri,
   Sat,
   Sun
 }
 
-impl Default for Weekday {
+impl Weekday {
 
-  fn default() -> Self {
-    Self::Thu
+  /// Provides each weekday in order, from Monday.
+  pub fn iter() -> impl Iterator<Item = Self> {
+    [
+      Self::Mon, Self::Tue, Self::Wed, Self::Thu,
+      Self::Fri, Self::Sat, Self::Sun
+    ].into_iter()
   }
-}
 
-impl Weekday {
+  /// Provides the three-letter name, e.g. `Mon`, as used
+  /// in the IMF-fixdate and asctime formats.
+  pub fn abbrev(&self) -> &'static str {
+    match self {
+      Self::Mon => "Mon",
+      Self::Tue => "Tue",
+      Self::Wed => "Wed",
+      Self::Thu => "Thu",
+      Self::Fri => "Fri",
+      Self::Sat => "Sat",
+      Self::Sun => "Sun"
+    }
+  }
+
+  /// Provides the weekday the given number of days after
+  /// the epoch, a Thursday, without iteration.
+  pub fn from_days_since_epoch(days: u64) -> Self {
+    const FROM_THU: [Weekday; 7] = [
+      Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun,
+      Weekday::Mon, Weekday::Tue, Weekday::Wed
+    ];
+    FROM_THU[(days % 7) as usize]
+  }
+
+  /// Matches the three-letter name, e.g. `Mon`, exactly,
+  /// via a single comparison of the token packed as an
+  /// integer.
+  pub fn from_abbrev(token: &[u8; 3]) -> Option<Self> {
+    match pack(token) {
+      0x4D6F6E => Some (Self::Mon),
+      0x547565 => Some (Self::Tue),
+      0x576564 => Some (Self::Wed),
+      0x546875 => Some (Self::Thu),
+      0x467269 => Some (Self::Fri),
+      0x536174 => Some (Self::Sat),
+      0x53756E => Some (Self::Sun),
+      _        => None
+    }
+  }
+
+  /// Provides the full name, e.g. `Monday`, as used in
+  /// the RFC 850 format.
+  pub fn full_name(&self) -> &'static str {
+    match self {
+      Self::Mon => "Monday",
+      Self::Tue => "Tuesday",
+      Self::Wed => "Wednesday",
+      Self::Thu => "Thursday",
+      Self::Fri => "Friday",
+      Self::Sat => "Saturday",
+      Self::Sun => "Sunday"
+    }
+  }
 
   pub fn skip(&self, diff_d: u64) -> Self {
     let mut current = self;
@@ -107,10 +364,21 @@ impl Weekday {
   }
 }
 
+// days since Monday
+pub(crate) fn iso_weekday(wd: Weekday) -> u64 {
+  Weekday::iter().position(|w| w == wd).unwrap_or_default() as u64
+}
+
+fn pack(token: &[u8; 3]) -> u32 {
+  u32::from_be_bytes([0, token[0], token[1], token[2]])
+}
+
 // Month
 
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub enum Month {
+  #[default]
   Jan,
   Feb,
   Mar,
@@ -125,15 +393,75 @@ pub enum Month {
   Dec
 }
 
-impl Default for Month {
+impl Month {
 
-  fn default() -> Self {
-    Self::Jan
+  /// Provides each month in order, from January.
+  pub fn iter() -> impl Iterator<Item = Self> {
+    [
+      Self::Jan, Self::Feb, Self::Mar, Self::Apr, Self::May, Self::Jun,
+      Self::Jul, Self::Aug, Self::Sep, Self::Oct, Self::Nov, Self::Dec
+    ].into_iter()
   }
-}
 
-impl Month {
+  /// Provides the three-letter name, e.g. `Jan`, as used
+  /// in each of the HTTP Date header formats.
+  pub fn abbrev(&self) -> &'static str {
+    match self {
+      Self::Jan => "Jan",
+      Self::Feb => "Feb",
+      Self::Mar => "Mar",
+      Self::Apr => "Apr",
+      Self::May => "May",
+      Self::Jun => "Jun",
+      Self::Jul => "Jul",
+      Self::Aug => "Aug",
+      Self::Sep => "Sep",
+      Self::Oct => "Oct",
+      Self::Nov => "Nov",
+      Self::Dec => "Dec"
+    }
+  }
 
+  /// Matches the three-letter name, e.g. `Jan`, exactly,
+  /// as for `Weekday::from_abbrev`.
+  pub fn from_abbrev(token: &[u8; 3]) -> Option<Self> {
+    match pack(token) {
+      0x4A616E => Some (Self::Jan),
+      0x466562 => Some (Self::Feb),
+      0x4D6172 => Some (Self::Mar),
+      0x417072 => Some (Self::Apr),
+      0x4D6179 => Some (Self::May),
+      0x4A756E => Some (Self::Jun),
+      0x4A756C => Some (Self::Jul),
+      0x417567 => Some (Self::Aug),
+      0x536570 => Some (Self::Sep),
+      0x4F6374 => Some (Self::Oct),
+      0x4E6F76 => Some (Self::Nov),
+      0x446563 => Some (Self::Dec),
+      _        => None
+    }
+  }
+
+  pub fn full_name(&self) -> &'static str {
+    match self {
+      Self::Jan => "January",
+      Self::Feb => "February",
+      Self::Mar => "March",
+      Self::Apr => "April",
+      Self::May => "May",
+      Self::Jun => "June",
+      Self::Jul => "July",
+      Self::Aug => "August",
+      Self::Sep => "September",
+      Self::Oct => "October",
+      Self::Nov => "November",
+      Self::Dec => "December"
+    }
+  }
+
+  /// Provides the number of days in the month, given
+  /// whether the year is a leap year, e.g. for validating
+  /// externally supplied dates.
   pub fn len(&self, is_leap_year: bool) -> u8 {
     match self {
       Self::Jan | Self::Mar | Self::May | Self::Jul |
@@ -168,11 +496,22 @@ impl Month {
   pub fn is_last(&self) -> bool {
     *self == Month::Dec
   }
+
+  pub(crate) fn days_before(&self, is_leap_year: bool) -> u64 {
+    let mut current = Self::default();
+    let mut days = 0;
+    while current != *self {
+      days   += current.len(is_leap_year) as u64;
+      current = current.skip(1);
+    }
+    days
+  }
 }
 
 // Year
 
 #[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Year(pub u64);
 
 impl Default for Year {
@@ -189,10 +528,66 @@ impl Year {
     Self(y + diff_y)
   }
 
+  /// Indicates whether the year is a leap year in the
+  /// Gregorian calendar, i.e. divisible by 4 other than
+  /// centuries not divisible by 400.
   pub fn is_leap(&self) -> bool {
     let Year(y) = self;
     y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
   }
+
+  pub(crate) fn days(&self) -> u64 {
+    if self.is_leap() { 366 } else { 365 }
+  }
+
+  /// Provides the number of ISO 8601 weeks in the ISO year,
+  /// i.e. 53 where the year starts on a Thursday, or on a
+  /// Wednesday if a leap year, and 52 otherwise, e.g. for
+  /// sizing week-based reports.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Year;
+  ///
+  /// assert_eq!(53, Year(2020).iso_weeks());
+  /// assert_eq!(52, Year(2024).iso_weeks());
+  /// assert!(Year(2026).is_long_year());
+  /// ```
+  pub fn iso_weeks(&self) -> u8 {
+    let jan_01 = Date { d: 1, wd: Weekday::default(), m: Month::Jan, y: *self }.computed_weekday();
+    match (jan_01, self.is_leap()) {
+      (Weekday::Thu, _) | (Weekday::Wed, true) => 53,
+      _                                        => 52
+    }
+  }
+
+  /// Indicates whether the ISO year has 53 weeks.
+  pub fn is_long_year(&self) -> bool {
+    self.iso_weeks() == 53
+  }
+
+  pub(crate) fn days_before(&self) -> u64 {
+    let Year(y) = self;
+    let leaps = |y: u64| y / 4 - y / 100 + y / 400;
+    (y - 1970) * 365 + leaps(y - 1) - leaps(1969)
+  }
+}
+
+/// Provides the number of days in the year, i.e. 366 for
+/// a leap year and 365 otherwise.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Year, Month, days_in_year};
+///
+/// assert_eq!(366, days_in_year(Year(2000)));
+/// assert_eq!(365, days_in_year(Year(2100)));
+/// assert_eq!(29, Month::Feb.len(Year(2024).is_leap()));
+/// ```
+pub fn days_in_year(y: Year) -> u64 {
+  y.days()
 }
 
 impl Display for Year {
@@ -206,38 +601,46 @@ impl Display for Year {
 #[cfg(test)]
 pub mod test {
 
-  use super::{Date, Weekday, Month, Year, D_AS_S};
+  use super::{Date, Weekday, Month, Year, days_in_year, D_AS_S, MAX_DAYS};
+
+  pub const M_28_AS_D: u64  =  28;
+  pub const M_29_AS_D: u64  =  29;
+  pub const M_30_AS_D: u64  =  30;
+  pub const M_31_AS_D: u64  =  31;
+
+  pub const Y_365_AS_D: u64 = 365;
+  pub const Y_366_AS_D: u64 = 366;
 
-  pub const M_28_AS_S: u64  = D_AS_S *  28;
-  pub const M_29_AS_S: u64  = D_AS_S *  29;
-  pub const M_30_AS_S: u64  = D_AS_S *  30;
-  pub const M_31_AS_S: u64  = D_AS_S *  31;
+  pub const M_28_AS_S: u64  = D_AS_S * M_28_AS_D;
+  pub const M_29_AS_S: u64  = D_AS_S * M_29_AS_D;
+  pub const M_30_AS_S: u64  = D_AS_S * M_30_AS_D;
+  pub const M_31_AS_S: u64  = D_AS_S * M_31_AS_D;
 
-  pub const Y_365_AS_S: u64 = D_AS_S * 365;
-  pub const Y_366_AS_S: u64 = D_AS_S * 366;
+  pub const Y_365_AS_S: u64 = D_AS_S * Y_365_AS_D;
+  pub const Y_366_AS_S: u64 = D_AS_S * Y_366_AS_D;
 
   // 1970
-  pub const JAN_01_1970_00_00_00: Date = Date { wd: Weekday::Thu, d:  1, m: Month::Jan, y: Year(1970), xs:          0 };
-  pub const FEB_28_1970_23_59_59: Date = Date { wd: Weekday::Sat, d: 28, m: Month::Feb, y: Year(1970), xs: D_AS_S - 1 };
-  pub const MAR_01_1970_00_00_00: Date = Date { wd: Weekday::Sun, d:  1, m: Month::Mar, y: Year(1970), xs:          0 };
-  pub const APR_30_1970_23_59_59: Date = Date { wd: Weekday::Thu, d: 30, m: Month::Apr, y: Year(1970), xs: D_AS_S - 1 };
-  pub const MAY_01_1970_00_00_00: Date = Date { wd: Weekday::Fri, d:  1, m: Month::May, y: Year(1970), xs:          0 };
-  pub const JUL_31_1970_23_59_59: Date = Date { wd: Weekday::Fri, d: 31, m: Month::Jul, y: Year(1970), xs: D_AS_S - 1 };
-  pub const SEP_01_1970_00_00_00: Date = Date { wd: Weekday::Tue, d:  1, m: Month::Sep, y: Year(1970), xs:          0 };
-  pub const DEC_31_1970_23_59_59: Date = Date { wd: Weekday::Thu, d: 31, m: Month::Dec, y: Year(1970), xs: D_AS_S - 1 };
+  pub const JAN_01_1970_00_00_00: Date = Date { wd: Weekday::Thu, d:  1, m: Month::Jan, y: Year(1970) };
+  pub const FEB_28_1970_23_59_59: Date = Date { wd: Weekday::Sat, d: 28, m: Month::Feb, y: Year(1970) };
+  pub const MAR_01_1970_00_00_00: Date = Date { wd: Weekday::Sun, d:  1, m: Month::Mar, y: Year(1970) };
+  pub const APR_30_1970_23_59_59: Date = Date { wd: Weekday::Thu, d: 30, m: Month::Apr, y: Year(1970) };
+  pub const MAY_01_1970_00_00_00: Date = Date { wd: Weekday::Fri, d:  1, m: Month::May, y: Year(1970) };
+  pub const JUL_31_1970_23_59_59: Date = Date { wd: Weekday::Fri, d: 31, m: Month::Jul, y: Year(1970) };
+  pub const SEP_01_1970_00_00_00: Date = Date { wd: Weekday::Tue, d:  1, m: Month::Sep, y: Year(1970) };
+  pub const DEC_31_1970_23_59_59: Date = Date { wd: Weekday::Thu, d: 31, m: Month::Dec, y: Year(1970) };
 
   // 1972
-  pub const JAN_01_1972_00_00_00: Date = Date { wd: Weekday::Sat, d:  1, m: Month::Jan, y: Year(1972), xs:          0 };
-  pub const FEB_29_1972_23_59_59: Date = Date { wd: Weekday::Tue, d: 29, m: Month::Feb, y: Year(1972), xs: D_AS_S - 1 };
-  pub const MAR_01_1972_00_00_00: Date = Date { wd: Weekday::Wed, d:  1, m: Month::Mar, y: Year(1972), xs:          0 };
-  pub const DEC_31_1972_23_59_59: Date = Date { wd: Weekday::Sun, d: 31, m: Month::Dec, y: Year(1972), xs: D_AS_S - 1 };
+  pub const JAN_01_1972_00_00_00: Date = Date { wd: Weekday::Sat, d:  1, m: Month::Jan, y: Year(1972) };
+  pub const FEB_29_1972_23_59_59: Date = Date { wd: Weekday::Tue, d: 29, m: Month::Feb, y: Year(1972) };
+  pub const MAR_01_1972_00_00_00: Date = Date { wd: Weekday::Wed, d:  1, m: Month::Mar, y: Year(1972) };
+  pub const DEC_31_1972_23_59_59: Date = Date { wd: Weekday::Sun, d: 31, m: Month::Dec, y: Year(1972) };
 
   // 2000
-  pub const JAN_01_2000_00_00_00: Date = Date { wd: Weekday::Sat, d:  1, m: Month::Jan, y: Year(2000), xs:          0 };
-  pub const DEC_31_2000_23_59_59: Date = Date { wd: Weekday::Sun, d: 31, m: Month::Dec, y: Year(2000), xs: D_AS_S - 1 };
+  pub const JAN_01_2000_00_00_00: Date = Date { wd: Weekday::Sat, d:  1, m: Month::Jan, y: Year(2000) };
+  pub const DEC_31_2000_23_59_59: Date = Date { wd: Weekday::Sun, d: 31, m: Month::Dec, y: Year(2000) };
 
   // 2024
-  pub const DEC_31_2024_23_59_59: Date = Date { wd: Weekday::Tue, d: 31, m: Month::Dec, y: Year(2024), xs: D_AS_S - 1 };
+  pub const DEC_31_2024_23_59_59: Date = Date { wd: Weekday::Tue, d: 31, m: Month::Dec, y: Year(2024) };
 
   #[test]
   fn date_default() {
@@ -246,8 +649,91 @@ pub mod test {
   }
 
   #[test]
+  fn date_from() {
+
+    // 1970
+    assert_eq!(JAN_01_1970_00_00_00, Date::from(                                                              0));
+    assert_eq!(FEB_28_1970_23_59_59, Date::from(                  M_31_AS_S                     + M_28_AS_S - 1));
+    assert_eq!(MAR_01_1970_00_00_00, Date::from(                  M_31_AS_S                     + M_28_AS_S    ));
+    assert_eq!(APR_30_1970_23_59_59, Date::from(                  M_31_AS_S * 2 + M_30_AS_S     + M_28_AS_S - 1));
+    assert_eq!(MAY_01_1970_00_00_00, Date::from(                  M_31_AS_S * 2 + M_30_AS_S     + M_28_AS_S    ));
+    assert_eq!(JUL_31_1970_23_59_59, Date::from(                  M_31_AS_S * 4 + M_30_AS_S * 2 + M_28_AS_S - 1));
+    assert_eq!(SEP_01_1970_00_00_00, Date::from(                  M_31_AS_S * 5 + M_30_AS_S * 2 + M_28_AS_S    ));
+    assert_eq!(DEC_31_1970_23_59_59, Date::from(Y_365_AS_S                                                  - 1));
+
+    // 1972
+    assert_eq!(JAN_01_1972_00_00_00, Date::from(Y_365_AS_S *  2                                                ));
+    assert_eq!(FEB_29_1972_23_59_59, Date::from(Y_365_AS_S *  2                 + M_31_AS_S     + M_29_AS_S - 1));
+    assert_eq!(MAR_01_1972_00_00_00, Date::from(Y_365_AS_S *  2                 + M_31_AS_S     + M_29_AS_S    ));
+    assert_eq!(DEC_31_1972_23_59_59, Date::from(Y_365_AS_S *  2 + Y_366_AS_S                                - 1));
+
+    // 2000
+    assert_eq!(JAN_01_2000_00_00_00, Date::from(Y_365_AS_S * 23 + Y_366_AS_S *  7                              ));
+    assert_eq!(DEC_31_2000_23_59_59, Date::from(Y_365_AS_S * 23 + Y_366_AS_S *  8                           - 1));
+
+    // 2024
+    assert_eq!(DEC_31_2024_23_59_59, Date::from(Y_365_AS_S * 41 + Y_366_AS_S * 14                           - 1));
+  }
+
+  #[test]
+  fn date_skip_days() {
+
+    // individual, each from the Unix epoch
+
+    // 1970
+    assert_eq!(JAN_01_1970_00_00_00, JAN_01_1970_00_00_00.skip_days(                                                              0));
+    assert_eq!(FEB_28_1970_23_59_59, JAN_01_1970_00_00_00.skip_days(                  M_31_AS_D                     + M_28_AS_D - 1));
+    assert_eq!(MAR_01_1970_00_00_00, JAN_01_1970_00_00_00.skip_days(                  M_31_AS_D                     + M_28_AS_D    ));
+    assert_eq!(APR_30_1970_23_59_59, JAN_01_1970_00_00_00.skip_days(                  M_31_AS_D * 2 + M_30_AS_D     + M_28_AS_D - 1));
+    assert_eq!(MAY_01_1970_00_00_00, JAN_01_1970_00_00_00.skip_days(                  M_31_AS_D * 2 + M_30_AS_D     + M_28_AS_D    ));
+    assert_eq!(JUL_31_1970_23_59_59, JAN_01_1970_00_00_00.skip_days(                  M_31_AS_D * 4 + M_30_AS_D * 2 + M_28_AS_D - 1));
+    assert_eq!(SEP_01_1970_00_00_00, JAN_01_1970_00_00_00.skip_days(                  M_31_AS_D * 5 + M_30_AS_D * 2 + M_28_AS_D    ));
+    assert_eq!(DEC_31_1970_23_59_59, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D                                                  - 1));
+
+    // 1972
+    assert_eq!(JAN_01_1972_00_00_00, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D *  2                                                ));
+    assert_eq!(FEB_29_1972_23_59_59, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D *  2                 + M_31_AS_D     + M_29_AS_D - 1));
+    assert_eq!(MAR_01_1972_00_00_00, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D *  2                 + M_31_AS_D     + M_29_AS_D    ));
+    assert_eq!(DEC_31_1972_23_59_59, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D *  2 + Y_366_AS_D                                - 1));
+
+    // 2000
+    assert_eq!(JAN_01_2000_00_00_00, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D * 23 + Y_366_AS_D *  7                              ));
+    assert_eq!(DEC_31_2000_23_59_59, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D * 23 + Y_366_AS_D *  8                           - 1));
+
+    // 2024
+    assert_eq!(DEC_31_2024_23_59_59, JAN_01_1970_00_00_00.skip_days(Y_365_AS_D * 41 + Y_366_AS_D * 14                           - 1));
+
+    // sequential, each from the preceding value
+
+    // 1970
+    assert_eq!(MAR_01_1970_00_00_00, FEB_28_1970_23_59_59.skip_days(                                                              1));
+    assert_eq!(APR_30_1970_23_59_59, MAR_01_1970_00_00_00.skip_days(                  M_31_AS_D     + M_30_AS_D                 - 1));
+    assert_eq!(MAY_01_1970_00_00_00, APR_30_1970_23_59_59.skip_days(                                                              1));
+    assert_eq!(JUL_31_1970_23_59_59, MAY_01_1970_00_00_00.skip_days(                  M_31_AS_D * 2 + M_30_AS_D                 - 1));
+    assert_eq!(SEP_01_1970_00_00_00, JUL_31_1970_23_59_59.skip_days(                  M_31_AS_D                                 + 1));
+    assert_eq!(DEC_31_1970_23_59_59, SEP_01_1970_00_00_00.skip_days(                  M_31_AS_D * 2 + M_30_AS_D * 2             - 1));
+
+    // 1972
+    assert_eq!(JAN_01_1972_00_00_00, DEC_31_1970_23_59_59.skip_days(Y_365_AS_D                                                  + 1));
+    assert_eq!(FEB_29_1972_23_59_59, JAN_01_1972_00_00_00.skip_days(                  M_31_AS_D                     + M_29_AS_D - 1));
+    assert_eq!(MAR_01_1972_00_00_00, FEB_29_1972_23_59_59.skip_days(                                                              1));
+    assert_eq!(DEC_31_1972_23_59_59, MAR_01_1972_00_00_00.skip_days(                  M_31_AS_D * 6 + M_30_AS_D * 4             - 1));
+
+    // 2000
+    assert_eq!(JAN_01_2000_00_00_00, DEC_31_1972_23_59_59.skip_days(Y_365_AS_D * 21 + Y_366_AS_D *  6                           + 1));
+    assert_eq!(DEC_31_2000_23_59_59, JAN_01_2000_00_00_00.skip_days(                  M_31_AS_D * 7 + M_30_AS_D * 4 + M_29_AS_D - 1));
+
+    // 2024
+    assert_eq!(DEC_31_2024_23_59_59, DEC_31_2000_23_59_59.skip_days(Y_365_AS_D * 18 + Y_366_AS_D *  6                              ));
+  }
+
+  #[test]
+  #[allow(deprecated)]
   fn date_skip() {
 
+    // the time of day formerly held by each date at 23:59:59, now passed
+    const XS: u64 = D_AS_S - 1;
+
     // individual, each from the Unix epoch
 
     // 1970
@@ -276,27 +762,28 @@ pub mod test {
     // sequential, each from the preceding value
 
     // 1970
-    assert_eq!(MAR_01_1970_00_00_00, FEB_28_1970_23_59_59.skip(                                                              1));
+    assert_eq!(MAR_01_1970_00_00_00, FEB_28_1970_23_59_59.skip(XS +                                                          1));
     assert_eq!(APR_30_1970_23_59_59, MAR_01_1970_00_00_00.skip(                  M_31_AS_S     + M_30_AS_S                 - 1));
-    assert_eq!(MAY_01_1970_00_00_00, APR_30_1970_23_59_59.skip(                                                              1));
+    assert_eq!(MAY_01_1970_00_00_00, APR_30_1970_23_59_59.skip(XS +                                                          1));
     assert_eq!(JUL_31_1970_23_59_59, MAY_01_1970_00_00_00.skip(                  M_31_AS_S * 2 + M_30_AS_S                 - 1));
-    assert_eq!(SEP_01_1970_00_00_00, JUL_31_1970_23_59_59.skip(                  M_31_AS_S                                 + 1));
+    assert_eq!(SEP_01_1970_00_00_00, JUL_31_1970_23_59_59.skip(XS +              M_31_AS_S                                 + 1));
     assert_eq!(DEC_31_1970_23_59_59, SEP_01_1970_00_00_00.skip(                  M_31_AS_S * 2 + M_30_AS_S * 2             - 1));
 
     // 1972
-    assert_eq!(JAN_01_1972_00_00_00, DEC_31_1970_23_59_59.skip(Y_365_AS_S                                                  + 1));
+    assert_eq!(JAN_01_1972_00_00_00, DEC_31_1970_23_59_59.skip(XS + Y_365_AS_S                                             + 1));
     assert_eq!(FEB_29_1972_23_59_59, JAN_01_1972_00_00_00.skip(                  M_31_AS_S                     + M_29_AS_S - 1));
-    assert_eq!(MAR_01_1972_00_00_00, FEB_29_1972_23_59_59.skip(                                                              1));
+    assert_eq!(MAR_01_1972_00_00_00, FEB_29_1972_23_59_59.skip(XS +                                                          1));
     assert_eq!(DEC_31_1972_23_59_59, MAR_01_1972_00_00_00.skip(                  M_31_AS_S * 6 + M_30_AS_S * 4             - 1));
 
     // 2000
-    assert_eq!(JAN_01_2000_00_00_00, DEC_31_1972_23_59_59.skip(Y_365_AS_S * 21 + Y_366_AS_S *  6                           + 1));
+    assert_eq!(JAN_01_2000_00_00_00, DEC_31_1972_23_59_59.skip(XS + Y_365_AS_S * 21 + Y_366_AS_S *  6                      + 1));
     assert_eq!(DEC_31_2000_23_59_59, JAN_01_2000_00_00_00.skip(                  M_31_AS_S * 7 + M_30_AS_S * 4 + M_29_AS_S - 1));
 
     // 2024
-    assert_eq!(DEC_31_2024_23_59_59, DEC_31_2000_23_59_59.skip(Y_365_AS_S * 18 + Y_366_AS_S *  6                              ));
+    assert_eq!(DEC_31_2024_23_59_59, DEC_31_2000_23_59_59.skip(XS + Y_365_AS_S * 18 + Y_366_AS_S *  6                         ));
   }
 
+
   #[test]
   fn date_for_header() {
 
@@ -323,4 +810,257 @@ pub mod test {
     // 2024
     assert_eq!(String::from("Tue, 31 Dec 2024"), DEC_31_2024_23_59_59.for_header());
   }
+
+  #[test]
+  fn date_is_leap() {
+
+    assert!(!Year(1970).is_leap());
+    assert!( Year(1972).is_leap());
+    assert!(!Year(1900).is_leap());
+    assert!( Year(2000).is_leap());
+    assert!(!Year(2100).is_leap());
+    assert!( Year(2024).is_leap());
+  }
+
+  #[test]
+  fn date_iso_weeks() {
+
+    let long = [1964, 1970, 1976, 1981, 1987, 1992, 1998, 2004, 2009, 2015, 2020, 2026];
+
+    for y in 1960..=2030 {
+      assert_eq!(long.contains(&y), Year(y).is_long_year());
+      assert_eq!(if long.contains(&y) { 53 } else { 52 }, Year(y).iso_weeks());
+    }
+  }
+
+  #[test]
+  fn date_days_in_year() {
+
+    assert_eq!(365, days_in_year(Year(1970)));
+    assert_eq!(366, days_in_year(Year(1972)));
+    assert_eq!(365, days_in_year(Year(2100)));
+    assert_eq!(366, days_in_year(Year(2000)));
+
+    for y in [Year(1970), Year(1972), Year(2000), Year(2100)] {
+      let sum = Month::iter().map(|m| m.len(y.is_leap()) as u64).sum::<u64>();
+      assert_eq!(days_in_year(y), sum);
+    }
+    assert_eq!(28, Month::Feb.len(false));
+    assert_eq!(29, Month::Feb.len(true));
+    assert_eq!(30, Month::Apr.len(true));
+    assert_eq!(31, Month::Dec.len(false));
+  }
+
+  #[test]
+  fn date_days_since_epoch() {
+
+    assert_eq!(    0, JAN_01_1970_00_00_00.days_since_epoch());
+    assert_eq!(   58, FEB_28_1970_23_59_59.days_since_epoch());
+    assert_eq!(  364, DEC_31_1970_23_59_59.days_since_epoch());
+    assert_eq!(  730, JAN_01_1972_00_00_00.days_since_epoch());
+    assert_eq!(  789, FEB_29_1972_23_59_59.days_since_epoch());
+    assert_eq!(10957, JAN_01_2000_00_00_00.days_since_epoch());
+    assert_eq!(20088, DEC_31_2024_23_59_59.days_since_epoch());
+    assert_eq!(20088 * D_AS_S, DEC_31_2024_23_59_59.xs());
+
+    for date in [JAN_01_1970_00_00_00, FEB_28_1970_23_59_59, FEB_29_1972_23_59_59, DEC_31_2024_23_59_59] {
+      assert_eq!(date.wd, Weekday::from_days_since_epoch(date.days_since_epoch()));
+    }
+  }
+
+  #[test]
+  fn date_succ_pred_day() {
+
+    let pairs = [
+      (FEB_28_1970_23_59_59, MAR_01_1970_00_00_00),
+      (APR_30_1970_23_59_59, MAY_01_1970_00_00_00),
+      (DEC_31_1970_23_59_59, Date { wd: Weekday::Fri, d: 1, m: Month::Jan, y: Year(1971) }),
+      (FEB_29_1972_23_59_59, MAR_01_1972_00_00_00),
+      (DEC_31_2000_23_59_59, Date { wd: Weekday::Mon, d: 1, m: Month::Jan, y: Year(2001) })
+    ];
+
+    for (date, next) in pairs {
+      assert_eq!(next, date.succ_day());
+      assert_eq!(Some (date), next.pred_day());
+    }
+
+    assert_eq!(None, JAN_01_1970_00_00_00.pred_day());
+
+    // as for skip_days
+    let mut date = JAN_01_1970_00_00_00;
+    for days in 1..=Y_366_AS_D * 4 {
+      date = date.succ_day();
+      assert_eq!(JAN_01_1970_00_00_00.skip_days(days), date);
+    }
+    for _ in 0..Y_366_AS_D * 4 {
+      date = date.pred_day().unwrap();
+    }
+    assert_eq!(JAN_01_1970_00_00_00, date);
+  }
+
+  #[test]
+  fn date_add_sub_days() {
+
+    let pairs = [
+      (JAN_01_1970_00_00_00, FEB_28_1970_23_59_59,  58),
+      (FEB_28_1970_23_59_59, DEC_31_1970_23_59_59, 306),
+      (DEC_31_1970_23_59_59, FEB_29_1972_23_59_59, 425),
+      (FEB_29_1972_23_59_59, DEC_31_2024_23_59_59, Y_365_AS_D * 39 + Y_366_AS_D * 14 - 60)
+    ];
+
+    for (date, later, days) in pairs {
+      assert_eq!(Some (later), date.add_days(days));
+      assert_eq!(Some (date),  later.sub_days(days));
+      assert_eq!(date.skip_days(days), later);
+    }
+
+    assert_eq!(Some (JAN_01_1970_00_00_00), JAN_01_1970_00_00_00.add_days(0));
+    assert_eq!(None, JAN_01_1970_00_00_00.sub_days(1));
+    assert_eq!(None, DEC_31_2024_23_59_59.add_days(u64::MAX));
+
+    // to and beyond 31 Dec 9999
+    let last = JAN_01_1970_00_00_00.add_days(MAX_DAYS).unwrap();
+    assert_eq!(Date { wd: Weekday::Fri, d: 31, m: Month::Dec, y: Year(9999) }, last);
+    assert_eq!(None, last.add_days(1));
+
+    assert_eq!(JAN_01_1970_00_00_00.add_days(14), JAN_01_1970_00_00_00.add_weeks(2));
+    assert_eq!(Weekday::Thu, DEC_31_1970_23_59_59.add_weeks(52).unwrap().wd);
+    assert_eq!(None, JAN_01_1970_00_00_00.add_weeks(u64::MAX));
+  }
+
+  #[test]
+  fn date_nth_weekday() {
+
+    let cases = [
+      (Year(1994), Month::Nov, Weekday::Tue, 2, Some ( 8)),
+      (Year(1994), Month::Nov, Weekday::Sun, 1, Some ( 6)),
+      (Year(1994), Month::Nov, Weekday::Mon, 4, Some (28)),
+      (Year(1994), Month::Nov, Weekday::Tue, 5, Some (29)),
+      (Year(1994), Month::Nov, Weekday::Wed, 5, Some (30)),
+      (Year(1994), Month::Nov, Weekday::Thu, 5, None     ),
+      (Year(2024), Month::Feb, Weekday::Thu, 5, Some (29)),
+      (Year(2023), Month::Feb, Weekday::Wed, 5, None     ),
+      (Year(1970), Month::Jan, Weekday::Thu, 1, Some ( 1)),
+      (Year(1970), Month::Jan, Weekday::Thu, 0, None     )
+    ];
+
+    for (y, m, wd, n, d) in cases {
+      let date = Date::nth_weekday(y, m, wd, n);
+      assert_eq!(d, date.map(|date| date.d));
+      if let Some (date) = date {
+        assert!(date.is_weekday_valid());
+        assert!(date.is_nth_weekday(wd, n));
+        assert!(!date.is_nth_weekday(wd, n + 1));
+        assert!(!date.is_nth_weekday(wd.skip(1), n));
+      }
+    }
+
+    assert!(!FEB_29_1972_23_59_59.is_nth_weekday(Weekday::Tue, 0));
+    assert!(!Date { d: 0, ..FEB_29_1972_23_59_59 }.is_nth_weekday(Weekday::Tue, 1));
+  }
+
+  #[test]
+  fn date_recompute_weekday() {
+
+    let dates = [
+      JAN_01_1970_00_00_00, FEB_28_1970_23_59_59, MAR_01_1970_00_00_00, APR_30_1970_23_59_59,
+      MAY_01_1970_00_00_00, JUL_31_1970_23_59_59, SEP_01_1970_00_00_00, DEC_31_1970_23_59_59,
+      JAN_01_1972_00_00_00, FEB_29_1972_23_59_59, MAR_01_1972_00_00_00, DEC_31_1972_23_59_59,
+      JAN_01_2000_00_00_00, DEC_31_2000_23_59_59,
+      DEC_31_2024_23_59_59
+    ];
+
+    for date in dates {
+      assert!(date.is_weekday_valid());
+      let mut repaired = Date { wd: date.wd.skip(1), ..date };
+      assert!(!repaired.is_weekday_valid());
+      repaired.recompute_weekday();
+      assert_eq!(date, repaired);
+    }
+  }
+
+  #[test]
+  fn date_iter() {
+
+    assert_eq!(7, Weekday::iter().count());
+    assert_eq!(Some (Weekday::Mon), Weekday::iter().next());
+    assert_eq!(Some (Weekday::Sun), Weekday::iter().last());
+    assert!(Weekday::iter().zip(Weekday::iter().skip(1)).all(|(wd, next)| wd.skip(1) == next));
+
+    assert_eq!(12, Month::iter().count());
+    assert_eq!(Some (Month::Jan), Month::iter().next());
+    assert_eq!(Some (Month::Dec), Month::iter().last());
+    assert!(Month::iter().zip(Month::iter().skip(1)).all(|(m, next)| m.skip(1) == next));
+    assert_eq!(365, Month::iter().map(|m| m.len(false) as u64).sum::<u64>());
+  }
+
+  #[test]
+  fn date_from_abbrev() {
+
+    for wd in Weekday::iter() {
+      assert_eq!(Some (wd), Weekday::from_abbrev(wd.abbrev().as_bytes().try_into().unwrap()));
+    }
+    for m in Month::iter() {
+      assert_eq!(Some (m), Month::from_abbrev(m.abbrev().as_bytes().try_into().unwrap()));
+    }
+
+    assert_eq!(None, Weekday::from_abbrev(b"mon"));
+    assert_eq!(None, Weekday::from_abbrev(b"Jan"));
+    assert_eq!(None, Month::from_abbrev(b"JAN"));
+    assert_eq!(None, Month::from_abbrev(b"Mon"));
+    assert_eq!(None, Month::from_abbrev(&[0; 3]));
+  }
+
+  #[test]
+  fn date_names() {
+
+    assert_eq!("Mon",       Weekday::Mon.abbrev());
+    assert_eq!("Monday",    Weekday::Mon.full_name());
+    assert_eq!("Wed",       Weekday::Wed.abbrev());
+    assert_eq!("Wednesday", Weekday::Wed.full_name());
+    assert_eq!("Sun",       Weekday::Sun.abbrev());
+    assert_eq!("Sunday",    Weekday::Sun.full_name());
+
+    assert_eq!("Jan",       Month::Jan.abbrev());
+    assert_eq!("January",   Month::Jan.full_name());
+    assert_eq!("Sep",       Month::Sep.abbrev());
+    assert_eq!("September", Month::Sep.full_name());
+    assert_eq!("Dec",       Month::Dec.abbrev());
+    assert_eq!("December",  Month::Dec.full_name());
+  }
+}
+
+#[cfg(kani)]
+mod verification {
+
+  use super::{Date, D_AS_S};
+
+  // the first four years, bounding the loops by year
+  const DAYS_MAX: u64 = 1461;
+  const DIFF_D_MAX: u64 = 62;
+
+  #[kani::proof]
+  #[kani::unwind(64)]
+  fn date_skip_days() {
+
+    let days: u64 = kani::any();
+    let diff_d: u64 = kani::any();
+    kani::assume(days < DAYS_MAX && diff_d <= DIFF_D_MAX);
+
+    let skipped = Date::from(days * D_AS_S).skip_days(diff_d);
+
+    assert_eq!(Date::from((days + diff_d) * D_AS_S), skipped);
+    assert!(skipped.d >= 1 && skipped.d <= skipped.m.len(skipped.y.is_leap()));
+    assert!(skipped.is_weekday_valid());
+  }
+
+  #[kani::proof]
+  #[kani::unwind(13)]
+  fn date_days_since_epoch() {
+
+    let days: u64 = kani::any();
+    kani::assume(days < DAYS_MAX);
+
+    assert_eq!(days, Date::from(days * D_AS_S).days_since_epoch());
+  }
 }
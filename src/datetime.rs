@@ -2,18 +2,45 @@
 //!
 //! A datetime struct for HTTP clients and servers.
 
-use crate::date::Date;
-use crate::time::Time;
+use crate::date::{Date, Weekday, Month, Year, ImfFixdateDate, D_AS_S, iso_weekday};
+use crate::time::{Time, ImfFixdateTime, M_AS_S, H_AS_S, H_AS_M, D_AS_H};
+use crate::parse::{self, ParseOptions, ParseError, ParseErrorDetail};
+use crate::jump;
+#[cfg(feature = "simd")]
+use crate::simd;
+#[cfg(feature = "clock-override")]
+use crate::clock;
 
-use std::time::SystemTime;
-use std::fmt::{self, Display, Formatter};
+use std::time::{SystemTime, SystemTimeError, Duration};
+use std::thread;
+use std::fmt::{self, Debug, Display, Formatter, Write};
+use std::str::{self, FromStr};
+use std::ops::{Add, Sub, AddAssign, SubAssign};
 use std::error::Error;
+use std::io;
+
+/// The length in bytes of an IMF-fixdate timestamp,
+/// e.g. `Thu, 01 Jan 1970 00:00:00 GMT`, as output by
+/// `for_header` and `for_header_bytes`.
+pub const IMF_FIXDATE_LEN: usize = 29;
+
+// the Julian Day and Modified Julian Day at the epoch
+const JD_AT_EPOCH: f64 = 2_440_587.5;
+const MJD_AT_EPOCH: f64 = 40_587.0;
+// the end of the last four-digit year
+pub(crate) const MAX_SECS: u64 = 253_402_300_799;
+// the longest jump forward, in days, for which the date is
+// skipped rather than computed afresh, below the crossover
+// shown by `cargo bench --bench set` for current years
+const SKIP_MAX_D: u64 = 32;
 
 /// Stores the date, time and raw seconds since the epoch,
 /// with constructor, core methods for update (`now`) and
 /// output as a HTTP Date header timestamp (`for_header`),
 /// utility methods for construction via diff (`set`) and
-/// current number of seconds since the epoch (`raw`) and
+/// current number of seconds since the epoch (`raw`),
+/// construction from seconds since the epoch (`from`),
+/// parsing from a HTTP Date header timestamp (`parse`) and
 /// a `Default` implementation for the Unix epoch values.
 ///
 /// # Example
@@ -34,11 +61,12 @@ use std::error::Error;
 ///   .unwrap()
 ///   .for_header();
 /// ```
-#[derive(Default, PartialEq, Debug)]
+#[derive(Default, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Datetime {
-  pub date: Date,
-  pub time: Time,
-  pub secs: u64
+  pub(crate) date: Date,
+  pub(crate) time: Time,
+  pub(crate) secs: u64
 }
 
 impl Datetime {
@@ -49,9 +77,13 @@ impl Datetime {
   }
 
   pub fn raw() -> Result<u64, Box<dyn Error>> {
+    #[cfg(feature = "clock-override")]
+    let raw = clock::raw()?;
+    #[cfg(not(feature = "clock-override"))]
     let raw = SystemTime::now()
       .duration_since(SystemTime::UNIX_EPOCH)?
       .as_secs();
+    jump::check(raw);
     Ok (raw)
   }
 
@@ -61,28 +93,1002 @@ impl Datetime {
     Ok (now)
   }
 
+  /// Provides the current datetime as for `now` unless
+  /// earlier than this datetime, in which case this datetime
+  /// is provided, smoothing over steps of the clock backward,
+  /// since a Date header going backward can confuse caches
+  /// and the validators of signed requests.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let dt = Datetime::new()
+  ///   .unwrap();
+  ///
+  /// let ahead = dt.set(dt.secs() + 60);
+  ///
+  /// assert_eq!(ahead, ahead.now_monotonic().unwrap());
+  /// ```
+  pub fn now_monotonic(&self) -> Result<Self, Box<dyn Error>> {
+    let raw = Self::raw()?.max(self.secs);
+    Ok (self.set(raw))
+  }
+
+  /// Updates each of many datetimes to the current time,
+  /// as for `now`, with the clock read once, e.g. for
+  /// designs in which thousands of connection state
+  /// machines each carry a datetime.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let mut dts = [Datetime::default(); 3];
+  ///
+  /// Datetime::now_many(&mut dts)
+  ///   .unwrap();
+  ///
+  /// assert!(dts.iter().all(|dt| dt == &dts[0]));
+  /// ```
+  pub fn now_many<'a>(dts: impl IntoIterator<Item = &'a mut Datetime>) -> Result<(), Box<dyn Error>> {
+    let raw = Self::raw()?;
+    for dt in dts {
+      *dt = dt.set(raw);
+    }
+    Ok (())
+  }
+
+  /// Provides the datetime for `secs` seconds since the
+  /// epoch, the date skipped forward from that of this
+  /// datetime for a jump of up to 32 days, the usual case,
+  /// else, including for a jump backward, computed afresh,
+  /// with `secs` clamped as for `From<u64>`.
   pub fn set(&self, secs: u64) -> Self {
-    let date = self.date.skip(secs - self.secs);
+    let secs = secs.min(MAX_SECS);
+    let date = match (secs / D_AS_S).checked_sub(self.secs / D_AS_S) {
+      Some (diff_d) if diff_d <= SKIP_MAX_D => self.date.skip_days(diff_d),
+      _                                     => Date::from(secs)
+    };
     let time = Time::from(secs);
     Self { date, time, secs }
   }
 
+  /// Provides the datetime at the start of the day of
+  /// `date`, i.e. 00:00:00 GMT, e.g. for a fixed expiry,
+  /// with the weekday of `date` disregarded, or
+  /// `ComponentError::Range` where the year, month and day
+  /// are out of range, as for `from_parts`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, Date};
+  ///
+  /// let date = "06 Nov 1994".parse::<Date>().unwrap();
+  ///
+  /// assert_eq!("Sun, 06 Nov 1994 00:00:00 GMT", Datetime::midnight(&date).unwrap().for_header());
+  /// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", Datetime::at(&date, 8, 49, 37).unwrap().for_header());
+  /// ```
+  pub fn midnight(date: &Date) -> Result<Self, ComponentError> {
+    Self::at(date, 0, 0, 0)
+  }
+
+  /// Provides the datetime on the day of `date` at the
+  /// given hour, minute and second, as for `midnight`, or
+  /// `ComponentError::Range` where any is out of range.
+  pub fn at(date: &Date, h: u8, m: u8, s: u8) -> Result<Self, ComponentError> {
+    Self::check_date(date)?;
+    Self::on_day_at(date.days_since_epoch(), h, m, s)
+  }
+
+  /// Provides the datetime on the given day of the year,
+  /// from 1, at the given hour, minute and second, e.g. for
+  /// systems keyed on ordinal dates, or
+  /// `ComponentError::Range` where any is out of range.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let dt = Datetime::from_ordinal_date(1994, 310, 8, 49, 37).unwrap();
+  ///
+  /// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", dt.for_header());
+  /// ```
+  pub fn from_ordinal_date(y: u64, day: u16, h: u8, m: u8, s: u8) -> Result<Self, ComponentError> {
+    if !(1970..=9999).contains(&y) || day < 1 || day as u64 > Year(y).days() {
+      return Err(ComponentError::Range)
+    }
+    Self::on_day_at(Year(y).days_before() + day as u64 - 1, h, m, s)
+  }
+
+  /// Provides the datetime at midnight on the given weekday
+  /// of the given ISO 8601 week of the given ISO year, e.g.
+  /// for schedules expressed in ISO weeks, as against the
+  /// `iso_week` method, or `ComponentError::Range` where the
+  /// week is out of range or the day before the epoch.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, Weekday};
+  ///
+  /// let dt = Datetime::from_iso_week_date(2025, 1, Weekday::Mon).unwrap();
+  ///
+  /// assert_eq!("Mon, 30 Dec 2024 00:00:00 GMT", dt.for_header());
+  /// assert_eq!((2025, 1), dt.iso_week());
+  /// ```
+  pub fn from_iso_week_date(y: u64, week: u8, wd: Weekday) -> Result<Self, ComponentError> {
+    if !(1970..=9999).contains(&y) || week < 1 || week > Year(y).iso_weeks() {
+      return Err(ComponentError::Range)
+    }
+    // week 1 being that with the year's first Thursday
+    let jan_04 = Year(y).days_before() + 3;
+    let monday = jan_04 as i64 - iso_weekday(Weekday::from_days_since_epoch(jan_04)) as i64;
+    let days = monday + (week as i64 - 1) * 7 + iso_weekday(wd) as i64;
+    match u64::try_from(days) {
+      Ok (days) if days * D_AS_S <= MAX_SECS => Ok (Self::from(days * D_AS_S)),
+      _                                      => Err(ComponentError::Range)
+    }
+  }
+
+  fn check_date(date: &Date) -> Result<(), ComponentError> {
+    let Year(y) = date.y;
+    match (1970..=9999).contains(&y) && date.d >= 1 && date.d <= date.m.len(date.y.is_leap()) {
+      true  => Ok (()),
+      false => Err(ComponentError::Range)
+    }
+  }
+
+  fn on_day_at(days: u64, h: u8, m: u8, s: u8) -> Result<Self, ComponentError> {
+    let (h, m, s) = (h as u64, m as u64, s as u64);
+    if h >= D_AS_H || m >= H_AS_M || s >= M_AS_S {
+      return Err(ComponentError::Range)
+    }
+    Ok (Self::from(days * D_AS_S + h * H_AS_S + m * M_AS_S + s))
+  }
+
+  /// Composes a datetime from a date and time, checking
+  /// that each field is in range and that the weekday is
+  /// that of the date.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, Date, Time, ComponentError};
+  ///
+  /// let dt = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+  ///
+  /// assert_eq!(Ok (dt), Datetime::from_parts(dt.date(), dt.time()));
+  ///
+  /// let time = Time { h: 9, ..dt.time() };
+  ///
+  /// assert_eq!("Sun, 06 Nov 1994 09:49:37 GMT", Datetime::from_parts(dt.date(), time).unwrap().for_header());
+  /// assert_eq!(Err(ComponentError::Range), Datetime::from_parts(dt.date(), Time { h: 24, ..time }));
+  /// ```
+  pub fn from_parts(date: Date, time: Time) -> Result<Self, ComponentError> {
+    Self::check_date(&date)?;
+    let (h, m, s) = (time.h as u64, time.m as u64, time.s as u64);
+    if h >= D_AS_H || m >= H_AS_M || s >= M_AS_S {
+      return Err(ComponentError::Range)
+    }
+    if !date.is_weekday_valid() {
+      return Err(ComponentError::Weekday)
+    }
+    let secs = date.days_since_epoch() * D_AS_S + time.seconds_since_midnight();
+    Ok (Self { date, time, secs })
+  }
+
+  /// Provides the date component, a copy, changes to which
+  /// are applied via `from_parts` or the `with_` methods.
+  pub fn date(&self) -> Date {
+    self.date
+  }
+
+  /// Provides the time component, a copy, as for `date`.
+  pub fn time(&self) -> Time {
+    self.time
+  }
+
+  /// Provides the number of seconds since the epoch.
+  pub fn secs(&self) -> u64 {
+    self.secs
+  }
+
+  /// Provides the datetime with the day of the month
+  /// replaced, the other fields kept and the weekday and
+  /// seconds recomputed, or `ComponentError::Range` where
+  /// the day is not in the month. The `with_month`,
+  /// `with_year`, `with_hour`, `with_minute` and
+  /// `with_second` methods are equivalent.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, ComponentError};
+  ///
+  /// let dt = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+  ///
+  /// assert_eq!("Wed, 30 Nov 1994 08:49:37 GMT", dt.with_day(30).unwrap().for_header());
+  /// assert_eq!("Sun, 06 Nov 1994 23:49:37 GMT", dt.with_hour(23).unwrap().for_header());
+  /// assert_eq!(Err(ComponentError::Range), dt.with_day(31));
+  /// ```
+  pub fn with_day(&self, d: u8) -> Result<Self, ComponentError> {
+    self.on_date(self.date.y, self.date.m, d)
+  }
+
+  pub fn with_month(&self, m: Month) -> Result<Self, ComponentError> {
+    self.on_date(self.date.y, m, self.date.d)
+  }
+
+  pub fn with_year(&self, y: u64) -> Result<Self, ComponentError> {
+    self.on_date(Year(y), self.date.m, self.date.d)
+  }
+
+  pub fn with_hour(&self, h: u8) -> Result<Self, ComponentError> {
+    Self::on_day_at(self.days_since_epoch(), h, self.time.m, self.time.s)
+  }
+
+  pub fn with_minute(&self, m: u8) -> Result<Self, ComponentError> {
+    Self::on_day_at(self.days_since_epoch(), self.time.h, m, self.time.s)
+  }
+
+  pub fn with_second(&self, s: u8) -> Result<Self, ComponentError> {
+    Self::on_day_at(self.days_since_epoch(), self.time.h, self.time.m, s)
+  }
+
+  // at the same time of day
+  fn on_date(&self, y: Year, m: Month, d: u8) -> Result<Self, ComponentError> {
+    let Year(year) = y;
+    if !(1970..=9999).contains(&year) || d < 1 || d > m.len(y.is_leap()) {
+      return Err(ComponentError::Range)
+    }
+    let days = y.days_before() + m.days_before(y.is_leap()) + d as u64 - 1;
+    Self::on_day_at(days, self.time.h, self.time.m, self.time.s)
+  }
+
+  /// Provides the number of seconds from this datetime to
+  /// `target`, or zero where `target` is not later.
+  pub fn seconds_until(&self, target: &Datetime) -> u64 {
+    target.secs.saturating_sub(self.secs)
+  }
+
+  /// Provides the time from `other` to this datetime broken
+  /// down into calendar units, i.e. whole years and months,
+  /// respecting the lengths of the months, then days, hours,
+  /// minutes and seconds, e.g. for human-facing reporting of
+  /// the age of a resource, or all zero where `other` is not
+  /// earlier. A day of the month absent from a later month
+  /// is taken as the last day of that month.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, CalendarDiff};
+  ///
+  /// let created = Datetime::parse("Tue, 31 Jan 1995 12:00:00 GMT").unwrap();
+  /// let now = Datetime::parse("Wed, 01 Mar 1995 08:49:37 GMT").unwrap();
+  ///
+  /// let age = CalendarDiff { years: 0, months: 1, days: 0, hours: 20, minutes: 49, seconds: 37 };
+  ///
+  /// assert_eq!(age, now.since_calendar(&created));
+  /// ```
+  pub fn since_calendar(&self, other: &Datetime) -> CalendarDiff {
+    if self.secs <= other.secs {
+      return CalendarDiff::default()
+    }
+    let mut months = self.month_index() - other.month_index();
+    if other.months_later(months, DayOverflow::Clamp) > self.secs {
+      months -= 1;
+    }
+    let rest = self.secs - other.months_later(months, DayOverflow::Clamp);
+    CalendarDiff {
+        years:  months / 12,
+       months: (months % 12)            as u8,
+         days: (rest / D_AS_S)          as u8,
+        hours: (rest / H_AS_S % D_AS_H) as u8,
+      minutes: (rest / M_AS_S % H_AS_M) as u8,
+      seconds: (rest % M_AS_S)          as u8
+    }
+  }
+
+  /// Describes this datetime relative to `now` in its
+  /// largest calendar unit, as for `since_calendar`,
+  /// truncated, e.g. `3 minutes ago` or `in 2 days`, or
+  /// `now` where the two are equal, e.g. for dashboards
+  /// displaying cache expiries.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let now = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+  ///
+  /// assert_eq!("3 minutes ago", now.set(now.secs() - 200).humanize_since(&now));
+  /// assert_eq!("in 1 day", now.set(now.secs() + 86_400).humanize_since(&now));
+  /// ```
+  pub fn humanize_since(&self, now: &Datetime) -> String {
+    let (diff, is_past) = match self.secs <= now.secs {
+      true  => (now.since_calendar(self), true),
+      false => (self.since_calendar(now), false)
+    };
+    let CalendarDiff { years, months, days, hours, minutes, seconds } = diff;
+    let units = [
+      (years,          "year"),
+      (months  as u64, "month"),
+      (days    as u64, "day"),
+      (hours   as u64, "hour"),
+      (minutes as u64, "minute"),
+      (seconds as u64, "second")
+    ];
+    let Some ((n, unit)) = units.into_iter().find(|(n, _)| *n > 0) else { return String::from("now") };
+    let plural = if n == 1 { "" } else { "s" };
+    match is_past {
+      true  => format!("{n} {unit}{plural} ago"),
+      false => format!("in {n} {unit}{plural}")
+    }
+  }
+
+  // the number of months from January of year zero
+  fn month_index(&self) -> u64 {
+    let Year(y) = self.date.y;
+    let m = Month::iter()
+      .position(|m| m == self.date.m)
+      .unwrap_or_default();
+    y * 12 + m as u64
+  }
+
+  // the seconds since the epoch the given number of months
+  // later, with any day beyond the length of the month
+  // handled per the policy
+  fn months_later(&self, months: u64, overflow: DayOverflow) -> u64 {
+    let index = self.month_index() + months;
+    let y = Year(index / 12);
+    let m = Month::default().skip(index % 12);
+    let d = match overflow {
+      DayOverflow::Clamp => self.date.d.min(m.len(y.is_leap())),
+      DayOverflow::Roll  => self.date.d
+    };
+    let days = y.days_before() + m.days_before(y.is_leap()) + d as u64 - 1;
+    days * D_AS_S + self.secs % D_AS_S
+  }
+
+  /// Provides the datetime the given number of calendar
+  /// months later at the same time of day, e.g. for
+  /// subscription-style expiry, with a day of the month
+  /// absent from the later month either limited to its last
+  /// day (`DayOverflow::Clamp`) or carried over into the
+  /// following month (`DayOverflow::Roll`), or
+  /// `ComponentError::Range` beyond the year 9999. The
+  /// `add_years` method is equivalent, e.g. for
+  /// certificate-style expiry.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, DayOverflow};
+  ///
+  /// let dt = Datetime::parse("Tue, 31 Jan 1995 08:49:37 GMT").unwrap();
+  ///
+  /// assert_eq!("Tue, 28 Feb 1995 08:49:37 GMT", dt.add_months(1, DayOverflow::Clamp).unwrap().for_header());
+  /// assert_eq!("Fri, 03 Mar 1995 08:49:37 GMT", dt.add_months(1, DayOverflow::Roll).unwrap().for_header());
+  /// ```
+  pub fn add_months(&self, months: u64, overflow: DayOverflow) -> Result<Self, ComponentError> {
+    let is_in_range = self.month_index()
+      .checked_add(months)
+      .is_some_and(|index| index / 12 <= 9999);
+    if !is_in_range {
+      return Err(ComponentError::Range)
+    }
+    Ok (self.set(self.months_later(months, overflow)))
+  }
+
+  pub fn add_years(&self, years: u64, overflow: DayOverflow) -> Result<Self, ComponentError> {
+    let months = years
+      .checked_mul(12)
+      .ok_or(ComponentError::Range)?;
+    self.add_months(months, overflow)
+  }
+
+  /// Provides the duration between this datetime and
+  /// `other`, whichever is later, e.g. for skew checks.
+  pub fn abs_diff(&self, other: &Datetime) -> Duration {
+    Duration::from_secs(self.secs.abs_diff(other.secs))
+  }
+
+  /// Indicates whether this datetime and `other` are no
+  /// more than `tolerance` apart, e.g. for rejecting a
+  /// request with an implausible timestamp.
+  pub fn within_skew(&self, other: &Datetime, tolerance: Duration) -> bool {
+    self.abs_diff(other) <= tolerance
+  }
+
+  /// Provides this datetime bounded by `min` and `max`,
+  /// e.g. for capping far-future expiries from an origin.
+  ///
+  /// # Panics
+  ///
+  /// Panics where `min` is later than `max`.
+  pub fn clamp(&self, min: &Datetime, max: &Datetime) -> Self {
+    assert!(min.secs <= max.secs, "min later than max");
+    self.max(min).min(max)
+  }
+
+  /// Provides the earlier of this datetime and `other`.
+  pub fn min(&self, other: &Datetime) -> Self {
+    if other.secs < self.secs { *other } else { *self }
+  }
+
+  /// Provides the later of this datetime and `other`.
+  pub fn max(&self, other: &Datetime) -> Self {
+    if other.secs > self.secs { *other } else { *self }
+  }
+
+  /// Indicates whether this datetime and `other` fall in
+  /// the same second, e.g. for deciding whether a cached
+  /// header value needs regenerating.
+  pub fn is_same_second(&self, other: &Datetime) -> bool {
+    self.secs == other.secs
+  }
+
+  pub fn is_same_minute(&self, other: &Datetime) -> bool {
+    self.secs / M_AS_S == other.secs / M_AS_S
+  }
+
+  pub fn is_same_day(&self, other: &Datetime) -> bool {
+    self.secs / D_AS_S == other.secs / D_AS_S
+  }
+
+  /// Provides the duration from this datetime to the next
+  /// whole second, i.e. always one second, datetimes being
+  /// stored in whole seconds.
+  pub fn duration_until_next_second(&self) -> Duration {
+    self.duration_until_next(1)
+  }
+
+  /// Provides the duration from this datetime to the start
+  /// of the next minute, e.g. for a refresher loop, with
+  /// a datetime on the boundary giving a whole minute.
+  pub fn duration_until_next_minute(&self) -> Duration {
+    self.duration_until_next(M_AS_S)
+  }
+
+  pub fn duration_until_next_hour(&self) -> Duration {
+    self.duration_until_next(H_AS_S)
+  }
+
+  /// Provides the duration from this datetime to the next
+  /// midnight GMT, e.g. for log rotation.
+  pub fn duration_until_next_day(&self) -> Duration {
+    self.duration_until_next(D_AS_S)
+  }
+
+  fn duration_until_next(&self, period: u64) -> Duration {
+    Duration::from_secs(period - self.secs % period)
+  }
+
+  /// Blocks the current thread until the clock read by
+  /// `raw` reaches this datetime, returning immediately
+  /// where it has already done so.
+  pub fn sleep_until(&self) {
+    thread::sleep(self.remaining());
+  }
+
+  /// Waits asynchronously until the clock reaches this
+  /// datetime, as for `sleep_until`, for use within
+  /// a Tokio runtime with the time driver enabled.
+  #[cfg(feature = "tokio")]
+  pub async fn sleep_until_async(&self) {
+    tokio::time::sleep(self.remaining()).await;
+  }
+
+  // the whole seconds to go per the clock read by raw, so
+  // any override, less the part of the current second
+  // elapsed on the system clock
+  fn remaining(&self) -> Duration {
+    let Ok (raw) = Self::raw() else { return Duration::ZERO };
+    let elapsed = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)
+      .map_or(Duration::ZERO, |since| Duration::from_nanos(since.subsec_nanos() as u64));
+    Duration::from_secs(self.secs.saturating_sub(raw)).saturating_sub(elapsed)
+  }
+
+  /// Parses a HTTP Date header timestamp in any of the
+  /// three formats recipients are required to accept, i.e.
+  /// the IMF-fixdate and the obsolete RFC 850 and asctime.
+  pub fn parse(s: &str) -> Result<Self, ParseError> {
+    Self::parse_with_options(s, &ParseOptions::default())
+  }
+
+  /// Parses as for `parse` in lenient mode, i.e. with
+  /// numeric offsets and obsolete named zones accepted in
+  /// place of `GMT`, names matched case-insensitively, days
+  /// of one digit and a bare number of seconds since the
+  /// epoch, optionally prefixed with `@`, also accepted.
+  pub fn parse_lenient(s: &str) -> Result<Self, ParseError> {
+    Self::parse_with_options(s, &ParseOptions::default().lenient(true))
+  }
+
+  pub fn parse_with_options(s: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+    parse::parse(s, options)
+  }
+
+  /// Parses as for `parse_with_options`, reporting any error
+  /// with the field at fault and its byte offset.
+  pub fn parse_detailed(s: &str, options: &ParseOptions) -> Result<Self, ParseErrorDetail> {
+    parse::parse_detailed(s, options)
+  }
+
+  /// Parses a timestamp in the format given by a subset of
+  /// the strptime tokens, i.e. `%a`, `%A`, `%d`, `%e`, `%b`,
+  /// `%B`, `%m`, `%Y`, `%y`, `%H`, `%M`, `%S`, `%z`, `%Z`
+  /// and `%%`, with any other character matched exactly.
+  /// Fields absent from the format are taken from the
+  /// epoch and two-digit years from 1970 to 2069.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let dt = Datetime::parse_with("%Y%m%d-%H%M%S", "19941106-084937").unwrap();
+  ///
+  /// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", dt.for_header());
+  /// ```
+  pub fn parse_with(fmt: &str, s: &str) -> Result<Self, ParseError> {
+    parse::parse_format(fmt, s)
+  }
+
+  /// Parses an RFC 3339 timestamp, e.g. as used in
+  /// configuration and APIs, with any offset applied to
+  /// give the datetime in GMT and any fractional seconds
+  /// discarded.
+  pub fn from_rfc3339(s: &str) -> Result<Self, ParseError> {
+    parse::parse_rfc3339(s)
+  }
+
+  /// Parses an RFC 2822 timestamp, e.g. from an email
+  /// Date header, with any offset or obsolete named zone
+  /// applied to give the datetime in GMT.
+  pub fn from_rfc2822(s: &str) -> Result<Self, ParseError> {
+    parse::parse_rfc2822(s)
+  }
+
+  /// Parses an RFC 5322 date-time tolerantly, as for
+  /// `from_rfc2822` but with comments, e.g. `(CEST)`, and
+  /// folding whitespace disregarded, and where the string
+  /// is a trace header value, e.g. from Received, the
+  /// date-time after the final semicolon taken.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let received = "from a.example by b.example (envelope-from <x@a.example>);\r\n\tSun, 6 Nov 1994 10:49:37 +0200 (CEST)";
+  ///
+  /// let dt = Datetime::from_rfc5322(received).unwrap();
+  ///
+  /// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", dt.for_header());
+  /// ```
+  pub fn from_rfc5322(s: &str) -> Result<Self, ParseError> {
+    parse::parse_rfc5322(s)
+  }
+
   pub fn for_header(&self) -> String {
-    ImfFixdate(self).to_string()
+    let mut s = String::with_capacity(IMF_FIXDATE_LEN);
+    let _ = write!(s, "{}", ImfFixdate(self));
+    s
+  }
+
+  /// Outputs the HTTP Date header timestamp as exactly
+  /// `IMF_FIXDATE_LEN` ASCII bytes, allowing buffers to be
+  /// pre-sized and fields spliced at fixed offsets. Years
+  /// up to 9999 are supported, with any later year written
+  /// as its last four digits.
+  pub fn for_header_bytes(&self) -> [u8; IMF_FIXDATE_LEN] {
+    #[cfg(feature = "simd")]
+    return simd::format(self);
+    #[cfg(not(feature = "simd"))]
+    {
+      let Year(y) = self.date.y;
+      let mut bytes = *b"Thu, 01 Jan 1970 00:00:00 GMT";
+      bytes[ 0.. 3].copy_from_slice(self.date.wd.abbrev().as_bytes());
+      bytes[ 5.. 7].copy_from_slice(&two_digits( self.date.d                  ));
+      bytes[ 8..11].copy_from_slice(self.date.m.abbrev().as_bytes());
+      bytes[12..14].copy_from_slice(&two_digits((y / 100 % 100) as u8));
+      bytes[14..16].copy_from_slice(&two_digits((y       % 100) as u8));
+      bytes[17..19].copy_from_slice(&two_digits( self.time.h                  ));
+      bytes[20..22].copy_from_slice(&two_digits( self.time.m                  ));
+      bytes[23..25].copy_from_slice(&two_digits( self.time.s                  ));
+      bytes
+    }
+  }
+
+  /// Writes the HTTP Date header timestamp as its ASCII
+  /// bytes directly to `w`, e.g. a socket or `BufWriter`,
+  /// without an intermediate `String` or `fmt::Write`
+  /// adapter.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let mut buf = Vec::new();
+  ///
+  /// Datetime::default().write_header_io(&mut buf).unwrap();
+  ///
+  /// assert_eq!(b"Thu, 01 Jan 1970 00:00:00 GMT", &buf[..]);
+  /// ```
+  pub fn write_header_io<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+    w.write_all(&self.for_header_bytes())
+  }
+
+  /// Provides the number of whole days since the epoch,
+  /// from which the weekday follows, the epoch being a
+  /// Thursday.
+  pub fn days_since_epoch(&self) -> u64 {
+    self.secs / D_AS_S
+  }
+
+  /// Provides the next datetime after this one on the given
+  /// weekday at the given time, e.g. the start of the next
+  /// maintenance window, for Retry-After and Expires values,
+  /// or `ComponentError::Range` where the time is out of
+  /// range.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::{Datetime, Weekday, Time, ComponentError};
+  ///
+  /// let dt = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+  ///
+  /// let next = dt.advance_to_next(Weekday::Sun, &"03:00:00".parse::<Time>().unwrap()).unwrap();
+  ///
+  /// assert_eq!("Sun, 13 Nov 1994 03:00:00 GMT", next.for_header());
+  /// assert_eq!(Err(ComponentError::Range), dt.advance_to_next(Weekday::Sun, &Time { h: 24, m: 0, s: 0 }));
+  /// ```
+  pub fn advance_to_next(&self, wd: Weekday, time: &Time) -> Result<Self, ComponentError> {
+    let (h, m, s) = (time.h as u64, time.m as u64, time.s as u64);
+    if h >= D_AS_H || m >= H_AS_M || s >= M_AS_S {
+      return Err(ComponentError::Range)
+    }
+    let days = self.days_since_epoch();
+    let ahead = (0..7)
+      .find(|ahead| Weekday::from_days_since_epoch(days + ahead) == wd)
+      .unwrap_or_default();
+    let mut secs = (days + ahead) * D_AS_S + h * H_AS_S + m * M_AS_S + s;
+    if secs <= self.secs { secs += D_AS_S * 7 };
+    Ok (self.set(secs))
+  }
+
+  /// Provides the ISO 8601 year and week, from 1 to 53,
+  /// i.e. those of the Thursday of the week, which starts
+  /// on a Monday.
+  pub fn iso_week(&self) -> (u64, u8) {
+    let thursday = self.days_since_epoch() + 3 - iso_weekday(self.date.wd);
+    let Date { y, .. } = Date::from(thursday * D_AS_S);
+    let Year(iso_y) = y;
+    (iso_y, ((thursday - y.days_before()) / 7 + 1) as u8)
+  }
+
+  /// Provides the quarter of the year, from 1 to 4.
+  pub fn quarter(&self) -> u8 {
+    let m = Month::iter()
+      .position(|m| m == self.date.m)
+      .unwrap_or_default();
+    m as u8 / 3 + 1
+  }
+
+  /// Provides the datetime at the start of the month, i.e.
+  /// midnight on the first, e.g. for monthly segmentation.
+  pub fn start_of_month(&self) -> Self {
+    let days = self.days_since_epoch() - (self.date.d as u64 - 1);
+    self.on_day(days, 1, 0)
+  }
+
+  /// Provides the datetime at the end of the month, i.e.
+  /// the last second of the last day, e.g. for the expiry
+  /// of a billing period.
+  pub fn end_of_month(&self) -> Self {
+    let len = self.date.m.len(self.date.y.is_leap());
+    let days = self.days_since_epoch() + (len - self.date.d) as u64;
+    self.on_day(days, len, D_AS_S - 1)
+  }
+
+  /// Provides the number of days remaining in the month
+  /// after the day of this datetime, i.e. zero on the last
+  /// day, e.g. for end-of-month cache policies.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Datetime;
+  ///
+  /// let dt = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+  ///
+  /// assert_eq!(24, dt.days_remaining_in_month());
+  /// assert_eq!(55, dt.days_remaining_in_year());
+  /// ```
+  pub fn days_remaining_in_month(&self) -> u8 {
+    self.date.m.len(self.date.y.is_leap()) - self.date.d
+  }
+
+  /// Provides the number of days remaining in the year
+  /// after the day of this datetime, as for
+  /// `days_remaining_in_month`.
+  pub fn days_remaining_in_year(&self) -> u16 {
+    let y = self.date.y;
+    (y.days_before() + y.days() - 1 - self.days_since_epoch()) as u16
+  }
+
+  // in the same month and year
+  fn on_day(&self, days: u64, d: u8, xs: u64) -> Self {
+    let secs = days * D_AS_S + xs;
+    let date = Date { d, wd: Weekday::from_days_since_epoch(days), ..self.date };
+    let time = Time::from(secs);
+    Self { date, time, secs }
+  }
+
+  /// Provides the Julian Day, i.e. the number of days,
+  /// including the fraction, since noon GMT on 1 January
+  /// 4713 BC in the proleptic Julian calendar.
+  pub fn to_julian_day(&self) -> f64 {
+    self.secs as f64 / D_AS_S as f64 + JD_AT_EPOCH
+  }
+
+  /// Provides the datetime for a Julian Day, rounded to the
+  /// nearest second, or `None` where the day is not finite
+  /// or falls outside the years from 1970 to 9999.
+  pub fn from_julian_day(jd: f64) -> Option<Self> {
+    Self::from_days(jd - JD_AT_EPOCH)
+  }
+
+  /// Provides the Modified Julian Day, i.e. the Julian Day
+  /// less 2,400,000.5, counting from midnight GMT.
+  pub fn to_modified_julian_day(&self) -> f64 {
+    self.secs as f64 / D_AS_S as f64 + MJD_AT_EPOCH
+  }
+
+  pub fn from_modified_julian_day(mjd: f64) -> Option<Self> {
+    Self::from_days(mjd - MJD_AT_EPOCH)
+  }
+
+  fn from_days(days: f64) -> Option<Self> {
+    let secs = (days * D_AS_S as f64).round();
+    match secs.is_finite() && secs >= 0.0 && secs <= MAX_SECS as f64 {
+      true  => Some (Self::from(secs as u64)),
+      false => None
+    }
+  }
+
+  /// Encodes the datetime as its seconds since the epoch
+  /// in eight little-endian bytes, e.g. for persistence in
+  /// an on-disk cache, reversed by `from_le_bytes`, which
+  /// gives `None` for a number of seconds beyond 9999, e.g.
+  /// from bytes corrupted.
+  pub fn to_le_bytes(&self) -> [u8; 8] {
+    self.secs.to_le_bytes()
+  }
+
+  pub fn from_le_bytes(bytes: [u8; 8]) -> Option<Self> {
+    let secs = u64::from_le_bytes(bytes);
+    match secs <= MAX_SECS {
+      true  => Some (Self::from(secs)),
+      false => None
+    }
+  }
+
+  /// Adds the whole seconds of a duration, giving `None`
+  /// past the latest datetime supported, as against the
+  /// `+` operator, which panics.
+  pub fn checked_add(&self, rhs: Duration) -> Option<Self> {
+    let secs = self.secs
+      .checked_add(rhs.as_secs())
+      .filter(|secs| *secs <= MAX_SECS)?;
+    Some (self.set(secs))
+  }
+
+  /// Subtracts the whole seconds of a duration, giving
+  /// `None` before the Unix epoch, as for `checked_add`.
+  pub fn checked_sub(&self, rhs: Duration) -> Option<Self> {
+    let secs = self.secs.checked_sub(rhs.as_secs())?;
+    Some (self.set(secs))
+  }
+
+  /// Writes the HTTP Date header timestamp for each of
+  /// `dts` to `w`, each followed by a newline, e.g. for
+  /// Last-Modified values in a sitemap or feed. The date
+  /// part is reused across consecutive datetimes on the
+  /// same day, with only the time part then regenerated.
+  pub fn format_many(dts: &[Datetime], w: &mut impl fmt::Write) -> fmt::Result {
+    let mut cached: Option<(u64, [u8; IMF_FIXDATE_LEN])> = None;
+    for dt in dts {
+      let day = dt.secs / D_AS_S;
+      let bytes = match cached {
+        Some ((cached_day, mut bytes)) if cached_day == day => {
+          bytes[17..19].copy_from_slice(&two_digits(dt.time.h));
+          bytes[20..22].copy_from_slice(&two_digits(dt.time.m));
+          bytes[23..25].copy_from_slice(&two_digits(dt.time.s));
+          bytes
+        },
+        _ => dt.for_header_bytes()
+      };
+      cached = Some ((day, bytes));
+      w.write_str(str::from_utf8(&bytes).map_err(|_| fmt::Error)?)?;
+      w.write_char('\n')?;
+    }
+    Ok (())
+  }
+}
+
+/// Adds the whole seconds of a duration, via `set`,
+/// panicking past the latest datetime supported, as
+/// `SystemTime` does on overflow, with `checked_add` the
+/// alternative.
+impl Add<Duration> for Datetime {
+  type Output = Self;
+
+  fn add(self, rhs: Duration) -> Self {
+    self.checked_add(rhs)
+      .expect("overflow when adding duration to datetime")
+  }
+}
+
+/// Subtracts the whole seconds of a duration, panicking
+/// before the Unix epoch, as for `Add`, with `checked_sub`
+/// the alternative.
+impl Sub<Duration> for Datetime {
+  type Output = Self;
+
+  fn sub(self, rhs: Duration) -> Self {
+    self.checked_sub(rhs)
+      .expect("overflow when subtracting duration from datetime")
+  }
+}
+
+impl AddAssign<Duration> for Datetime {
+
+  fn add_assign(&mut self, rhs: Duration) {
+    *self = *self + rhs;
+  }
+}
+
+impl SubAssign<Duration> for Datetime {
+
+  fn sub_assign(&mut self, rhs: Duration) {
+    *self = *self - rhs;
+  }
+}
+
+/// Converts the whole seconds since the epoch, failing for
+/// a time before the epoch.
+impl TryFrom<SystemTime> for Datetime {
+  type Error = SystemTimeError;
+
+  fn try_from(st: SystemTime) -> Result<Self, Self::Error> {
+    let secs = st
+      .duration_since(SystemTime::UNIX_EPOCH)?
+      .as_secs();
+    Ok (Self::from(secs))
+  }
+}
+
+// clamped at the latest datetime supported, i.e. that at
+// the end of 9999
+impl From<u64> for Datetime {
+
+  fn from(secs: u64) -> Self {
+    let secs = secs.min(MAX_SECS);
+    let date = Date::from(secs);
+    let time = Time::from(secs);
+    Self { date, time, secs }
+  }
+}
+
+impl FromStr for Datetime {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::parse(s)
   }
 }
 
+/// Formats the datetime as the IMF-fixdate timestamp with
+/// the number of seconds since the epoch, e.g.
+/// `Datetime("Thu, 01 Jan 1970 00:00:00 GMT", secs: 0)`,
+/// the date and time components following from these.
+impl Debug for Datetime {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "Datetime(\"{}\", secs: {})", ImfFixdate(self), self.secs)
+  }
+}
+
+/// Formats the datetime for defmt logging as the HTTP Date
+/// header timestamp, e.g. `Thu, 01 Jan 1970 00:00:00 GMT`,
+/// with the bytes written as a single string argument.
+#[cfg(feature = "defmt")]
+impl defmt::Format for Datetime {
+
+  fn format(&self, f: defmt::Formatter) {
+    let bytes = self.for_header_bytes();
+    defmt::write!(f, "{=str}", std::str::from_utf8(&bytes).unwrap_or_default())
+  }
+}
+
+fn two_digits(n: u8) -> [u8; 2] {
+  [b'0' + n / 10 % 10, b'0' + n % 10]
+}
+
+// ComponentError
+
+/// Indicates why a datetime could not be composed from its
+/// parts, i.e. a field out of range or a weekday other than
+/// that of the date.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ComponentError {
+  Range,
+  Weekday
+}
+
+impl Display for ComponentError {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Range   => write!(f, "datetime field out of range"),
+      Self::Weekday => write!(f, "weekday does not match date")
+    }
+  }
+}
+
+impl Error for ComponentError {}
+
+// CalendarDiff
+
+/// Stores the time between two datetimes in calendar
+/// units, as provided by `Datetime::since_calendar`.
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub struct CalendarDiff {
+    pub years: u64,
+   pub months: u8,
+     pub days: u8,
+    pub hours: u8,
+  pub minutes: u8,
+  pub seconds: u8
+}
+
+// DayOverflow
+
+/// Indicates how `Datetime::add_months` and `add_years`
+/// handle a day of the month absent from the later month,
+/// i.e. by limiting it to the last day of the month
+/// (`Clamp`), or by carrying the excess days over into the
+/// following month (`Roll`).
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub enum DayOverflow {
+  #[default]
+  Clamp,
+  Roll
+}
+
 // ImfFixdate
 
-struct ImfFixdate<'a>(&'a Datetime);
+pub(crate) struct ImfFixdate<'a>(pub(crate) &'a Datetime);
 
 impl Display for ImfFixdate<'_> {
 
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     let ImfFixdate(dt) = self;
     write!(f, "{} {} GMT",
-      dt.date.for_header(),
-      dt.time.for_header()
+      ImfFixdateDate(&dt.date),
+      ImfFixdateTime(&dt.time)
     )
   }
 }
@@ -90,8 +1096,8 @@ impl Display for ImfFixdate<'_> {
 #[cfg(test)]
 mod test {
 
-  use super::Datetime;
-  use crate::date::{self, D_AS_S, test::{M_28_AS_S, M_29_AS_S, M_30_AS_S, M_31_AS_S, Y_365_AS_S, Y_366_AS_S}};
+  use super::{Datetime, ComponentError, CalendarDiff, DayOverflow, IMF_FIXDATE_LEN, SKIP_MAX_D, MAX_SECS};
+  use crate::date::{self, Date, Weekday, Month, Year, D_AS_S, test::{M_28_AS_S, M_29_AS_S, M_30_AS_S, M_31_AS_S, Y_365_AS_S, Y_366_AS_S}};
   use crate::time::{self, Time, M_AS_S, H_AS_M, D_AS_H};
 
   use std::time::{SystemTime, Duration};
@@ -106,70 +1112,63 @@ mod test {
   const FEB_28_1970_23_59_59: Datetime = Datetime {
     date: date::test::FEB_28_1970_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: M_31_AS_S + M_28_AS_S - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: M_31_AS_S + M_28_AS_S - 1
   };
   const MAR_01_1970_00_00_00: Datetime = Datetime {
     date: date::test::MAR_01_1970_00_00_00,
     time: Time {
-       h: 0,
-       m: 0,
-       s: 0,
-      xs: M_31_AS_S + M_28_AS_S
+      h: 0,
+      m: 0,
+      s: 0
     },
     secs: M_31_AS_S + M_28_AS_S
   };
   const APR_30_1970_23_59_59: Datetime = Datetime {
     date: date::test::APR_30_1970_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: M_31_AS_S * 2 + M_30_AS_S + M_28_AS_S - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: M_31_AS_S * 2 + M_30_AS_S + M_28_AS_S - 1
   };
   const MAY_01_1970_00_00_00: Datetime = Datetime {
     date: date::test::MAY_01_1970_00_00_00,
     time: Time {
-       h: 0,
-       m: 0,
-       s: 0,
-      xs: M_31_AS_S * 2 + M_30_AS_S + M_28_AS_S
+      h: 0,
+      m: 0,
+      s: 0
     },
     secs: M_31_AS_S * 2 + M_30_AS_S + M_28_AS_S
   };
   const JUL_31_1970_23_59_59: Datetime = Datetime {
     date: date::test::JUL_31_1970_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: M_31_AS_S * 4 + M_30_AS_S * 2 + M_28_AS_S - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: M_31_AS_S * 4 + M_30_AS_S * 2 + M_28_AS_S - 1
   };
   const SEP_01_1970_00_00_00: Datetime = Datetime {
     date: date::test::SEP_01_1970_00_00_00,
     time: Time {
-       h: 0,
-       m: 0,
-       s: 0,
-      xs: M_31_AS_S * 5 + M_30_AS_S * 2 + M_28_AS_S
+      h: 0,
+      m: 0,
+      s: 0
     },
     secs: M_31_AS_S * 5 + M_30_AS_S * 2 + M_28_AS_S
   };
   const DEC_31_1970_23_59_59: Datetime = Datetime {
     date: date::test::DEC_31_1970_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: Y_365_AS_S - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: Y_365_AS_S - 1
   };
@@ -178,40 +1177,36 @@ mod test {
   const JAN_01_1972_00_00_00: Datetime = Datetime {
     date: date::test::JAN_01_1972_00_00_00,
     time: Time {
-       h: 0,
-       m: 0,
-       s: 0,
-      xs: Y_365_AS_S * 2
+      h: 0,
+      m: 0,
+      s: 0
     },
     secs: Y_365_AS_S * 2
   };
   const FEB_29_1972_23_59_59: Datetime = Datetime {
     date: date::test::FEB_29_1972_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: Y_365_AS_S * 2 + M_31_AS_S + M_29_AS_S - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: Y_365_AS_S * 2 + M_31_AS_S + M_29_AS_S - 1
   };
   const MAR_01_1972_00_00_00: Datetime = Datetime {
     date: date::test::MAR_01_1972_00_00_00,
     time: Time {
-       h: 0,
-       m: 0,
-       s: 0,
-      xs: Y_365_AS_S * 2 + M_31_AS_S + M_29_AS_S
+      h: 0,
+      m: 0,
+      s: 0
     },
     secs: Y_365_AS_S * 2 + M_31_AS_S + M_29_AS_S
   };
   const DEC_31_1972_23_59_59: Datetime = Datetime {
     date: date::test::DEC_31_1972_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: Y_365_AS_S * 2 + Y_366_AS_S - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: Y_365_AS_S * 2 + Y_366_AS_S - 1
   };
@@ -220,20 +1215,18 @@ mod test {
   const JAN_01_2000_00_00_00: Datetime = Datetime {
     date: date::test::JAN_01_2000_00_00_00,
     time: Time {
-       h: 0,
-       m: 0,
-       s: 0,
-      xs: Y_365_AS_S * 23 + Y_366_AS_S * 7
+      h: 0,
+      m: 0,
+      s: 0
     },
     secs: Y_365_AS_S * 23 + Y_366_AS_S * 7
   };
   const DEC_31_2000_23_59_59: Datetime = Datetime {
     date: date::test::DEC_31_2000_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: Y_365_AS_S * 23 + Y_366_AS_S * 8 - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: Y_365_AS_S * 23 + Y_366_AS_S * 8 - 1
   };
@@ -242,10 +1235,9 @@ mod test {
   const DEC_31_2024_23_59_59: Datetime = Datetime {
     date: date::test::DEC_31_2024_23_59_59,
     time: Time {
-       h: (D_AS_H - 1) as u8,
-       m: (H_AS_M - 1) as u8,
-       s: (M_AS_S - 1) as u8,
-      xs: Y_365_AS_S * 41 + Y_366_AS_S * 14 - D_AS_S
+      h: (D_AS_H - 1) as u8,
+      m: (H_AS_M - 1) as u8,
+      s: (M_AS_S - 1) as u8
     },
     secs: Y_365_AS_S * 41 + Y_366_AS_S * 14 - 1
   };
@@ -276,7 +1268,7 @@ mod test {
     let dt_new = Datetime::new().unwrap();
 
     assert_eq!(st_raw, dt_new.secs);
-    assert_eq!(st_raw, dt_new.date.xs + dt_new.time.xs);
+    assert_eq!(st_raw, dt_new.date.days_since_epoch() * D_AS_S + dt_new.time.seconds_since_midnight());
   }
 
   #[test]
@@ -289,7 +1281,65 @@ mod test {
     let dt_now = dt_new.now().unwrap();
 
     assert_eq!(dt_new.secs + 1, dt_now.secs);
-    assert_eq!(dt_new.date.xs + dt_new.time.xs + 1, dt_now.date.xs + dt_now.time.xs);
+    assert_eq!(Datetime::from(dt_new.secs + 1), dt_now);
+  }
+
+  #[test]
+  fn datetime_now_monotonic() {
+
+    let dt_new = Datetime::new().unwrap();
+
+    // behind the clock, as for now
+    let dt_now = DEC_31_2024_23_59_59.now_monotonic().unwrap();
+    assert!(dt_now.secs - dt_new.secs <= 1);
+    assert_eq!(Datetime::from(dt_now.secs), dt_now);
+
+    // ahead of the clock, held
+    let ahead = dt_new.set(dt_new.secs + 60);
+    assert_eq!(ahead, ahead.now_monotonic().unwrap());
+  }
+
+  #[test]
+  fn datetime_now_many() {
+
+    let mut dts = [JAN_01_1970_00_00_00, FEB_29_1972_23_59_59, DEC_31_2024_23_59_59];
+
+    Datetime::now_many(&mut dts).unwrap();
+
+    let dt_new = Datetime::new().unwrap();
+    for dt in dts {
+      assert!(dt_new.secs - dt.secs <= 1);
+      assert_eq!(Datetime::from(dt.secs), dt);
+    }
+
+    // via an iterator
+    let mut pairs = [(1, JAN_01_1970_00_00_00), (2, DEC_31_1970_23_59_59)];
+    Datetime::now_many(pairs.iter_mut().map(|(_, dt)| dt)).unwrap();
+    assert_eq!(pairs[0].1, pairs[1].1);
+  }
+
+  #[test]
+  fn datetime_from() {
+
+    // 1970
+    assert_eq!(JAN_01_1970_00_00_00, Datetime::from(                                                              0));
+    assert_eq!(FEB_28_1970_23_59_59, Datetime::from(                  M_31_AS_S                     + M_28_AS_S - 1));
+    assert_eq!(SEP_01_1970_00_00_00, Datetime::from(                  M_31_AS_S * 5 + M_30_AS_S * 2 + M_28_AS_S    ));
+
+    // 1972
+    assert_eq!(FEB_29_1972_23_59_59, Datetime::from(Y_365_AS_S *  2                 + M_31_AS_S     + M_29_AS_S - 1));
+    assert_eq!(MAR_01_1972_00_00_00, Datetime::from(Y_365_AS_S *  2                 + M_31_AS_S     + M_29_AS_S    ));
+
+    // 2000
+    assert_eq!(JAN_01_2000_00_00_00, Datetime::from(Y_365_AS_S * 23 + Y_366_AS_S *  7                              ));
+
+    // 2024
+    assert_eq!(DEC_31_2024_23_59_59, Datetime::from(Y_365_AS_S * 41 + Y_366_AS_S * 14                           - 1));
+
+    // clamped
+    assert_eq!(Datetime::from(MAX_SECS), Datetime::from(MAX_SECS + 1));
+    assert_eq!(Datetime::from(MAX_SECS), Datetime::from(u64::MAX));
+    assert_eq!(Datetime::from(MAX_SECS), DEC_31_2024_23_59_59.set(u64::MAX));
   }
 
   #[test]
@@ -316,6 +1366,16 @@ mod test {
 
     // 2024
     assert_eq!(DEC_31_2024_23_59_59, DEC_31_2000_23_59_59.set(Y_365_AS_S * 41 + Y_366_AS_S * 14                           - 1));
+
+    // backward
+    assert_eq!(JAN_01_1970_00_00_00, DEC_31_2024_23_59_59.set(                                                              0));
+    assert_eq!(FEB_28_1970_23_59_59, MAR_01_1970_00_00_00.set(                  M_31_AS_S                     + M_28_AS_S - 1));
+
+    // either side of the longest skip
+    for diff_d in [SKIP_MAX_D - 1, SKIP_MAX_D, SKIP_MAX_D + 1] {
+      let secs = FEB_29_1972_23_59_59.secs + diff_d * D_AS_S;
+      assert_eq!(Datetime::from(secs), FEB_29_1972_23_59_59.set(secs));
+    }
   }
 
   #[test]
@@ -344,4 +1404,599 @@ mod test {
     // 2024
     assert_eq!(String::from("Tue, 31 Dec 2024 23:59:59 GMT"), DEC_31_2024_23_59_59.for_header());
   }
+
+  #[test]
+  fn datetime_write_header_io() {
+
+    let mut buf = Vec::new();
+    for dt in [JAN_01_1970_00_00_00, FEB_29_1972_23_59_59, DEC_31_2024_23_59_59] {
+      dt.write_header_io(&mut buf).unwrap();
+      buf.extend_from_slice(b"\r\n");
+    }
+    assert_eq!(b"Thu, 01 Jan 1970 00:00:00 GMT\r\nTue, 29 Feb 1972 23:59:59 GMT\r\nTue, 31 Dec 2024 23:59:59 GMT\r\n", &buf[..]);
+
+    // errors passed on from the writer
+    let mut full = [0; IMF_FIXDATE_LEN - 1];
+    let err = JAN_01_1970_00_00_00.write_header_io(&mut &mut full[..]).unwrap_err();
+    assert_eq!(std::io::ErrorKind::WriteZero, err.kind());
+  }
+
+  #[test]
+  fn datetime_debug() {
+
+    assert_eq!("Datetime(\"Thu, 01 Jan 1970 00:00:00 GMT\", secs: 0)",         format!("{:?}", JAN_01_1970_00_00_00));
+    assert_eq!("Datetime(\"Tue, 31 Dec 2024 23:59:59 GMT\", secs: 1735689599)", format!("{:?}", DEC_31_2024_23_59_59));
+    assert_eq!("Some(Datetime(\"Thu, 01 Jan 1970 00:00:00 GMT\", secs: 0))",   format!("{:?}", Some (JAN_01_1970_00_00_00)));
+  }
+
+  #[test]
+  fn datetime_for_header_bytes() {
+
+    let dts = [
+      JAN_01_1970_00_00_00, FEB_28_1970_23_59_59, MAR_01_1970_00_00_00, APR_30_1970_23_59_59,
+      MAY_01_1970_00_00_00, JUL_31_1970_23_59_59, SEP_01_1970_00_00_00, DEC_31_1970_23_59_59,
+      JAN_01_1972_00_00_00, FEB_29_1972_23_59_59, MAR_01_1972_00_00_00, DEC_31_1972_23_59_59,
+      JAN_01_2000_00_00_00, DEC_31_2000_23_59_59,
+      DEC_31_2024_23_59_59
+    ];
+
+    for dt in dts {
+      let bytes = dt.for_header_bytes();
+      assert_eq!(IMF_FIXDATE_LEN, bytes.len());
+      assert_eq!(dt.for_header().as_bytes(), &bytes);
+      assert_eq!(IMF_FIXDATE_LEN, dt.for_header().capacity());
+    }
+  }
+
+  #[test]
+  fn datetime_abs_diff() {
+
+    assert_eq!(Duration::from_secs(Y_365_AS_S - 1), JAN_01_1970_00_00_00.abs_diff(&DEC_31_1970_23_59_59));
+    assert_eq!(Duration::from_secs(Y_365_AS_S - 1), DEC_31_1970_23_59_59.abs_diff(&JAN_01_1970_00_00_00));
+    assert_eq!(Duration::ZERO,                      DEC_31_1970_23_59_59.abs_diff(&DEC_31_1970_23_59_59));
+    assert_eq!(Duration::from_secs(300),            DEC_31_2024_23_59_59.abs_diff(&Datetime::from(DEC_31_2024_23_59_59.secs + 300)));
+  }
+
+  #[test]
+  fn datetime_is_same() {
+
+    let dt = Datetime::from(784_111_777);
+
+    assert!( dt.is_same_second(&dt));
+    assert!(!dt.is_same_second(&Datetime::from(784_111_778)));
+    assert!( dt.is_same_minute(&Datetime::from(784_111_779)));
+    assert!(!dt.is_same_minute(&Datetime::from(784_111_800)));
+    assert!( dt.is_same_day(&Datetime::from(784_080_000)));
+    assert!( dt.is_same_day(&Datetime::from(784_166_399)));
+    assert!(!dt.is_same_day(&Datetime::from(784_166_400)));
+
+    let st = SystemTime::UNIX_EPOCH + Duration::from_millis(784_111_777_500);
+    assert!(dt.is_same_second(&Datetime::try_from(st).unwrap()));
+    assert!(Datetime::try_from(SystemTime::UNIX_EPOCH - Duration::from_secs(1)).is_err());
+  }
+
+  #[test]
+  fn datetime_duration_until_next() {
+
+    let dt = Datetime::from(784_111_777);
+
+    assert_eq!(Duration::from_secs(     1), dt.duration_until_next_second());
+    assert_eq!(Duration::from_secs(    23), dt.duration_until_next_minute());
+    assert_eq!(Duration::from_secs(   623), dt.duration_until_next_hour());
+    assert_eq!(Duration::from_secs( 54623), dt.duration_until_next_day());
+
+    assert_eq!(Duration::from_secs(     1), JAN_01_1970_00_00_00.duration_until_next_second());
+    assert_eq!(Duration::from_secs(    60), JAN_01_1970_00_00_00.duration_until_next_minute());
+    assert_eq!(Duration::from_secs(  3600), JAN_01_1970_00_00_00.duration_until_next_hour());
+    assert_eq!(Duration::from_secs(D_AS_S), JAN_01_1970_00_00_00.duration_until_next_day());
+    assert_eq!(Duration::from_secs(     1), DEC_31_2024_23_59_59.duration_until_next_day());
+  }
+
+  #[test]
+  fn datetime_days_since_epoch() {
+
+    let dts = [
+      JAN_01_1970_00_00_00, FEB_28_1970_23_59_59, MAR_01_1970_00_00_00, DEC_31_1970_23_59_59,
+      FEB_29_1972_23_59_59, JAN_01_2000_00_00_00, DEC_31_2024_23_59_59
+    ];
+
+    for dt in dts {
+      assert_eq!(dt.date.days_since_epoch(), dt.days_since_epoch());
+    }
+    assert_eq!(20088, DEC_31_2024_23_59_59.days_since_epoch());
+  }
+
+  #[test]
+  fn datetime_midnight() {
+
+    assert_eq!(Ok (JAN_01_1970_00_00_00), Datetime::midnight(&JAN_01_1970_00_00_00.date));
+    assert_eq!(Ok (MAR_01_1970_00_00_00), Datetime::midnight(&MAR_01_1970_00_00_00.date));
+    assert_eq!(Ok (Datetime::from(FEB_29_1972_23_59_59.secs - (D_AS_S - 1))), Datetime::midnight(&FEB_29_1972_23_59_59.date));
+
+    // out of range, the pub fields unchecked
+    let date = JAN_01_1970_00_00_00.date;
+    for date in [
+      Date { y: Year(1969),  ..date },
+      Date { y: Year(10000), ..date },
+      Date { d: 0,           ..date },
+      Date { d: 31, m: Month::Feb, ..date },
+      Date { d: 29, m: Month::Feb, ..date }
+    ] {
+      assert_eq!(Err(ComponentError::Range), Datetime::midnight(&date));
+      assert_eq!(Err(ComponentError::Range), Datetime::at(&date, 0, 0, 0));
+    }
+  }
+
+  #[test]
+  fn datetime_at() {
+
+    assert_eq!(Ok (FEB_28_1970_23_59_59), Datetime::at(&FEB_28_1970_23_59_59.date, 23, 59, 59));
+    assert_eq!(Ok (DEC_31_2024_23_59_59), Datetime::at(&DEC_31_2024_23_59_59.date, 23, 59, 59));
+    assert_eq!(Ok (JAN_01_1970_00_00_00), Datetime::at(&JAN_01_1970_00_00_00.date,  0,  0,  0));
+
+    assert_eq!(Err(ComponentError::Range), Datetime::at(&JAN_01_1970_00_00_00.date, 24,  0,  0));
+    assert_eq!(Err(ComponentError::Range), Datetime::at(&JAN_01_1970_00_00_00.date,  0, 60,  0));
+    assert_eq!(Err(ComponentError::Range), Datetime::at(&JAN_01_1970_00_00_00.date,  0,  0, 60));
+  }
+
+  #[test]
+  fn datetime_from_ordinal_date() {
+
+    assert_eq!(Ok (JAN_01_1970_00_00_00), Datetime::from_ordinal_date(1970,   1,  0,  0,  0));
+    assert_eq!(Ok (FEB_28_1970_23_59_59), Datetime::from_ordinal_date(1970,  59, 23, 59, 59));
+    assert_eq!(Ok (MAR_01_1970_00_00_00), Datetime::from_ordinal_date(1970,  60,  0,  0,  0));
+    assert_eq!(Ok (FEB_29_1972_23_59_59), Datetime::from_ordinal_date(1972,  60, 23, 59, 59));
+    assert_eq!(Ok (DEC_31_2024_23_59_59), Datetime::from_ordinal_date(2024, 366, 23, 59, 59));
+
+    assert_eq!(Err(ComponentError::Range), Datetime::from_ordinal_date(1970,   0,  0,  0,  0));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_ordinal_date(1970, 366,  0,  0,  0));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_ordinal_date(1969,   1,  0,  0,  0));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_ordinal_date(10000,  1,  0,  0,  0));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_ordinal_date(1970,   1, 24,  0,  0));
+  }
+
+  #[test]
+  fn datetime_iso_week() {
+
+    assert_eq!((1970,  1), JAN_01_1970_00_00_00.iso_week());
+    assert_eq!((1970,  9), FEB_28_1970_23_59_59.iso_week());
+    assert_eq!((1970, 53), DEC_31_1970_23_59_59.iso_week());
+    assert_eq!((1971, 52), JAN_01_1972_00_00_00.iso_week());
+    assert_eq!((2025,  1), DEC_31_2024_23_59_59.iso_week());
+    assert_eq!((2020, 53), Datetime::parse("Sun, 03 Jan 2021 00:00:00 GMT").unwrap().iso_week());
+    assert_eq!((2021,  1), Datetime::parse("Mon, 04 Jan 2021 00:00:00 GMT").unwrap().iso_week());
+  }
+
+  #[test]
+  fn datetime_from_iso_week_date() {
+
+    assert_eq!(Ok (JAN_01_1970_00_00_00), Datetime::from_iso_week_date(1970,  1, Weekday::Thu));
+    assert_eq!(Datetime::midnight(&DEC_31_1970_23_59_59.date), Datetime::from_iso_week_date(1970, 53, Weekday::Thu));
+    assert_eq!(Datetime::midnight(&DEC_31_2024_23_59_59.date), Datetime::from_iso_week_date(2025,  1, Weekday::Tue));
+    assert_eq!(Datetime::midnight(&JAN_01_1972_00_00_00.date), Datetime::from_iso_week_date(1971, 52, Weekday::Sat));
+
+    // round trip across the years
+    let mut secs = 0;
+    while secs < 253_402_300_800 {
+      let dt = Datetime::midnight(&Datetime::from(secs).date).unwrap();
+      let (y, week) = dt.iso_week();
+      assert_eq!(Ok (dt), Datetime::from_iso_week_date(y, week, dt.date.wd));
+      secs += D_AS_S * 397;
+    }
+
+    assert_eq!(Err(ComponentError::Range), Datetime::from_iso_week_date(1970,  1, Weekday::Wed));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_iso_week_date(1970,  0, Weekday::Thu));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_iso_week_date(1971, 53, Weekday::Mon));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_iso_week_date(9999, 52, Weekday::Sat));
+    assert_eq!(Err(ComponentError::Range), Datetime::from_iso_week_date(1969, 52, Weekday::Mon));
+  }
+
+  #[test]
+  fn datetime_from_parts() {
+
+    for dt in [JAN_01_1970_00_00_00, FEB_28_1970_23_59_59, FEB_29_1972_23_59_59, DEC_31_2024_23_59_59] {
+      assert_eq!(Ok (dt), Datetime::from_parts(dt.date, dt.time));
+    }
+
+    let dt = FEB_28_1970_23_59_59;
+
+    assert_eq!(Ok (MAR_01_1970_00_00_00.set(M_31_AS_S + M_28_AS_S + D_AS_S - 1)), Datetime::from_parts(Date { d: 1, wd: Weekday::Sun, m: Month::Mar, ..dt.date }, dt.time));
+
+    assert_eq!(Err(ComponentError::Range),   Datetime::from_parts(Date { d: 29, ..dt.date }, dt.time));
+    assert_eq!(Err(ComponentError::Range),   Datetime::from_parts(Date { d:  0, ..dt.date }, dt.time));
+    assert_eq!(Err(ComponentError::Range),   Datetime::from_parts(Date { y: Year(1969), ..dt.date }, dt.time));
+    assert_eq!(Err(ComponentError::Range),   Datetime::from_parts(dt.date, Time { h: 24, ..dt.time }));
+    assert_eq!(Err(ComponentError::Range),   Datetime::from_parts(dt.date, Time { s: 60, ..dt.time }));
+    assert_eq!(Err(ComponentError::Weekday), Datetime::from_parts(Date { wd: Weekday::Sun, ..dt.date }, dt.time));
+  }
+
+  #[test]
+  fn datetime_accessors() {
+
+    assert_eq!(FEB_28_1970_23_59_59.date, FEB_28_1970_23_59_59.date());
+    assert_eq!(FEB_28_1970_23_59_59.time, FEB_28_1970_23_59_59.time());
+    assert_eq!(FEB_28_1970_23_59_59.secs, FEB_28_1970_23_59_59.secs());
+  }
+
+  #[test]
+  fn datetime_with() {
+
+    assert_eq!(Ok (FEB_28_1970_23_59_59), JAN_01_1970_00_00_00.with_day(28).and_then(|dt| dt.with_month(Month::Feb)).and_then(|dt| dt.with_hour(23)).and_then(|dt| dt.with_minute(59)).and_then(|dt| dt.with_second(59)));
+    assert_eq!(Ok (FEB_29_1972_23_59_59), FEB_28_1970_23_59_59.with_year(1972).and_then(|dt| dt.with_day(29)));
+    assert_eq!(Ok (DEC_31_2024_23_59_59), DEC_31_1970_23_59_59.with_year(2024));
+
+    // each consistent with the seconds
+    let dt = FEB_29_1972_23_59_59.with_month(Month::Mar).unwrap();
+    assert_eq!(Ok (dt), Datetime::from_parts(dt.date, dt.time));
+    assert_eq!(Datetime::from(dt.secs), dt);
+
+    assert_eq!(Err(ComponentError::Range), FEB_28_1970_23_59_59.with_day(29));
+    assert_eq!(Err(ComponentError::Range), FEB_28_1970_23_59_59.with_day(0));
+    assert_eq!(Err(ComponentError::Range), FEB_29_1972_23_59_59.with_year(1973));
+    assert_eq!(Err(ComponentError::Range), DEC_31_1970_23_59_59.with_year(1969));
+    assert_eq!(Err(ComponentError::Range), DEC_31_1970_23_59_59.with_year(10_000));
+    assert_eq!(Err(ComponentError::Range), DEC_31_1970_23_59_59.with_month(Month::Nov));
+    assert_eq!(Err(ComponentError::Range), JAN_01_1970_00_00_00.with_hour(24));
+    assert_eq!(Err(ComponentError::Range), JAN_01_1970_00_00_00.with_minute(60));
+    assert_eq!(Err(ComponentError::Range), JAN_01_1970_00_00_00.with_second(60));
+  }
+
+  #[test]
+  fn datetime_within_skew() {
+
+    let dt = Datetime::from(1000);
+
+    assert!( dt.within_skew(&Datetime::from(1000), Duration::ZERO));
+    assert!( dt.within_skew(&Datetime::from( 700), Duration::from_secs(300)));
+    assert!( dt.within_skew(&Datetime::from(1300), Duration::from_secs(300)));
+    assert!(!dt.within_skew(&Datetime::from( 699), Duration::from_secs(300)));
+    assert!(!dt.within_skew(&Datetime::from(1301), Duration::from_secs(300)));
+  }
+
+  #[test]
+  fn datetime_clamp() {
+
+    let min = MAR_01_1970_00_00_00;
+    let max = DEC_31_1970_23_59_59;
+
+    assert_eq!(min, JAN_01_1970_00_00_00.clamp(&min, &max));
+    assert_eq!(min, min.clamp(&min, &max));
+    assert_eq!(Datetime::from(D_AS_S * 100), Datetime::from(D_AS_S * 100).clamp(&min, &max));
+    assert_eq!(max, max.clamp(&min, &max));
+    assert_eq!(max, JAN_01_1972_00_00_00.clamp(&min, &max));
+    assert_eq!(min, JAN_01_1972_00_00_00.clamp(&min, &min));
+
+    assert_eq!(min, min.min(&max));
+    assert_eq!(min, max.min(&min));
+    assert_eq!(max, min.max(&max));
+    assert_eq!(max, max.max(&min));
+  }
+
+  #[test]
+  #[should_panic]
+  fn datetime_clamp_invalid() {
+
+    JAN_01_1970_00_00_00.clamp(&DEC_31_1970_23_59_59, &MAR_01_1970_00_00_00);
+  }
+
+  #[test]
+  fn datetime_advance_to_next() {
+
+    let dt = Datetime::from(784_111_777);
+    let at = |s: &str| s.parse::<Time>().unwrap();
+
+    assert_eq!("Sun, 06 Nov 1994 08:49:38 GMT", dt.advance_to_next(Weekday::Sun, &at("08:49:38")).unwrap().for_header());
+    assert_eq!("Sun, 13 Nov 1994 08:49:37 GMT", dt.advance_to_next(Weekday::Sun, &at("08:49:37")).unwrap().for_header());
+    assert_eq!("Sun, 13 Nov 1994 03:00:00 GMT", dt.advance_to_next(Weekday::Sun, &at("03:00:00")).unwrap().for_header());
+    assert_eq!("Mon, 07 Nov 1994 00:00:00 GMT", dt.advance_to_next(Weekday::Mon, &at("00:00:00")).unwrap().for_header());
+    assert_eq!("Sat, 12 Nov 1994 23:59:59 GMT", dt.advance_to_next(Weekday::Sat, &at("23:59:59")).unwrap().for_header());
+    assert_eq!("Thu, 01 Jan 1970 00:00:01 GMT", JAN_01_1970_00_00_00.advance_to_next(Weekday::Thu, &at("00:00:01")).unwrap().for_header());
+    assert_eq!("Thu, 02 Jan 2025 00:00:00 GMT", DEC_31_2024_23_59_59.advance_to_next(Weekday::Thu, &at("00:00:00")).unwrap().for_header());
+
+    assert_eq!(Err(ComponentError::Range), dt.advance_to_next(Weekday::Sun, &Time { h: 24, m:  0, s:  0 }));
+    assert_eq!(Err(ComponentError::Range), dt.advance_to_next(Weekday::Sun, &Time { h:  0, m: 60, s:  0 }));
+    assert_eq!(Err(ComponentError::Range), dt.advance_to_next(Weekday::Sun, &Time { h:  0, m:  0, s: 60 }));
+  }
+
+  #[test]
+  fn datetime_quarter() {
+
+    assert_eq!(1, JAN_01_1970_00_00_00.quarter());
+    assert_eq!(1, MAR_01_1970_00_00_00.quarter());
+    assert_eq!(2, APR_30_1970_23_59_59.quarter());
+    assert_eq!(3, JUL_31_1970_23_59_59.quarter());
+    assert_eq!(3, SEP_01_1970_00_00_00.quarter());
+    assert_eq!(4, DEC_31_2024_23_59_59.quarter());
+  }
+
+  #[test]
+  fn datetime_month_bounds() {
+
+    assert_eq!(JAN_01_1970_00_00_00, JAN_01_1970_00_00_00.start_of_month());
+    assert_eq!(MAR_01_1970_00_00_00, MAR_01_1970_00_00_00.start_of_month());
+    assert_eq!(MAY_01_1970_00_00_00, Datetime::from(MAY_01_1970_00_00_00.secs + M_30_AS_S).start_of_month());
+    assert_eq!(JAN_01_1972_00_00_00, JAN_01_1972_00_00_00.set(Y_365_AS_S * 2 + M_31_AS_S - 1).start_of_month());
+
+    assert_eq!(FEB_28_1970_23_59_59, Datetime::from(M_31_AS_S).end_of_month());
+    assert_eq!(FEB_29_1972_23_59_59, FEB_29_1972_23_59_59.end_of_month());
+    assert_eq!(APR_30_1970_23_59_59, Datetime::from(M_31_AS_S * 2 + M_28_AS_S + 1).end_of_month());
+    assert_eq!(DEC_31_1970_23_59_59, Datetime::from(Y_365_AS_S - M_31_AS_S).end_of_month());
+    assert_eq!(DEC_31_2024_23_59_59, DEC_31_2024_23_59_59.end_of_month());
+  }
+
+  #[test]
+  fn datetime_days_remaining() {
+
+    assert_eq!(30, JAN_01_1970_00_00_00.days_remaining_in_month());
+    assert_eq!( 0, FEB_28_1970_23_59_59.days_remaining_in_month());
+    assert_eq!(30, MAR_01_1970_00_00_00.days_remaining_in_month());
+    assert_eq!( 0, FEB_29_1972_23_59_59.days_remaining_in_month());
+    assert_eq!(28, JAN_01_1972_00_00_00.set(Y_365_AS_S * 2 + M_31_AS_S).days_remaining_in_month());
+    assert_eq!( 0, DEC_31_2024_23_59_59.days_remaining_in_month());
+
+    assert_eq!(364, JAN_01_1970_00_00_00.days_remaining_in_year());
+    assert_eq!(306, FEB_28_1970_23_59_59.days_remaining_in_year());
+    assert_eq!(  0, DEC_31_1970_23_59_59.days_remaining_in_year());
+    assert_eq!(365, JAN_01_1972_00_00_00.days_remaining_in_year());
+    assert_eq!(306, FEB_29_1972_23_59_59.days_remaining_in_year());
+    assert_eq!(  0, DEC_31_2024_23_59_59.days_remaining_in_year());
+  }
+
+  #[test]
+  fn datetime_julian_day() {
+
+    assert_eq!(2_440_587.5, JAN_01_1970_00_00_00.to_julian_day());
+    assert_eq!(2_451_544.5, JAN_01_2000_00_00_00.to_julian_day());
+    assert_eq!(   40_587.0, JAN_01_1970_00_00_00.to_modified_julian_day());
+    assert_eq!(   51_544.0, JAN_01_2000_00_00_00.to_modified_julian_day());
+
+    assert_eq!(Some (JAN_01_1970_00_00_00), Datetime::from_julian_day(2_440_587.5));
+    assert_eq!(Some (JAN_01_2000_00_00_00), Datetime::from_modified_julian_day(51_544.0));
+
+    for dt in [FEB_29_1972_23_59_59, DEC_31_2024_23_59_59, Datetime::from(784_111_777)] {
+      assert_eq!(Some (dt), Datetime::from_julian_day(dt.to_julian_day()));
+      assert_eq!(Some (dt), Datetime::from_modified_julian_day(dt.to_modified_julian_day()));
+    }
+
+    assert_eq!(None, Datetime::from_julian_day(2_440_587.0));
+    assert_eq!(None, Datetime::from_modified_julian_day(3_000_000.0));
+    assert_eq!(None, Datetime::from_modified_julian_day(f64::NAN));
+    assert_eq!(None, Datetime::from_modified_julian_day(f64::INFINITY));
+  }
+
+  #[test]
+  fn datetime_add_sub_duration() {
+
+    assert_eq!(FEB_28_1970_23_59_59, JAN_01_1970_00_00_00 + Duration::from_secs(M_31_AS_S + M_28_AS_S - 1));
+    assert_eq!(MAR_01_1970_00_00_00, FEB_28_1970_23_59_59 + Duration::from_millis(1_999));
+    assert_eq!(FEB_28_1970_23_59_59, MAR_01_1970_00_00_00 - Duration::from_secs(1));
+    assert_eq!(JAN_01_1970_00_00_00, MAR_01_1970_00_00_00 - Duration::from_secs(M_31_AS_S + M_28_AS_S));
+
+    let mut dt = DEC_31_1970_23_59_59;
+    dt += Duration::from_secs(Y_365_AS_S + 1);
+    assert_eq!(JAN_01_1972_00_00_00, dt);
+    dt -= Duration::from_secs(Y_365_AS_S * 2);
+    assert_eq!(JAN_01_1970_00_00_00, dt);
+  }
+
+  #[test]
+  #[should_panic(expected = "overflow when adding duration to datetime")]
+  fn datetime_add_duration_overflow() {
+
+    let _ = DEC_31_2024_23_59_59 + Duration::from_secs(MAX_SECS);
+  }
+
+  #[test]
+  #[should_panic(expected = "overflow when subtracting duration from datetime")]
+  fn datetime_sub_duration_overflow() {
+
+    let _ = MAR_01_1970_00_00_00 - Duration::from_secs(Y_365_AS_S);
+  }
+
+  #[test]
+  fn datetime_checked_add_sub() {
+
+    assert_eq!(Some (FEB_28_1970_23_59_59), JAN_01_1970_00_00_00.checked_add(Duration::from_secs(M_31_AS_S + M_28_AS_S - 1)));
+    assert_eq!(Some (FEB_28_1970_23_59_59), MAR_01_1970_00_00_00.checked_sub(Duration::from_secs(1)));
+    assert_eq!(Some (Datetime::from(MAX_SECS)), JAN_01_1970_00_00_00.checked_add(Duration::from_secs(MAX_SECS)));
+
+    assert_eq!(None, JAN_01_1970_00_00_00.checked_add(Duration::from_secs(MAX_SECS + 1)));
+    assert_eq!(None, DEC_31_2024_23_59_59.checked_add(Duration::MAX));
+    assert_eq!(None, JAN_01_1970_00_00_00.checked_sub(Duration::from_secs(1)));
+  }
+
+  #[test]
+  fn datetime_le_bytes() {
+
+    assert_eq!([0; 8],                               JAN_01_1970_00_00_00.to_le_bytes());
+    assert_eq!([0xA1, 0x98, 0xBC, 0x2E, 0, 0, 0, 0], Datetime::from(784_111_777).to_le_bytes());
+
+    for dt in [JAN_01_1970_00_00_00, FEB_29_1972_23_59_59, DEC_31_2024_23_59_59] {
+      assert_eq!(Some (dt), Datetime::from_le_bytes(dt.to_le_bytes()));
+    }
+
+    assert_eq!(Some (Datetime::from(MAX_SECS)), Datetime::from_le_bytes(MAX_SECS.to_le_bytes()));
+    assert_eq!(None,                            Datetime::from_le_bytes((MAX_SECS + 1).to_le_bytes()));
+    assert_eq!(None,                            Datetime::from_le_bytes([0xFF; 8]));
+  }
+
+  #[test]
+  fn datetime_format_many() {
+
+    let dts = [
+      JAN_01_1970_00_00_00, FEB_28_1970_23_59_59, MAR_01_1970_00_00_00,
+      JAN_01_1970_00_00_00.set(1), JAN_01_1970_00_00_00.set(D_AS_S - 1),
+      DEC_31_2024_23_59_59
+    ];
+
+    let mut out = String::new();
+    Datetime::format_many(&dts, &mut out).unwrap();
+
+    let expected = dts.iter()
+      .map(|dt| dt.for_header() + "\n")
+      .collect::<String>();
+    assert_eq!(expected, out);
+
+    let mut out = String::new();
+    Datetime::format_many(&[], &mut out).unwrap();
+    assert_eq!("", out);
+  }
+
+  #[test]
+  fn datetime_since_calendar() {
+
+    let diff = |later: &str, earlier: &str| {
+      let CalendarDiff { years, months, days, hours, minutes, seconds } = Datetime::parse(later).unwrap()
+        .since_calendar(&Datetime::parse(earlier).unwrap());
+      (years, months, days, hours, minutes, seconds)
+    };
+
+    assert_eq!(( 0,  0,  0,  0,  0,  1), diff("Thu, 01 Jan 1970 00:00:01 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 0,  0,  0, 23, 59, 59), diff("Thu, 01 Jan 1970 23:59:59 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 0,  1,  0,  0,  0,  0), diff("Sun, 01 Feb 1970 00:00:00 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 0,  0, 30, 23, 59, 59), diff("Sat, 31 Jan 1970 23:59:59 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 1,  0,  0,  0,  0,  0), diff("Fri, 01 Jan 1971 00:00:00 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 0, 11, 30,  0,  0,  0), diff("Thu, 31 Dec 1970 00:00:00 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+
+    // the day limited to the length of the month
+    assert_eq!(( 0,  1,  1,  0,  0,  0), diff("Sun, 01 Mar 1970 00:00:00 GMT", "Sat, 31 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 0,  1,  0,  0,  0,  0), diff("Sat, 28 Feb 1970 00:00:00 GMT", "Sat, 31 Jan 1970 00:00:00 GMT"));
+    assert_eq!(( 1,  0,  0,  0,  0,  0), diff("Wed, 28 Feb 1973 00:00:00 GMT", "Tue, 29 Feb 1972 00:00:00 GMT"));
+    assert_eq!(( 0, 11, 30,  0,  0,  0), diff("Tue, 27 Feb 1973 00:00:00 GMT", "Mon, 28 Feb 1972 00:00:00 GMT"));
+
+    // a time of day earlier than that of the earlier datetime
+    assert_eq!(( 0,  0, 27, 23,  0,  0), diff("Sat, 28 Feb 1970 00:00:00 GMT", "Sat, 31 Jan 1970 01:00:00 GMT"));
+    assert_eq!((24, 10,  5,  8, 49, 37), diff("Sun, 06 Nov 1994 08:49:37 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"));
+
+    assert_eq!(CalendarDiff::default(), DEC_31_1970_23_59_59.since_calendar(&DEC_31_1970_23_59_59));
+    assert_eq!(CalendarDiff::default(), JAN_01_1970_00_00_00.since_calendar(&DEC_31_1970_23_59_59));
+  }
+
+  #[test]
+  fn datetime_humanize_since() {
+
+    let now = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    let at = |diff: i64| now.set(now.secs.saturating_add_signed(diff));
+
+    let cases = [
+      (         0, "now",             "now"),
+      (         1, "1 second ago",    "in 1 second"),
+      (        59, "59 seconds ago",  "in 59 seconds"),
+      (        60, "1 minute ago",    "in 1 minute"),
+      (       200, "3 minutes ago",   "in 3 minutes"),
+      (      7199, "1 hour ago",      "in 1 hour"),
+      (    86_399, "23 hours ago",    "in 23 hours"),
+      (    86_400, "1 day ago",       "in 1 day"),
+      // in calendar units, so differing across months and
+      // years of differing lengths
+      ( 2_592_000, "30 days ago",     "in 1 month"),
+      (31_536_000, "1 year ago",      "in 1 year"),
+      (63_072_000, "2 years ago",     "in 1 year")
+    ];
+
+    for (secs, past, future) in cases {
+      assert_eq!(past,   at(-secs).humanize_since(&now));
+      assert_eq!(future, at( secs).humanize_since(&now));
+    }
+
+    assert_eq!("24 years ago", JAN_01_1970_00_00_00.humanize_since(&now));
+  }
+
+  #[test]
+  fn datetime_add_months() {
+
+    let add = |ts: &str, months: u64, overflow: DayOverflow| {
+      Datetime::parse(ts).unwrap().add_months(months, overflow).unwrap().for_header()
+    };
+
+    assert_eq!("Sun, 01 Feb 1970 00:00:00 GMT", add("Thu, 01 Jan 1970 00:00:00 GMT",   1, DayOverflow::Clamp));
+    assert_eq!("Fri, 01 Jan 1971 00:00:00 GMT", add("Thu, 01 Jan 1970 00:00:00 GMT",  12, DayOverflow::Clamp));
+    assert_eq!("Thu, 31 Dec 1970 23:59:59 GMT", add("Thu, 31 Dec 1970 23:59:59 GMT",   0, DayOverflow::Roll));
+    assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", add("Fri, 06 Nov 1970 08:49:37 GMT", 288, DayOverflow::Roll));
+
+    // beyond the length of the month
+    assert_eq!("Sat, 28 Feb 1970 23:59:59 GMT", add("Sat, 31 Jan 1970 23:59:59 GMT",   1, DayOverflow::Clamp));
+    assert_eq!("Tue, 03 Mar 1970 23:59:59 GMT", add("Sat, 31 Jan 1970 23:59:59 GMT",   1, DayOverflow::Roll));
+    assert_eq!("Tue, 29 Feb 1972 00:00:00 GMT", add("Sat, 31 Jan 1970 00:00:00 GMT",  25, DayOverflow::Clamp));
+    assert_eq!("Thu, 02 Mar 1972 00:00:00 GMT", add("Sat, 31 Jan 1970 00:00:00 GMT",  25, DayOverflow::Roll));
+    assert_eq!("Thu, 30 Apr 1970 00:00:00 GMT", add("Tue, 31 Mar 1970 00:00:00 GMT",   1, DayOverflow::Clamp));
+    assert_eq!("Fri, 01 May 1970 00:00:00 GMT", add("Tue, 31 Mar 1970 00:00:00 GMT",   1, DayOverflow::Roll));
+
+    let dt = FEB_29_1972_23_59_59;
+    assert_eq!("Wed, 28 Feb 1973 23:59:59 GMT", dt.add_years(1, DayOverflow::Clamp).unwrap().for_header());
+    assert_eq!("Thu, 01 Mar 1973 23:59:59 GMT", dt.add_years(1, DayOverflow::Roll).unwrap().for_header());
+    assert_eq!("Sun, 29 Feb 1976 23:59:59 GMT", dt.add_years(4, DayOverflow::Clamp).unwrap().for_header());
+    assert_eq!("Sun, 29 Feb 1976 23:59:59 GMT", dt.add_years(4, DayOverflow::Roll).unwrap().for_header());
+
+    assert_eq!(Err(ComponentError::Range), DEC_31_2024_23_59_59.add_years(7976, DayOverflow::Clamp));
+    assert_eq!(Err(ComponentError::Range), DEC_31_2024_23_59_59.add_months(u64::MAX, DayOverflow::Clamp));
+    assert_eq!(Err(ComponentError::Range), DEC_31_2024_23_59_59.add_years(u64::MAX, DayOverflow::Clamp));
+    assert!(DEC_31_2024_23_59_59.add_years(7975, DayOverflow::Roll).is_ok());
+  }
+
+  #[test]
+  fn datetime_seconds_until() {
+
+    assert_eq!(Y_365_AS_S - 1, JAN_01_1970_00_00_00.seconds_until(&DEC_31_1970_23_59_59));
+    assert_eq!(             1, DEC_31_1970_23_59_59.seconds_until(&JAN_01_1972_00_00_00) - Y_365_AS_S);
+    assert_eq!(             0, DEC_31_1970_23_59_59.seconds_until(&DEC_31_1970_23_59_59));
+    assert_eq!(             0, DEC_31_1970_23_59_59.seconds_until(&JAN_01_1970_00_00_00));
+  }
+
+  #[test]
+  fn datetime_sleep_until() {
+
+    let dt_new = Datetime::new().unwrap();
+
+    dt_new.set(dt_new.secs + 1).sleep_until();
+
+    assert!(Datetime::raw().unwrap() > dt_new.secs);
+
+    let before = SystemTime::now();
+    JAN_01_1970_00_00_00.sleep_until();
+
+    assert!(before.elapsed().unwrap() < Duration::from_secs(1));
+  }
+
+  #[cfg(feature = "clock-override")]
+  #[test]
+  fn datetime_sleep_until_frozen() {
+
+    let guard = crate::clock::FreezeGuard::new(&JAN_01_1970_00_00_00);
+
+    // reached per the clock frozen, though not per the system clock, and the reverse
+    assert_eq!(Duration::ZERO, JAN_01_1970_00_00_00.remaining());
+    guard.set(&DEC_31_2024_23_59_59);
+    let remaining = JAN_01_1970_00_00_00.set(DEC_31_2024_23_59_59.secs + 2).remaining();
+    assert!(remaining > Duration::from_secs(1) && remaining <= Duration::from_secs(2));
+  }
+
+  #[cfg(feature = "tokio")]
+  #[test]
+  fn datetime_sleep_until_async() {
+
+    let dt_new = Datetime::new().unwrap();
+
+    tokio::runtime::Builder::new_current_thread()
+      .enable_time()
+      .build().unwrap()
+      .block_on(dt_new.set(dt_new.secs + 1).sleep_until_async());
+
+    assert!(Datetime::raw().unwrap() > dt_new.secs);
+  }
+
+  #[cfg(feature = "rkyv")]
+  #[test]
+  fn datetime_rkyv() {
+
+    use rkyv::rancor::Error;
+
+    let dt = FEB_29_1972_23_59_59;
+
+    let bytes = rkyv::to_bytes::<Error>(&dt).unwrap();
+    let archived = rkyv::access::<rkyv::Archived<Datetime>, Error>(&bytes).unwrap();
+
+    assert_eq!(dt.secs, archived.secs.to_native());
+    assert_eq!(dt, rkyv::deserialize::<Datetime, Error>(archived).unwrap());
+  }
 }
@@ -0,0 +1,188 @@
+//! # ffi
+//!
+//! `#[repr(C)]` mirrors of `Date`, `Time` and `Datetime`,
+//! available with the `ffi` feature, for passing values
+//! across a C interface by struct rather than as strings.
+//! Each converts from the core type and back via
+//! `try_from`, which checks the fields as for
+//! `Datetime::from_parts`. The layout is stable, i.e. in C:
+//!
+//! ```c
+//! typedef struct { uint16_t y; uint8_t m; uint8_t d; uint8_t wd; } httpdt_date;  /* 6 bytes, align 2 */
+//! typedef struct { uint8_t h; uint8_t m; uint8_t s; } httpdt_time;               /* 3 bytes, align 1 */
+//! typedef struct { uint64_t secs; httpdt_date date; httpdt_time time; } httpdt_datetime; /* 24 bytes, align 8 */
+//! ```
+
+use crate::datetime::{Datetime, ComponentError};
+use crate::date::{self, Weekday, Month, Year};
+use crate::time::Time;
+
+/// Mirrors `Date`, with the month from 1 for January and
+/// the weekday from 0 for Monday to 6 for Sunday, as for
+/// ISO 8601 less one.
+#[repr(C)]
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CDate {
+   pub y: u16,
+   pub m: u8,
+   pub d: u8,
+  pub wd: u8
+}
+
+/// Mirrors `Time`.
+#[repr(C)]
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CTime {
+  pub h: u8,
+  pub m: u8,
+  pub s: u8
+}
+
+/// Mirrors `Datetime`, with the number of seconds since
+/// the epoch alongside the date and time.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::Datetime;
+/// use httpdt::ffi::CDatetime;
+///
+/// let c = CDatetime::from(Datetime::from(784_111_777));
+///
+/// assert_eq!((1994, 11, 6, 6), (c.date.y, c.date.m, c.date.d, c.date.wd));
+/// assert_eq!(Ok (Datetime::from(784_111_777)), Datetime::try_from(c));
+/// ```
+#[repr(C)]
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CDatetime {
+  pub secs: u64,
+  pub date: CDate,
+  pub time: CTime
+}
+
+impl From<date::Date> for CDate {
+
+  fn from(date: date::Date) -> Self {
+    let Year(y) = date.y;
+    Self {
+       y: y as u16,
+       m: Month::iter().position(|m| m == date.m).unwrap_or_default() as u8 + 1,
+       d: date.d,
+      wd: date::iso_weekday(date.wd) as u8
+    }
+  }
+}
+
+impl TryFrom<CDate> for date::Date {
+  type Error = ComponentError;
+
+  fn try_from(c: CDate) -> Result<Self, Self::Error> {
+    let m = Month::iter().nth((c.m as usize).wrapping_sub(1)).ok_or(ComponentError::Range)?;
+    let wd = Weekday::iter().nth(c.wd as usize).ok_or(ComponentError::Range)?;
+    let date = Self { d: c.d, wd, m, y: Year(c.y as u64) };
+    // checked in full against a time of midnight
+    Datetime::from_parts(date, Time::default())?;
+    Ok (date)
+  }
+}
+
+impl From<Time> for CTime {
+
+  fn from(time: Time) -> Self {
+    Self { h: time.h, m: time.m, s: time.s }
+  }
+}
+
+impl From<CTime> for Time {
+
+  fn from(c: CTime) -> Self {
+    Self { h: c.h, m: c.m, s: c.s }
+  }
+}
+
+impl From<Datetime> for CDatetime {
+
+  fn from(dt: Datetime) -> Self {
+    Self { secs: dt.secs, date: dt.date.into(), time: dt.time.into() }
+  }
+}
+
+// with the seconds since the epoch required to agree with
+// the date and time
+impl TryFrom<CDatetime> for Datetime {
+  type Error = ComponentError;
+
+  fn try_from(c: CDatetime) -> Result<Self, Self::Error> {
+    let dt = Self::from_parts(c.date.try_into()?, c.time.into())?;
+    match dt.secs == c.secs {
+      true  => Ok (dt),
+      false => Err(ComponentError::Range)
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{CDate, CTime, CDatetime};
+  use crate::datetime::{Datetime, ComponentError};
+  use crate::date::Date;
+
+  use std::mem::{size_of, align_of, offset_of};
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+  const DEC_31_2024_23_59_59: u64 = 1_735_689_599;
+
+  #[test]
+  fn ffi_layout() {
+
+    assert_eq!((6, 2), (size_of::<CDate>(), align_of::<CDate>()));
+    assert_eq!((3, 1), (size_of::<CTime>(), align_of::<CTime>()));
+    assert_eq!((24, 8), (size_of::<CDatetime>(), align_of::<CDatetime>()));
+
+    assert_eq!((0, 2, 3, 4), (offset_of!(CDate, y), offset_of!(CDate, m), offset_of!(CDate, d), offset_of!(CDate, wd)));
+    assert_eq!((0, 8, 14), (offset_of!(CDatetime, secs), offset_of!(CDatetime, date), offset_of!(CDatetime, time)));
+  }
+
+  #[test]
+  fn ffi_from() {
+
+    let c = CDatetime::from(Datetime::from(NOV_06_1994_08_49_37));
+    assert_eq!(CDatetime {
+      secs: NOV_06_1994_08_49_37,
+      date: CDate { y: 1994, m: 11, d: 6, wd: 6 },
+      time: CTime { h: 8, m: 49, s: 37 }
+    }, c);
+
+    let c = CDatetime::from(Datetime::default());
+    assert_eq!(CDate { y: 1970, m: 1, d: 1, wd: 3 }, c.date);
+  }
+
+  #[test]
+  fn ffi_try_from() {
+
+    for secs in [0, NOV_06_1994_08_49_37, DEC_31_2024_23_59_59] {
+      let dt = Datetime::from(secs);
+      assert_eq!(Ok (dt),      Datetime::try_from(CDatetime::from(dt)));
+      assert_eq!(Ok (dt.date), Date::try_from(CDate::from(dt.date)));
+    }
+
+    let valid = CDatetime::from(Datetime::from(NOV_06_1994_08_49_37));
+    type Change = fn(&mut CDatetime);
+    let cases: [(Change, ComponentError); 8] = [
+      (|c| c.secs   += 1,    ComponentError::Range),
+      (|c| c.date.m  = 0,    ComponentError::Range),
+      (|c| c.date.m  = 13,   ComponentError::Range),
+      (|c| c.date.d  = 31,   ComponentError::Range),
+      (|c| c.date.y  = 1969, ComponentError::Range),
+      (|c| c.date.wd = 7,    ComponentError::Range),
+      (|c| c.date.wd = 0,    ComponentError::Weekday),
+      (|c| c.time.h  = 24,   ComponentError::Range)
+    ];
+    for (case, e) in cases {
+      let mut c = valid;
+      case(&mut c);
+      assert_eq!(Err(e), Datetime::try_from(c));
+    }
+  }
+}
@@ -0,0 +1,342 @@
+//! # Freshness
+//!
+//! Evaluation of the freshness of stored responses, per
+//! RFC 9111 section 4.2.
+
+use crate::datetime::Datetime;
+use crate::header::CacheControl;
+
+/// Stores the inputs to the freshness of a response held
+/// by a cache, i.e. the parsed Date and Expires header
+/// values, the Age header value and the Cache-Control
+/// max-age and s-maxage lifetimes and stale-while-revalidate
+/// and stale-if-error windows, each in seconds, with the
+/// times at which the request was made and the response
+/// received.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, StoredResponse, Freshness};
+///
+/// let request_time = Datetime::default().set(100);
+/// let response_time = Datetime::default().set(101);
+///
+/// let stored = StoredResponse::new(request_time, response_time)
+///   .date(Datetime::default().set(100))
+///   .max_age(60);
+///
+/// let now = Datetime::default().set(131);
+///
+/// assert_eq!(Freshness::Fresh(29), stored.freshness(&now, false));
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct StoredResponse {
+  pub request_time: Datetime,
+  pub response_time: Datetime,
+  pub date: Option<Datetime>,
+  pub expires: Option<Datetime>,
+  pub age: Option<u64>,
+  pub max_age: Option<u64>,
+  pub s_maxage: Option<u64>,
+  pub stale_while_revalidate: Option<u64>,
+  pub stale_if_error: Option<u64>
+}
+
+impl StoredResponse {
+
+  pub fn new(request_time: Datetime, response_time: Datetime) -> Self {
+    Self {
+      request_time,
+      response_time,
+      date: None,
+      expires: None,
+      age: None,
+      max_age: None,
+      s_maxage: None,
+      stale_while_revalidate: None,
+      stale_if_error: None
+    }
+  }
+
+  pub fn date(mut self, date: Datetime) -> Self {
+    self.date = Some (date);
+    self
+  }
+
+  pub fn expires(mut self, expires: Datetime) -> Self {
+    self.expires = Some (expires);
+    self
+  }
+
+  pub fn age(mut self, age: u64) -> Self {
+    self.age = Some (age);
+    self
+  }
+
+  pub fn max_age(mut self, max_age: u64) -> Self {
+    self.max_age = Some (max_age);
+    self
+  }
+
+  pub fn s_maxage(mut self, s_maxage: u64) -> Self {
+    self.s_maxage = Some (s_maxage);
+    self
+  }
+
+  pub fn stale_while_revalidate(mut self, stale_while_revalidate: u64) -> Self {
+    self.stale_while_revalidate = Some (stale_while_revalidate);
+    self
+  }
+
+  pub fn stale_if_error(mut self, stale_if_error: u64) -> Self {
+    self.stale_if_error = Some (stale_if_error);
+    self
+  }
+
+  /// Sets each lifetime and window present in a parsed
+  /// Cache-Control header value.
+  pub fn cache_control(mut self, cc: &CacheControl) -> Self {
+    self.max_age = cc.max_age.or(self.max_age);
+    self.s_maxage = cc.s_maxage.or(self.s_maxage);
+    self.stale_while_revalidate = cc.stale_while_revalidate.or(self.stale_while_revalidate);
+    self.stale_if_error = cc.stale_if_error.or(self.stale_if_error);
+    self
+  }
+
+  /// Determines whether the response is fresh at `now` and
+  /// for how much longer, or for how long it has been stale,
+  /// with s-maxage applied only by a `shared` cache. No
+  /// heuristic lifetime is used, so a response without an
+  /// explicit lifetime is always stale.
+  pub fn freshness(&self, now: &Datetime, shared: bool) -> Freshness {
+    let lifetime = self.freshness_lifetime(shared);
+    let age = self.current_age(now);
+    match lifetime > age {
+      true  => Freshness::Fresh(lifetime - age),
+      false => Freshness::Stale(age - lifetime)
+    }
+  }
+
+  /// Determines whether the response can be used at `now`,
+  /// i.e. whether it is fresh, stale but within the
+  /// stale-while-revalidate window, or the stale-if-error
+  /// window where an `error` was encountered, per RFC 5861,
+  /// or stale and unusable without validation.
+  pub fn usability(&self, now: &Datetime, shared: bool, error: bool) -> Usability {
+    let window = match error {
+      true  => self.stale_if_error,
+      false => self.stale_while_revalidate
+    };
+    match (self.freshness(now, shared), window) {
+      (Freshness::Fresh(_), _)                                 => Usability::Fresh,
+      (Freshness::Stale(secs), Some (window)) if secs < window => Usability::UsableStale,
+      _                                                        => Usability::Unusable
+    }
+  }
+
+  // RFC 9111 section 4.2.1
+  fn freshness_lifetime(&self, shared: bool) -> u64 {
+    let date = self.date.unwrap_or(self.response_time);
+    match (shared, self.s_maxage, self.max_age, self.expires) {
+      (true, Some (s_maxage), _, _) => s_maxage,
+      (_, _, Some (max_age), _)     => max_age,
+      (_, _, _, Some (expires))     => expires.secs.saturating_sub(date.secs),
+      _                             => 0
+    }
+  }
+
+  /// Provides the current age of the response at `now` in
+  /// seconds, per RFC 9111 section 4.2.3, i.e. the larger of
+  /// the apparent age from the Date header value and the Age
+  /// header value corrected for the response delay, plus the
+  /// time since receipt, rather than simply `now` less the
+  /// Date header value.
+  pub fn current_age(&self, now: &Datetime) -> u64 {
+    let date = self.date.unwrap_or(self.response_time);
+    let apparent_age = self.response_time.secs.saturating_sub(date.secs);
+    let response_delay = self.response_time.secs.saturating_sub(self.request_time.secs);
+    let corrected_age_value = self.age.unwrap_or(0).saturating_add(response_delay);
+    let corrected_initial_age = apparent_age.max(corrected_age_value);
+    let resident_time = now.secs.saturating_sub(self.response_time.secs);
+    corrected_initial_age.saturating_add(resident_time)
+  }
+}
+
+// Freshness
+
+/// Indicates either that a response is fresh, with the
+/// number of seconds for which it remains so, or that it
+/// is stale, with the number of seconds since it was last
+/// fresh.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Freshness {
+  Fresh(u64),
+  Stale(u64)
+}
+
+impl Freshness {
+
+  pub fn is_fresh(&self) -> bool {
+    matches!(self, Self::Fresh(_))
+  }
+}
+
+// Usability
+
+/// Indicates whether a stored response is fresh, stale but
+/// usable within a stale-while-revalidate or stale-if-error
+/// window, or stale and unusable without validation.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Usability {
+  Fresh,
+  UsableStale,
+  Unusable
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{StoredResponse, Freshness, Usability};
+  use crate::datetime::Datetime;
+  use crate::header::CacheControl;
+
+  fn at(secs: u64) -> Datetime {
+    Datetime::default().set(secs)
+  }
+
+  #[test]
+  fn freshness_max_age() {
+
+    let stored = StoredResponse::new(at(100), at(101))
+      .date(at(100))
+      .max_age(60);
+
+    assert_eq!(Freshness::Fresh(59), stored.freshness(&at(101), false));
+    assert_eq!(Freshness::Fresh( 1), stored.freshness(&at(159), false));
+    assert_eq!(Freshness::Stale( 0), stored.freshness(&at(160), false));
+    assert_eq!(Freshness::Stale(40), stored.freshness(&at(200), false));
+  }
+
+  #[test]
+  fn freshness_s_maxage() {
+
+    let stored = StoredResponse::new(at(100), at(100))
+      .max_age(60)
+      .s_maxage(30);
+
+    assert_eq!(Freshness::Fresh(60), stored.freshness(&at(100), false));
+    assert_eq!(Freshness::Fresh(30), stored.freshness(&at(100), true));
+  }
+
+  #[test]
+  fn freshness_expires() {
+
+    let stored = StoredResponse::new(at(100), at(100))
+      .date(at(100))
+      .expires(at(400));
+
+    assert_eq!(Freshness::Fresh(300), stored.freshness(&at(100), false));
+
+    let stored = stored.max_age(10);
+
+    assert_eq!(Freshness::Fresh( 10), stored.freshness(&at(100), false));
+
+    let stored = StoredResponse::new(at(100), at(100))
+      .date(at(100))
+      .expires(at(50));
+
+    assert_eq!(Freshness::Stale(  0), stored.freshness(&at(100), false));
+  }
+
+  #[test]
+  fn freshness_age() {
+
+    // upstream cache reports age of 20
+    let stored = StoredResponse::new(at(100), at(105))
+      .date(at(80))
+      .age(20)
+      .max_age(60);
+
+    // corrected initial age of max(105 - 80, 20 + 5)
+    assert_eq!(Freshness::Fresh(35), stored.freshness(&at(105), false));
+
+    // origin clock ahead of receipt
+    let stored = StoredResponse::new(at(100), at(100))
+      .date(at(130))
+      .max_age(60);
+
+    assert_eq!(Freshness::Fresh(60), stored.freshness(&at(100), false));
+  }
+
+  #[test]
+  fn freshness_current_age() {
+
+    // apparent age of 105 - 80 exceeds 10 + 5
+    let stored = StoredResponse::new(at(100), at(105))
+      .date(at(80))
+      .age(10);
+
+    assert_eq!(25, stored.current_age(&at(105)));
+    assert_eq!(45, stored.current_age(&at(125)));
+
+    // response delay of 5 added to age of 30 exceeds 105 - 80
+    let stored = stored.age(30);
+
+    assert_eq!(35, stored.current_age(&at(105)));
+    assert_eq!(55, stored.current_age(&at(125)));
+
+    // no Date or Age, with now before receipt
+    let stored = StoredResponse::new(at(100), at(105));
+
+    assert_eq!(5, stored.current_age(&at(105)));
+    assert_eq!(5, stored.current_age(&at(100)));
+  }
+
+  #[test]
+  fn freshness_usability() {
+
+    let stored = StoredResponse::new(at(100), at(100))
+      .max_age(60)
+      .stale_while_revalidate(30)
+      .stale_if_error(300);
+
+    assert_eq!(Usability::Fresh,       stored.usability(&at(159), false, false));
+    assert_eq!(Usability::UsableStale, stored.usability(&at(160), false, false));
+    assert_eq!(Usability::UsableStale, stored.usability(&at(189), false, false));
+    assert_eq!(Usability::Unusable,    stored.usability(&at(190), false, false));
+    assert_eq!(Usability::UsableStale, stored.usability(&at(190), false, true));
+    assert_eq!(Usability::UsableStale, stored.usability(&at(459), false, true));
+    assert_eq!(Usability::Unusable,    stored.usability(&at(460), false, true));
+
+    // no windows
+    let stored = StoredResponse::new(at(100), at(100)).max_age(60);
+
+    assert_eq!(Usability::Fresh,    stored.usability(&at(159), false, true));
+    assert_eq!(Usability::Unusable, stored.usability(&at(160), false, false));
+    assert_eq!(Usability::Unusable, stored.usability(&at(160), false, true));
+  }
+
+  #[test]
+  fn freshness_cache_control() {
+
+    let cc = CacheControl::parse("max-age=60, stale-while-revalidate=30").unwrap();
+    let stored = StoredResponse::new(at(100), at(100))
+      .s_maxage(10)
+      .cache_control(&cc);
+
+    assert_eq!(Some (60), stored.max_age);
+    assert_eq!(Some (10), stored.s_maxage);
+    assert_eq!(Some (30), stored.stale_while_revalidate);
+    assert_eq!(None,      stored.stale_if_error);
+  }
+
+  #[test]
+  fn freshness_none() {
+
+    let stored = StoredResponse::new(at(100), at(100));
+
+    assert_eq!(Freshness::Stale(0), stored.freshness(&at(100), false));
+    assert!(!stored.freshness(&at(100), false).is_fresh());
+  }
+}
@@ -0,0 +1,368 @@
+//! # Header
+//!
+//! Helpers for date-related HTTP header field values.
+
+use crate::datetime::{Datetime, ImfFixdate, MAX_SECS};
+use crate::parse::ParseError;
+
+use std::fmt::{self, Display, Formatter};
+use std::error::Error;
+
+/// Stores a Retry-After header value, in either the
+/// delay-seconds or HTTP-date form, with constructors for
+/// each from the current datetime and the datetime at
+/// which a rate-limit window resets. A reset which is not
+/// later than the current datetime results in a delay of
+/// zero seconds or a date equal to the current datetime.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, RetryAfter};
+///
+/// let now = Datetime::default();
+/// let reset = now.set(120);
+///
+/// assert_eq!("120", RetryAfter::delay(&now, &reset).to_string());
+/// assert_eq!("Thu, 01 Jan 1970 00:02:00 GMT", RetryAfter::date(&now, &reset).to_string());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum RetryAfter {
+  Delay(u64),
+  Date(Datetime)
+}
+
+impl RetryAfter {
+
+  pub fn delay(now: &Datetime, reset: &Datetime) -> Self {
+    Self::Delay(now.seconds_until(reset))
+  }
+
+  pub fn date(now: &Datetime, reset: &Datetime) -> Self {
+    match reset.secs > now.secs {
+      true  => Self::Date(*reset),
+      false => Self::Date(*now)
+    }
+  }
+
+  /// Parses a Retry-After header value, as delay-seconds
+  /// where it begins with a digit and otherwise as an
+  /// HTTP-date, as for `Datetime::parse`.
+  pub fn parse(s: &str) -> Result<Self, ParseError> {
+    match s.starts_with(|c: char| c.is_ascii_digit()) {
+      true  => Ok (Self::Delay(parse_delta_seconds(s)?)),
+      false => Ok (Self::Date(Datetime::parse(s)?))
+    }
+  }
+}
+
+impl Display for RetryAfter {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Delay(secs) => write!(f, "{secs}"),
+      Self::Date(dt)    => write!(f, "{}", ImfFixdate(dt))
+    }
+  }
+}
+
+// DeltaSeconds
+
+/// Stores a delta-seconds value, e.g. for the HSTS max-age,
+/// Alt-Svc ma or Access-Control-Max-Age header parameters,
+/// with the constructor `until` providing the number of
+/// seconds from the current datetime to a target, zero
+/// where the target is not later, so that the value stays
+/// consistent with HTTP-date expiries in the same response.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, DeltaSeconds};
+///
+/// let now = Datetime::default();
+/// let expires = now.set(3600);
+///
+/// assert_eq!("max-age=3600", format!("max-age={}", DeltaSeconds::until(&now, &expires)));
+/// assert_eq!("0", DeltaSeconds::until(&expires, &now).to_string());
+/// ```
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub struct DeltaSeconds(pub u64);
+
+impl DeltaSeconds {
+
+  pub fn until(now: &Datetime, target: &Datetime) -> Self {
+    Self(now.seconds_until(target))
+  }
+}
+
+impl Display for DeltaSeconds {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+// CacheControl
+
+/// Stores the delta-seconds directives of a Cache-Control
+/// header value relevant to freshness, i.e. max-age,
+/// s-maxage, stale-while-revalidate and stale-if-error,
+/// parsed case-insensitively with other directives
+/// ignored, with the expiry datetime provided
+/// from a base datetime, usually the response Date, by
+/// `expiry`, with s-maxage applied only by a `shared` cache.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, CacheControl};
+///
+/// let cc = CacheControl::parse("public, max-age=60, s-maxage=\"30\"").unwrap();
+///
+/// let date = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+///
+/// assert_eq!("Sun, 06 Nov 1994 08:50:37 GMT", cc.expiry(&date, false).unwrap().for_header());
+/// assert_eq!("Sun, 06 Nov 1994 08:50:07 GMT", cc.expiry(&date, true).unwrap().for_header());
+/// ```
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub struct CacheControl {
+  pub max_age: Option<u64>,
+  pub s_maxage: Option<u64>,
+  pub stale_while_revalidate: Option<u64>,
+  pub stale_if_error: Option<u64>
+}
+
+impl CacheControl {
+
+  pub fn parse(s: &str) -> Result<Self, ParseError> {
+    let mut cc = Self::default();
+    for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+      let (name, value) = match directive.split_once('=') {
+        Some ((name, value)) => (name.trim(), Some (value.trim())),
+        None                 => (directive, None)
+      };
+      let field = match name {
+        _ if name.eq_ignore_ascii_case("max-age")                => &mut cc.max_age,
+        _ if name.eq_ignore_ascii_case("s-maxage")               => &mut cc.s_maxage,
+        _ if name.eq_ignore_ascii_case("stale-while-revalidate") => &mut cc.stale_while_revalidate,
+        _ if name.eq_ignore_ascii_case("stale-if-error")         => &mut cc.stale_if_error,
+        _                                                        => continue
+      };
+      *field = Some (parse_delta_seconds(value.ok_or(ParseError::Format)?)?);
+    }
+    Ok (cc)
+  }
+
+  pub fn expiry(&self, base: &Datetime, shared: bool) -> Option<Datetime> {
+    let lifetime = match (shared, self.s_maxage, self.max_age) {
+      (true, Some (s_maxage), _) => s_maxage,
+      (_, _, Some (max_age))     => max_age,
+      _                          => return None
+    };
+    let secs = base.secs.saturating_add(lifetime).min(MAX_SECS);
+    Some (base.set(secs.max(base.secs)))
+  }
+}
+
+// NoCache
+
+/// Stores a consistent set of header values disabling
+/// caching of a response, i.e. the Date, an Expires at the
+/// epoch and so in the past, for HTTP/1.0 caches, and the
+/// Cache-Control value `CACHE_CONTROL`, with `headers`
+/// providing the three names and values ready to set.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, NoCache};
+///
+/// let nc = NoCache::new(&Datetime::from(784_111_777));
+///
+/// assert_eq!([
+///   ("Date",          String::from("Sun, 06 Nov 1994 08:49:37 GMT")),
+///   ("Expires",       String::from("Thu, 01 Jan 1970 00:00:00 GMT")),
+///   ("Cache-Control", String::from("no-store, no-cache, max-age=0, must-revalidate"))
+/// ], nc.headers());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct NoCache {
+  pub date: Datetime,
+  pub expires: Datetime
+}
+
+impl NoCache {
+
+  pub const CACHE_CONTROL: &'static str = "no-store, no-cache, max-age=0, must-revalidate";
+
+  pub fn new(now: &Datetime) -> Self {
+    Self { date: *now, expires: Datetime::default() }
+  }
+
+  /// Provides the values for the current datetime, as for
+  /// `Datetime::new`.
+  pub fn now() -> Result<Self, Box<dyn Error>> {
+    Ok (Self::new(&Datetime::new()?))
+  }
+
+  pub fn headers(&self) -> [(&'static str, String); 3] {
+    [
+      ("Date",          self.date.for_header()),
+      ("Expires",       self.expires.for_header()),
+      ("Cache-Control", Self::CACHE_CONTROL.to_string())
+    ]
+  }
+}
+
+/// Parses a delta-seconds value, as used by the Age and
+/// Retry-After headers and the max-age directive, i.e. one
+/// or more digits, optionally quoted, with any sign,
+/// decimal point or other character giving
+/// `ParseError::Format`. A value too large to represent is
+/// clamped to `u64::MAX`, per RFC 9111 section 1.2.2,
+/// rather than rejected.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{parse_delta_seconds, ParseError};
+///
+/// assert_eq!(Ok (3600), parse_delta_seconds("3600"));
+/// assert_eq!(Ok (u64::MAX), parse_delta_seconds("99999999999999999999"));
+/// assert_eq!(Err(ParseError::Format), parse_delta_seconds("-1"));
+/// ```
+pub fn parse_delta_seconds(value: &str) -> Result<u64, ParseError> {
+  let value = value
+    .strip_prefix('"')
+    .and_then(|v| v.strip_suffix('"'))
+    .unwrap_or(value);
+  if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+    return Err(ParseError::Format)
+  }
+  Ok (value.parse().unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{RetryAfter, DeltaSeconds, CacheControl, NoCache, parse_delta_seconds};
+  use crate::datetime::Datetime;
+  use crate::parse::ParseError;
+
+  #[test]
+  fn retry_after_delay() {
+
+    let now = Datetime::default().set(60);
+
+    assert_eq!(RetryAfter::Delay(60), RetryAfter::delay(&now, &now.set(120)));
+    assert_eq!(RetryAfter::Delay( 0), RetryAfter::delay(&now, &now));
+    assert_eq!(RetryAfter::Delay( 0), RetryAfter::delay(&now, &Datetime::default()));
+    assert_eq!("60", RetryAfter::delay(&now, &now.set(120)).to_string());
+  }
+
+  #[test]
+  fn retry_after_date() {
+
+    let now = Datetime::default().set(60);
+
+    assert_eq!(RetryAfter::Date(now.set(120)), RetryAfter::date(&now, &now.set(120)));
+    assert_eq!(RetryAfter::Date(now),          RetryAfter::date(&now, &Datetime::default()));
+    assert_eq!("Thu, 01 Jan 1970 00:02:00 GMT", RetryAfter::date(&now, &now.set(120)).to_string());
+  }
+
+  #[test]
+  fn retry_after_parse() {
+
+    assert_eq!(Ok (RetryAfter::Delay(120)),                         RetryAfter::parse("120"));
+    assert_eq!(Ok (RetryAfter::Date(Datetime::default().set(120))), RetryAfter::parse("Thu, 01 Jan 1970 00:02:00 GMT"));
+
+    assert_eq!(Err(ParseError::Format), RetryAfter::parse(""));
+    assert_eq!(Err(ParseError::Format), RetryAfter::parse("-1"));
+    assert_eq!(Err(ParseError::Format), RetryAfter::parse("120s"));
+    assert_eq!(Ok (RetryAfter::Delay(u64::MAX)), RetryAfter::parse("99999999999999999999"));
+  }
+
+  #[test]
+  fn delta_seconds_until() {
+
+    let now = Datetime::default().set(60);
+
+    assert_eq!(DeltaSeconds(60), DeltaSeconds::until(&now, &now.set(120)));
+    assert_eq!(DeltaSeconds( 0), DeltaSeconds::until(&now, &now));
+    assert_eq!(DeltaSeconds( 0), DeltaSeconds::until(&now, &Datetime::default()));
+    assert_eq!("31536000", DeltaSeconds::until(&now, &now.set(60 + 31_536_000)).to_string());
+  }
+
+  #[test]
+  fn no_cache_headers() {
+
+    let now = Datetime::default().set(784_111_777);
+    let nc = NoCache::new(&now);
+
+    assert_eq!(NoCache { date: now, expires: Datetime::default() }, nc);
+    assert_eq!(Ok (Some (0)), CacheControl::parse(NoCache::CACHE_CONTROL).map(|cc| cc.max_age));
+
+    let [date, expires, cache_control] = nc.headers();
+    assert_eq!(("Date",          now.for_header()),                   date);
+    assert_eq!(("Expires",       Datetime::default().for_header()),   expires);
+    assert_eq!(("Cache-Control", NoCache::CACHE_CONTROL.to_string()), cache_control);
+
+    let nc = NoCache::now().unwrap();
+    assert!(nc.expires.secs < nc.date.secs);
+  }
+
+  #[test]
+  fn header_parse_delta_seconds() {
+
+    assert_eq!(Ok (0),                 parse_delta_seconds("0"));
+    assert_eq!(Ok (60),                parse_delta_seconds("060"));
+    assert_eq!(Ok (60),                parse_delta_seconds("\"60\""));
+    assert_eq!(Ok (u64::MAX),          parse_delta_seconds("18446744073709551615"));
+    assert_eq!(Ok (u64::MAX),          parse_delta_seconds("18446744073709551616"));
+    assert_eq!(Ok (u64::MAX),          parse_delta_seconds(&"9".repeat(100)));
+
+    for invalid in ["", "\"\"", "+1", "-1", "1.5", "1e3", " 1", "1 ", "\"60", "0x10"] {
+      assert_eq!(Err(ParseError::Format), parse_delta_seconds(invalid));
+    }
+  }
+
+  #[test]
+  fn cache_control_parse() {
+
+    let cc = |max_age, s_maxage| CacheControl { max_age, s_maxage, ..CacheControl::default() };
+
+    assert_eq!(cc(Some (60), None    ), CacheControl::parse("max-age=60").unwrap());
+    assert_eq!(cc(Some (60), Some (0)), CacheControl::parse("Max-Age=60, S-MAXAGE=0").unwrap());
+    assert_eq!(cc(Some (60), None    ), CacheControl::parse("no-transform, max-age = \"60\" ,").unwrap());
+    assert_eq!(cc(None,      None    ), CacheControl::parse("no-store, private=\"x, y\"").unwrap());
+    assert_eq!(CacheControl::default(), CacheControl::parse("").unwrap());
+
+    let parsed = CacheControl::parse("max-age=60, stale-while-revalidate=30, Stale-If-Error=600").unwrap();
+    assert_eq!(Some ( 30), parsed.stale_while_revalidate);
+    assert_eq!(Some (600), parsed.stale_if_error);
+
+    assert_eq!(Err(ParseError::Format), CacheControl::parse("max-age"));
+    assert_eq!(Err(ParseError::Format), CacheControl::parse("max-age="));
+    assert_eq!(Err(ParseError::Format), CacheControl::parse("max-age=-1"));
+    assert_eq!(Err(ParseError::Format), CacheControl::parse("max-age=1.5"));
+    assert_eq!(Some (u64::MAX),         CacheControl::parse("max-age=99999999999999999999").unwrap().max_age);
+  }
+
+  #[test]
+  fn cache_control_expiry() {
+
+    let date = Datetime::default().set(60);
+    let cc = CacheControl { max_age: Some (60), s_maxage: Some (30), ..CacheControl::default() };
+
+    assert_eq!(Some (date.set(120)), cc.expiry(&date, false));
+    assert_eq!(Some (date.set( 90)), cc.expiry(&date, true));
+    assert_eq!(Some (date.set(120)), CacheControl { s_maxage: None, ..cc }.expiry(&date, true));
+    assert_eq!(None, CacheControl { max_age: None, ..cc }.expiry(&date, false));
+    assert_eq!(None, CacheControl::default().expiry(&date, true));
+
+    // capped at the end of year 9999
+    let cc = CacheControl { max_age: Some (u64::MAX), ..CacheControl::default() };
+    assert_eq!("Fri, 31 Dec 9999 23:59:59 GMT", cc.expiry(&date, false).unwrap().for_header());
+  }
+}
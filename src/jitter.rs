@@ -0,0 +1,127 @@
+//! # Jitter
+//!
+//! Perturbation of freshness lifetimes, avoiding the
+//! synchronized expiry of responses cached across replicas.
+
+use crate::datetime::{Datetime, MAX_SECS};
+
+use std::time::SystemTime;
+use std::error::Error;
+
+/// Perturbs a freshness lifetime in seconds by up to
+/// `percent` either way before computing the expiry
+/// datetime, with pseudo-random values drawn from a
+/// generator seeded either from the system clock (`new`)
+/// or deterministically (`seeded`). Percentages above 100
+/// are treated as 100.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, Jitter};
+///
+/// let mut jitter = Jitter::seeded(10, 42);
+///
+/// let ttl = jitter.ttl(3600);
+///
+/// assert!((3240..=3960).contains(&ttl));
+///
+/// let expires = jitter.expires(&Datetime::default(), 3600);
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Jitter {
+  percent: u64,
+    state: u64
+}
+
+impl Jitter {
+
+  pub fn new(percent: u8) -> Result<Self, Box<dyn Error>> {
+    let seed = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)?
+      .as_nanos() as u64;
+    Ok (Self::seeded(percent, seed))
+  }
+
+  pub fn seeded(percent: u8, seed: u64) -> Self {
+    let percent = percent.min(100) as u64;
+    Self { percent, state: seed }
+  }
+
+  pub fn ttl(&mut self, ttl: u64) -> u64 {
+    let span = ttl as u128 * self.percent as u128 / 100;
+    let offset = self.next() as u128 % (span * 2 + 1);
+    (ttl as u128 + offset - span).min(u64::MAX as u128) as u64
+  }
+
+  // capped at the latest datetime supported, as for
+  // `CacheControl::expiry`
+  pub fn expires(&mut self, base: &Datetime, ttl: u64) -> Datetime {
+    let ttl = self.ttl(ttl);
+    base.set(base.secs.saturating_add(ttl).min(MAX_SECS))
+  }
+
+  // SplitMix64
+  fn next(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::Jitter;
+  use crate::datetime::{Datetime, MAX_SECS};
+
+  #[test]
+  fn jitter_ttl() {
+
+    let mut jitter = Jitter::seeded(10, 0);
+    let ttls: Vec<u64> = (0..1000).map(|_| jitter.ttl(1000)).collect();
+
+    assert!(ttls.iter().all(|ttl| (900..=1100).contains(ttl)));
+    assert!(ttls.iter().any(|ttl| *ttl <  1000));
+    assert!(ttls.iter().any(|ttl| *ttl >  1000));
+  }
+
+  #[test]
+  fn jitter_seeded() {
+
+    let mut a = Jitter::seeded(25, 7);
+    let mut b = Jitter::seeded(25, 7);
+
+    for _ in 0..100 {
+      assert_eq!(a.ttl(60), b.ttl(60));
+    }
+  }
+
+  #[test]
+  fn jitter_bounds() {
+
+    let mut jitter = Jitter::seeded(0, 1);
+    assert_eq!(60, jitter.ttl(60));
+
+    let mut jitter = Jitter::seeded(255, 1);
+    assert!(jitter.ttl(60) <= 120);
+    assert_eq!(0, jitter.ttl(0));
+  }
+
+  #[test]
+  fn jitter_expires() {
+
+    let mut jitter = Jitter::seeded(10, 3);
+    let base = Datetime::from(1000);
+    let expires = jitter.expires(&base, 100);
+
+    assert!((1090..=1110).contains(&expires.secs));
+    assert_eq!(Datetime::from(expires.secs), expires);
+
+    // capped, e.g. for a max-age parsed as u64::MAX
+    let mut jitter = Jitter::seeded(10, 3);
+    assert_eq!(MAX_SECS, jitter.expires(&base, u64::MAX).secs);
+  }
+}
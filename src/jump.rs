@@ -0,0 +1,165 @@
+//! # Jump
+//!
+//! Detection of jumps of the clock, e.g. NTP steps, between
+//! successive reads of the current time.
+
+use crate::datetime::Datetime;
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+type Hook = Arc<dyn Fn(&ClockJump) + Send + Sync>;
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static WATCH: Mutex<Option<Watch>> = Mutex::new(None);
+
+/// Describes a jump of the clock, with the datetime
+/// expected from the previous read and the time elapsed
+/// since on the monotonic clock, and the datetime read.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ClockJump {
+  pub expected: Datetime,
+  pub actual: Datetime
+}
+
+impl ClockJump {
+
+  /// Provides the size of the jump in seconds, positive
+  /// where forward.
+  pub fn secs(&self) -> i64 {
+    self.actual.secs as i64 - self.expected.secs as i64
+  }
+}
+
+/// Registers `hook` to be called on any read of the current
+/// time, i.e. by `Datetime::raw`, and so by `new`, `now`
+/// and `DateService`, differing from that expected from the
+/// previous read by more than `threshold`, forward or
+/// backward, e.g. for logging NTP steps and invalidating
+/// caches assuming a steady clock. The expected time is
+/// measured on the monotonic clock in whole seconds, so
+/// thresholds below a second are treated as one second.
+/// Replaces any hook previously registered.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{set_jump_hook, clear_jump_hook};
+/// use std::time::Duration;
+///
+/// set_jump_hook(Duration::from_secs(5), |jump| {
+///   eprintln!("clock jumped {}s to {}", jump.secs(), jump.actual.for_header());
+/// });
+///
+/// clear_jump_hook();
+/// ```
+pub fn set_jump_hook(threshold: Duration, hook: impl Fn(&ClockJump) + Send + Sync + 'static) {
+  let mut watch = WATCH.lock().unwrap_or_else(|e| e.into_inner());
+  let threshold = threshold.as_secs().max(1);
+  *watch = Some (Watch { threshold, last: None, hook: Arc::new(hook) });
+  ACTIVE.store(true, Ordering::Release);
+}
+
+pub fn clear_jump_hook() {
+  let mut watch = WATCH.lock().unwrap_or_else(|e| e.into_inner());
+  *watch = None;
+  ACTIVE.store(false, Ordering::Release);
+}
+
+// called on each read of the clock, with the hook called
+// once the lock is released, in case it reads the clock
+pub(crate) fn check(raw: u64) {
+  if !ACTIVE.load(Ordering::Acquire) { return };
+  let called = {
+    let mut watch = WATCH.lock().unwrap_or_else(|e| e.into_inner());
+    let Some (watch) = watch.as_mut() else { return };
+    watch.observe(Instant::now(), raw)
+      .map(|jump| (jump, watch.hook.clone()))
+  };
+  if let Some ((jump, hook)) = called {
+    hook(&jump);
+  }
+}
+
+// Watch
+
+struct Watch {
+  threshold: u64,
+  // the monotonic time and seconds since the epoch of the
+  // previous read
+  last: Option<(Instant, u64)>,
+  hook: Hook
+}
+
+impl Watch {
+
+  fn observe(&mut self, at: Instant, raw: u64) -> Option<ClockJump> {
+    let last = self.last.replace((at, raw));
+    let (last_at, last_raw) = last?;
+    let expected = last_raw + at.saturating_duration_since(last_at).as_secs();
+    match raw.abs_diff(expected) > self.threshold {
+      true  => Some (ClockJump { expected: Datetime::from(expected), actual: Datetime::from(raw) }),
+      false => None
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{Watch, ClockJump, set_jump_hook, clear_jump_hook, check};
+  use crate::datetime::Datetime;
+
+  use std::sync::Arc;
+  use std::sync::atomic::{AtomicI64, Ordering};
+  use std::time::{Duration, Instant};
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn jump_watch_observe() {
+
+    let mut watch = Watch { threshold: 5, last: None, hook: Arc::new(|_| ()) };
+    let at = Instant::now();
+    let secs = |diff: i64| NOV_06_1994_08_49_37.saturating_add_signed(diff);
+
+    assert_eq!(None, watch.observe(at, secs(0)));
+
+    // steady, including across a gap in reads
+    assert_eq!(None, watch.observe(at + Duration::from_secs(  1), secs(  1)));
+    assert_eq!(None, watch.observe(at + Duration::from_secs(600), secs(600)));
+
+    // within the threshold
+    assert_eq!(None, watch.observe(at + Duration::from_secs(600), secs(605)));
+    assert_eq!(None, watch.observe(at + Duration::from_secs(600), secs(600)));
+
+    // forward and backward
+    let jump = watch.observe(at + Duration::from_secs(601), secs(3601)).unwrap();
+    assert_eq!(ClockJump { expected: Datetime::from(secs(601)), actual: Datetime::from(secs(3601)) }, jump);
+    assert_eq!(3000, jump.secs());
+
+    let jump = watch.observe(at + Duration::from_secs(602), secs(-10)).unwrap();
+    assert_eq!(-3612, jump.secs());
+  }
+
+  #[test]
+  fn jump_hook() {
+
+    let seen = Arc::new(AtomicI64::new(0));
+    let seen_by_hook = seen.clone();
+    set_jump_hook(Duration::from_secs(5), move |jump| seen_by_hook.store(jump.secs(), Ordering::SeqCst));
+
+    let raw = Datetime::raw().unwrap();
+    check(raw + 3600);
+    check(raw + 3600);
+
+    // by this or any concurrent read of the clock
+    assert_ne!(0, seen.load(Ordering::SeqCst));
+
+    clear_jump_hook();
+    seen.store(0, Ordering::SeqCst);
+    check(raw);
+    assert_eq!(0, seen.load(Ordering::SeqCst));
+  }
+}
@@ -8,9 +8,104 @@
 //! Calculates with a focus on clarity from `SystemTime`,
 //! with no external dependencies, and provides for
 //! updates to previously generated datetimes for speed.
+//!
+//! Parses timestamps in each of the formats recipients
+//! are required to accept, with the range of years
+//! accepted configurable.
+//!
+//! Displays and parses datetimes with a fixed offset from
+//! GMT, for logs and email, via `OffsetDatetime`, with
+//! an optional `posix-tz` feature for local time per a
+//! POSIX TZ specification, via `PosixTz`, and a `tzif`
+//! feature for named timezones loaded from compiled
+//! zoneinfo files at runtime, via `Zone`.
+//!
+//! Shares a cached datetime and timestamp across threads
+//! via `DateService`, with optional `axum`, `actix-web` and
+//! `warp` features for stamping responses with the Date
+//! header.
+//!
+//! Evaluates the freshness of stored responses from the
+//! date-related header values and request and response
+//! times, per RFC 9111, and date-based conditional
+//! request headers, per RFC 9110, with an optional `http`
+//! feature for extracting those header values from an
+//! `http::HeaderMap` in one pass, via `CacheMetadata`, or
+//! singly, via `HeaderMapExt`.
 
 mod datetime;
 mod date;
 mod time;
+mod parse;
+mod stream;
+mod scan;
+mod offset;
+mod service;
+mod freshness;
+mod conditional;
+mod header;
+mod skew;
+mod queue;
+mod jitter;
+mod schedule;
+mod jump;
+mod timestamp;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "clock-override")]
+mod clock;
+#[cfg(feature = "sntp")]
+mod sntp;
+#[cfg(feature = "posix-tz")]
+mod tz;
+#[cfg(feature = "tzif")]
+mod zoneinfo;
+#[cfg(feature = "http")]
+mod metadata;
+#[cfg(feature = "locale")]
+mod locale;
+#[cfg(feature = "libc")]
+mod tm;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "warp")]
+pub mod warp;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 
-pub use datetime::Datetime;
+pub use datetime::{Datetime, ComponentError, CalendarDiff, DayOverflow, IMF_FIXDATE_LEN};
+pub use date::{Date, Weekday, Month, Year, days_in_year};
+pub use time::Time;
+pub use parse::{ParseOptions, ParseError, ParseErrorDetail, ParseField};
+pub use stream::{StreamParser, Feed};
+pub use scan::{extract_dates, ExtractDates};
+pub use offset::OffsetDatetime;
+pub use service::{DateService, Subscription, ExpiresTable};
+pub use freshness::{StoredResponse, Freshness, Usability};
+pub use conditional::{if_range_date, weak_etag, Preconditions, PreconditionOutcome};
+pub use header::{RetryAfter, DeltaSeconds, CacheControl, NoCache, parse_delta_seconds};
+pub use skew::{SkewEstimator, validate_peer_date};
+pub use queue::{ExpiryQueue, PopExpired};
+pub use jitter::Jitter;
+pub use schedule::{Schedule, Recurrence};
+pub use jump::{ClockJump, set_jump_hook, clear_jump_hook};
+pub use timestamp::Timestamp;
+#[cfg(feature = "clock-override")]
+pub use clock::{Clock, SystemClock, FreezeGuard, set_global_clock, reset_global_clock};
+#[cfg(feature = "sntp")]
+pub use sntp::SntpClock;
+#[cfg(feature = "posix-tz")]
+pub use tz::PosixTz;
+#[cfg(feature = "tzif")]
+pub use zoneinfo::Zone;
+#[cfg(feature = "http")]
+pub use metadata::{CacheMetadata, HeaderMapExt};
+#[cfg(feature = "locale")]
+pub use locale::Names;
@@ -0,0 +1,90 @@
+//! # Locale
+//!
+//! Alternative month and weekday names for operator-facing
+//! output, available with the `locale` feature. The names
+//! are used only by the log-oriented formatters, never for
+//! the HTTP Date header or email, whose formats require the
+//! English names.
+
+use crate::date::{Weekday, Month};
+
+/// Holds a table of month names, from January, and weekday
+/// names, from Monday, e.g. loaded from configuration, for
+/// the `for_log_in` and `for_syslog_in` methods of
+/// `OffsetDatetime`, with `month` and `weekday` providing
+/// the name for any other output.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, OffsetDatetime, Names, Weekday};
+///
+/// let de = Names {
+///   months: ["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+///   weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"]
+/// };
+///
+/// let odt = OffsetDatetime::new(Datetime::from(30_000_000), 3600);
+///
+/// assert_eq!("Dez 14 06:20:00", odt.for_syslog_in(&de));
+/// assert_eq!("14/Dez/1970:06:20:00 +0100", odt.for_log_in(&de));
+/// assert_eq!("Do", de.weekday(Weekday::Thu));
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Names<'a> {
+  pub months: [&'a str; 12],
+  pub weekdays: [&'a str; 7]
+}
+
+impl Names<'static> {
+
+  /// The three-letter English names, as used by default.
+  pub const ENGLISH: Self = Self {
+    months: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+    weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+  };
+}
+
+impl<'a> Names<'a> {
+
+  pub fn month(&self, m: Month) -> &'a str {
+    let i = Month::iter().position(|other| other == m).unwrap_or_default();
+    self.months[i]
+  }
+
+  pub fn weekday(&self, wd: Weekday) -> &'a str {
+    let i = Weekday::iter().position(|other| other == wd).unwrap_or_default();
+    self.weekdays[i]
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::Names;
+  use crate::date::{Weekday, Month};
+
+  #[test]
+  fn names_english() {
+
+    for m in Month::iter() {
+      assert_eq!(m.abbrev(), Names::ENGLISH.month(m));
+    }
+    for wd in Weekday::iter() {
+      assert_eq!(wd.abbrev(), Names::ENGLISH.weekday(wd));
+    }
+  }
+
+  #[test]
+  fn names_month_weekday() {
+
+    let owned: Vec<String> = (1..=12).map(|n| format!("M{n}")).collect();
+    let months: [&str; 12] = std::array::from_fn(|i| owned[i].as_str());
+    let names = Names { months, weekdays: ["1", "2", "3", "4", "5", "6", "7"] };
+
+    assert_eq!("M1",  names.month(Month::Jan));
+    assert_eq!("M12", names.month(Month::Dec));
+    assert_eq!("1",   names.weekday(Weekday::Mon));
+    assert_eq!("7",   names.weekday(Weekday::Sun));
+  }
+}
@@ -0,0 +1,245 @@
+//! # Metadata
+//!
+//! Extraction of the date-related headers of a response
+//! for caching, and typed access to each, available with
+//! the `http` feature.
+
+use crate::datetime::Datetime;
+use crate::header::{RetryAfter, parse_delta_seconds};
+use crate::parse::ParseError;
+
+use http::header::{HeaderMap, HeaderName, HeaderValue, DATE, EXPIRES, LAST_MODIFIED, AGE, RETRY_AFTER};
+
+/// Stores the parsed Date, Expires, Last-Modified, Age and
+/// Retry-After header values of a response, as read from
+/// an `http::HeaderMap` in one pass by `from_headers`. Each
+/// field is `None` where the header is absent or invalid,
+/// with each invalid header recorded in `errors` with the
+/// reason, so that one bad value does not prevent the use
+/// of the others. Only the first of any repeated header is
+/// read.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{CacheMetadata, ParseError};
+/// use http::{HeaderMap, HeaderValue};
+/// use http::header::{DATE, EXPIRES, AGE};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(DATE, HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// headers.insert(EXPIRES, HeaderValue::from_static("0"));
+/// headers.insert(AGE, HeaderValue::from_static("30"));
+///
+/// let meta = CacheMetadata::from_headers(&headers);
+///
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", meta.date.unwrap().for_header());
+/// assert_eq!(Some (30), meta.age);
+/// assert_eq!(None, meta.expires);
+/// assert_eq!(vec![(EXPIRES, ParseError::Format)], meta.errors);
+/// ```
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct CacheMetadata {
+  pub date: Option<Datetime>,
+  pub expires: Option<Datetime>,
+  pub last_modified: Option<Datetime>,
+  pub age: Option<u64>,
+  pub retry_after: Option<RetryAfter>,
+  pub errors: Vec<(HeaderName, ParseError)>
+}
+
+impl CacheMetadata {
+
+  pub fn from_headers(headers: &HeaderMap) -> Self {
+    let mut meta = Self::default();
+    meta.date          = meta.read(headers, DATE,          Datetime::parse);
+    meta.expires       = meta.read(headers, EXPIRES,       Datetime::parse);
+    meta.last_modified = meta.read(headers, LAST_MODIFIED, Datetime::parse);
+    meta.age           = meta.read(headers, AGE,           parse_delta_seconds);
+    meta.retry_after   = meta.read(headers, RETRY_AFTER,   RetryAfter::parse);
+    meta
+  }
+
+  // the value where present and valid, with any error
+  // recorded against the header
+  fn read<T>(&mut self, headers: &HeaderMap, name: HeaderName, parse: impl Fn(&str) -> Result<T, ParseError>) -> Option<T> {
+    match get(headers, &name, parse)? {
+      Ok (value) => Some (value),
+      Err(e)     => { self.errors.push((name, e)); None }
+    }
+  }
+}
+
+// HeaderMapExt
+
+/// Provides typed access to the date-related headers of an
+/// `http::HeaderMap`, each getter giving `Ok (None)` where
+/// the header is absent and the `ParseError` where invalid,
+/// reading only the first of any repeated header, as for
+/// `CacheMetadata`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, HeaderMapExt};
+/// use http::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.set_date(&Datetime::from(784_111_777));
+///
+/// assert_eq!(Ok (Some (Datetime::from(784_111_777))), headers.date());
+/// assert_eq!(Ok (None), headers.last_modified());
+/// ```
+pub trait HeaderMapExt {
+
+  fn date(&self) -> Result<Option<Datetime>, ParseError>;
+
+  /// Sets the Date header, replacing any present.
+  fn set_date(&mut self, dt: &Datetime);
+
+  fn last_modified(&self) -> Result<Option<Datetime>, ParseError>;
+
+  fn expires(&self) -> Result<Option<Datetime>, ParseError>;
+
+  fn retry_after(&self) -> Result<Option<RetryAfter>, ParseError>;
+}
+
+impl HeaderMapExt for HeaderMap {
+
+  fn date(&self) -> Result<Option<Datetime>, ParseError> {
+    get(self, &DATE, Datetime::parse).transpose()
+  }
+
+  fn set_date(&mut self, dt: &Datetime) {
+    let value = HeaderValue::from_bytes(&dt.for_header_bytes())
+      .expect("IMF-fixdate timestamp is visible ASCII");
+    self.insert(DATE, value);
+  }
+
+  fn last_modified(&self) -> Result<Option<Datetime>, ParseError> {
+    get(self, &LAST_MODIFIED, Datetime::parse).transpose()
+  }
+
+  fn expires(&self) -> Result<Option<Datetime>, ParseError> {
+    get(self, &EXPIRES, Datetime::parse).transpose()
+  }
+
+  fn retry_after(&self) -> Result<Option<RetryAfter>, ParseError> {
+    get(self, &RETRY_AFTER, RetryAfter::parse).transpose()
+  }
+}
+
+// the first value of the header, trimmed and parsed, or
+// None where absent
+fn get<T>(headers: &HeaderMap, name: &HeaderName, parse: impl Fn(&str) -> Result<T, ParseError>) -> Option<Result<T, ParseError>> {
+  let value = headers.get(name)?;
+  let parsed = value.to_str()
+    .map_err(|_| ParseError::Format)
+    .and_then(|s| parse(s.trim()));
+  Some (parsed)
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{CacheMetadata, HeaderMapExt};
+  use crate::datetime::Datetime;
+  use crate::header::RetryAfter;
+  use crate::parse::ParseError;
+
+  use http::{HeaderMap, HeaderValue};
+  use http::header::{DATE, EXPIRES, LAST_MODIFIED, AGE, RETRY_AFTER, CACHE_CONTROL};
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  fn headers(pairs: &[(http::HeaderName, &'static [u8])]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+      headers.append(name, HeaderValue::from_bytes(value).unwrap());
+    }
+    headers
+  }
+
+  #[test]
+  fn cache_metadata_from_headers() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    let meta = CacheMetadata::from_headers(&headers(&[
+      (DATE,          b"Sun, 06 Nov 1994 08:49:37 GMT"),
+      (EXPIRES,       b"Sunday, 06-Nov-94 09:49:37 GMT"),
+      (LAST_MODIFIED, b"Sun Nov  6 07:49:37 1994"),
+      (AGE,           b" 30 "),
+      (RETRY_AFTER,   b"120"),
+      (CACHE_CONTROL, b"max-age=60")
+    ]));
+
+    assert_eq!(CacheMetadata {
+      date:          Some (dt),
+      expires:       Some (dt.set(NOV_06_1994_08_49_37 + 3600)),
+      last_modified: Some (dt.set(NOV_06_1994_08_49_37 - 3600)),
+      age:           Some (30),
+      retry_after:   Some (RetryAfter::Delay(120)),
+      errors:        vec![]
+    }, meta);
+
+    // absent
+    assert_eq!(CacheMetadata::default(), CacheMetadata::from_headers(&HeaderMap::new()));
+
+    // the first of any repeated
+    let meta = CacheMetadata::from_headers(&headers(&[
+      (RETRY_AFTER, b"Sun, 06 Nov 1994 08:49:37 GMT"),
+      (RETRY_AFTER, b"120")
+    ]));
+    assert_eq!(Some (RetryAfter::Date(dt)), meta.retry_after);
+  }
+
+  #[test]
+  fn cache_metadata_errors() {
+
+    let meta = CacheMetadata::from_headers(&headers(&[
+      (DATE,          b"Sun, 06 Nov 1994 08:49:37 GMT"),
+      (EXPIRES,       b"0"),
+      (LAST_MODIFIED, b"Mon, 06 Nov 1994 08:49:37 GMT"),
+      (AGE,           b"-1"),
+      (RETRY_AFTER,   b"Sun, 06 Nov 1994 08:49:37 \xff")
+    ]));
+
+    assert_eq!(Some (Datetime::from(NOV_06_1994_08_49_37)), meta.date);
+    assert_eq!((None, None, None, None), (meta.expires, meta.last_modified, meta.age, meta.retry_after));
+    assert_eq!(vec![
+      (EXPIRES,       ParseError::Format),
+      (LAST_MODIFIED, ParseError::Weekday),
+      (AGE,           ParseError::Format),
+      (RETRY_AFTER,   ParseError::Format)
+    ], meta.errors);
+  }
+
+  #[test]
+  fn header_map_ext() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    let mut map = headers(&[
+      (DATE,          b"Sun, 06 Nov 1994 07:49:37 GMT"),
+      (DATE,          b"Sun, 06 Nov 1994 06:49:37 GMT"),
+      (LAST_MODIFIED, b" Sun Nov  6 08:49:37 1994 "),
+      (EXPIRES,       b"0"),
+      (RETRY_AFTER,   b"120")
+    ]);
+
+    assert_eq!(Ok (Some (dt.set(NOV_06_1994_08_49_37 - 3600))), map.date());
+    assert_eq!(Ok (Some (dt)),                                  map.last_modified());
+    assert_eq!(Err(ParseError::Format),                         map.expires());
+    assert_eq!(Ok (Some (RetryAfter::Delay(120))),              map.retry_after());
+
+    // replacing each repeated
+    map.set_date(&dt);
+    assert_eq!(Ok (Some (dt)), map.date());
+    assert_eq!(1, map.get_all(DATE).iter().count());
+    assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", map[DATE]);
+
+    let map = HeaderMap::new();
+    assert_eq!((Ok (None), Ok (None), Ok (None), Ok (None)), (map.date(), map.last_modified(), map.expires(), map.retry_after()));
+  }
+}
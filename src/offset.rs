@@ -0,0 +1,311 @@
+//! # Offset
+//!
+//! A datetime with a fixed offset from GMT, for display
+//! and parsing in formats other than that of the HTTP Date
+//! header, e.g. for logs and email.
+
+use crate::datetime::Datetime;
+use crate::parse::{self, ParseError};
+use crate::date::{Month, ImfFixdateDate};
+#[cfg(feature = "locale")]
+use crate::locale::Names;
+use crate::time::{ImfFixdateTime, M_AS_S, H_AS_S, H_AS_M};
+
+use std::fmt::{self, Display, Formatter};
+
+/// Pairs a datetime, which remains in GMT, with a fixed
+/// offset in seconds east of GMT, providing the local
+/// datetime (`local`) and output in the RFC 5322 format
+/// used by email (`for_email`), in the Common Log Format
+/// (`for_log`), in the legacy BSD syslog format of RFC 3164
+/// (`for_syslog`) and in git's raw format of seconds since
+/// the epoch and offset (`for_git`), the Common Log and git
+/// formats also parsed (`parse_log`, `parse_git`).
+/// The HTTP Date header timestamp is unaffected by the
+/// offset and remains available via the datetime.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, OffsetDatetime};
+///
+/// let odt = OffsetDatetime::new(Datetime::from(784_111_777), 3600);
+///
+/// assert_eq!("Sun, 06 Nov 1994 09:49:37 +0100", odt.for_email());
+/// assert_eq!("06/Nov/1994:09:49:37 +0100", odt.for_log());
+/// assert_eq!("Nov  6 09:49:37", odt.for_syslog());
+/// assert_eq!("784111777 +0100", odt.for_git());
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", odt.dt.for_header());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct OffsetDatetime {
+  pub dt: Datetime,
+  pub offset: i32
+}
+
+impl OffsetDatetime {
+
+  pub fn new(dt: Datetime, offset: i32) -> Self {
+    Self { dt, offset }
+  }
+
+  /// Provides the datetime with its fields shifted by the
+  /// offset, saturating at the Unix epoch.
+  pub fn local(&self) -> Datetime {
+    let secs = self.dt.secs.saturating_add_signed(self.offset as i64);
+    Datetime::from(secs)
+  }
+
+  pub fn for_email(&self) -> String {
+    Rfc5322(self).to_string()
+  }
+
+  pub fn for_log(&self) -> String {
+    CommonLog(self, None).to_string()
+  }
+
+  /// Outputs the local datetime as for `for_log`, with the
+  /// month named from the table given.
+  #[cfg(feature = "locale")]
+  pub fn for_log_in(&self, names: &Names) -> String {
+    CommonLog(self, Some (&names.months)).to_string()
+  }
+
+  pub fn parse_log(s: &str) -> Result<Self, ParseError> {
+    parse::parse_common_log(s)
+  }
+
+  /// Outputs the local datetime in the BSD syslog format
+  /// of RFC 3164, e.g. `Nov  6 09:49:37`, with the day
+  /// padded with a space and neither year nor offset.
+  pub fn for_syslog(&self) -> String {
+    BsdSyslog(self, None).to_string()
+  }
+
+  /// Outputs the local datetime as for `for_syslog`, with
+  /// the month named from the table given.
+  #[cfg(feature = "locale")]
+  pub fn for_syslog_in(&self, names: &Names) -> String {
+    BsdSyslog(self, Some (&names.months)).to_string()
+  }
+
+  /// Outputs the seconds since the epoch and the offset as
+  /// in git's raw date format, e.g. `784111777 +0100`.
+  pub fn for_git(&self) -> String {
+    GitRaw(self).to_string()
+  }
+
+  /// Parses git's raw date format, e.g. from `git log
+  /// --format=%ad --date=raw`, the seconds since the epoch
+  /// optionally prefixed with `@`.
+  pub fn parse_git(s: &str) -> Result<Self, ParseError> {
+    parse::parse_git_raw(s)
+  }
+}
+
+// Offset
+
+struct Offset(i32);
+
+impl Display for Offset {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let Offset(offset) = self;
+    let sign = if *offset < 0 { '-' } else { '+' };
+    let abs = offset.unsigned_abs() as u64;
+    write!(f, "{}{:02}{:02}", sign, abs / H_AS_S, abs / M_AS_S % H_AS_M)
+  }
+}
+
+// Rfc5322
+
+struct Rfc5322<'a>(&'a OffsetDatetime);
+
+impl Display for Rfc5322<'_> {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let Rfc5322(odt) = self;
+    let local = odt.local();
+    write!(f, "{} {} {}",
+      ImfFixdateDate(&local.date),
+      ImfFixdateTime(&local.time),
+      Offset(odt.offset)
+    )
+  }
+}
+
+// the month name from any table given, else the English
+fn month_name<'a>(m: Month, months: Option<&[&'a str; 12]>) -> &'a str {
+  match months {
+    Some (months) => months[Month::iter().position(|other| other == m).unwrap_or_default()],
+    None          => m.abbrev()
+  }
+}
+
+// CommonLog
+
+struct CommonLog<'a>(&'a OffsetDatetime, Option<&'a [&'a str; 12]>);
+
+impl Display for CommonLog<'_> {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let CommonLog(odt, months) = self;
+    let local = odt.local();
+    write!(f, "{:02}/{}/{}:{} {}",
+      local.date.d,
+      month_name(local.date.m, *months),
+      local.date.y,
+      ImfFixdateTime(&local.time),
+      Offset(odt.offset)
+    )
+  }
+}
+
+// BsdSyslog
+
+struct BsdSyslog<'a>(&'a OffsetDatetime, Option<&'a [&'a str; 12]>);
+
+impl Display for BsdSyslog<'_> {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let BsdSyslog(odt, months) = self;
+    let local = odt.local();
+    write!(f, "{} {:>2} {}",
+      month_name(local.date.m, *months),
+      local.date.d,
+      ImfFixdateTime(&local.time)
+    )
+  }
+}
+
+// GitRaw
+
+struct GitRaw<'a>(&'a OffsetDatetime);
+
+impl Display for GitRaw<'_> {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let GitRaw(odt) = self;
+    write!(f, "{} {}", odt.dt.secs, Offset(odt.offset))
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::OffsetDatetime;
+  use crate::datetime::Datetime;
+  use crate::parse::ParseError;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn offset_local() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!(dt,                                           OffsetDatetime::new(dt,                       0).local());
+    assert_eq!(Datetime::from(NOV_06_1994_08_49_37 +  3600), OffsetDatetime::new(dt,                    3600).local());
+    assert_eq!(Datetime::from(NOV_06_1994_08_49_37 - 18000), OffsetDatetime::new(dt,                  -18000).local());
+    assert_eq!(Datetime::default(),                          OffsetDatetime::new(Datetime::default(), -3600).local());
+  }
+
+  #[test]
+  fn offset_for_email() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!("Sun, 06 Nov 1994 08:49:37 +0000", OffsetDatetime::new(dt,      0).for_email());
+    assert_eq!("Sun, 06 Nov 1994 14:19:37 +0530", OffsetDatetime::new(dt,  19800).for_email());
+    assert_eq!("Sun, 06 Nov 1994 03:49:37 -0500", OffsetDatetime::new(dt, -18000).for_email());
+    assert_eq!("Sat, 05 Nov 1994 22:19:37 -1030", OffsetDatetime::new(dt, -37800).for_email());
+  }
+
+  #[test]
+  fn offset_for_log() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!("06/Nov/1994:08:49:37 +0000", OffsetDatetime::new(dt,      0).for_log());
+    assert_eq!("06/Nov/1994:03:49:37 -0500", OffsetDatetime::new(dt, -18000).for_log());
+  }
+
+  #[test]
+  fn offset_parse_log() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (OffsetDatetime::new(dt,      0)), OffsetDatetime::parse_log("06/Nov/1994:08:49:37 +0000"));
+    assert_eq!(Ok (OffsetDatetime::new(dt,  19800)), OffsetDatetime::parse_log("06/Nov/1994:14:19:37 +0530"));
+    assert_eq!(Ok (OffsetDatetime::new(dt, -18000)), OffsetDatetime::parse_log("06/Nov/1994:03:49:37 -0500"));
+
+    assert_eq!(Err(ParseError::Format), OffsetDatetime::parse_log("06/Nov/1994:08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Range),  OffsetDatetime::parse_log("06/Nov/1994:08:49:37 +0060"));
+    assert_eq!(Err(ParseError::Range),  OffsetDatetime::parse_log("01/Jan/1970:00:00:00 +0100"));
+  }
+
+  #[test]
+  fn offset_for_syslog() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!("Nov  6 08:49:37", OffsetDatetime::new(dt,      0).for_syslog());
+    assert_eq!("Nov  6 03:49:37", OffsetDatetime::new(dt, -18000).for_syslog());
+    assert_eq!("Nov  5 22:19:37", OffsetDatetime::new(dt, -37800).for_syslog());
+    assert_eq!("Dec 31 23:59:59", OffsetDatetime::new(Datetime::from(31_535_999), 0).for_syslog());
+    assert_eq!("Jan  1 00:00:00", OffsetDatetime::new(Datetime::default(),       0).for_syslog());
+  }
+
+  #[cfg(feature = "locale")]
+  #[test]
+  fn offset_for_log_syslog_in() {
+
+    use crate::locale::Names;
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+    let names = Names { months: ["1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12"], ..Names::ENGLISH };
+
+    assert_eq!("06/11/1994:09:49:37 +0100", OffsetDatetime::new(dt, 3600).for_log_in(&names));
+    assert_eq!("11  6 09:49:37",            OffsetDatetime::new(dt, 3600).for_syslog_in(&names));
+    assert_eq!("31/12/1970:23:59:59 +0000", OffsetDatetime::new(Datetime::from(31_535_999), 0).for_log_in(&names));
+
+    // as by default with the English names
+    let odt = OffsetDatetime::new(dt, -18000);
+    assert_eq!(odt.for_log(),    odt.for_log_in(&Names::ENGLISH));
+    assert_eq!(odt.for_syslog(), odt.for_syslog_in(&Names::ENGLISH));
+  }
+
+  #[test]
+  fn offset_for_git() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!("784111777 +0000", OffsetDatetime::new(dt,      0).for_git());
+    assert_eq!("784111777 +0530", OffsetDatetime::new(dt,  19800).for_git());
+    assert_eq!("784111777 -0500", OffsetDatetime::new(dt, -18000).for_git());
+    assert_eq!("0 +0000",         OffsetDatetime::new(Datetime::default(), 0).for_git());
+  }
+
+  #[test]
+  fn offset_parse_git() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (OffsetDatetime::new(dt,      0)), OffsetDatetime::parse_git("784111777 +0000"));
+    assert_eq!(Ok (OffsetDatetime::new(dt,  19800)), OffsetDatetime::parse_git("784111777 +0530"));
+    assert_eq!(Ok (OffsetDatetime::new(dt, -18000)), OffsetDatetime::parse_git("@784111777 -0500"));
+
+    assert_eq!(Err(ParseError::Format), OffsetDatetime::parse_git("784111777"));
+    assert_eq!(Err(ParseError::Format), OffsetDatetime::parse_git("784111777 GMT"));
+    assert_eq!(Err(ParseError::Format), OffsetDatetime::parse_git("-1 +0000"));
+    assert_eq!(Err(ParseError::Format), OffsetDatetime::parse_git("784111777  +0000"));
+    assert_eq!(Err(ParseError::Range),  OffsetDatetime::parse_git("784111777 +0060"));
+    assert_eq!(Err(ParseError::Range),  OffsetDatetime::parse_git("784111777 +2400"));
+    assert_eq!(Err(ParseError::Range),  OffsetDatetime::parse_git("784111777 +9959"));
+    assert_eq!(Err(ParseError::Range),  OffsetDatetime::parse_git("253402300800 +0000"));
+
+    // round trip
+    let odt = OffsetDatetime::new(dt, 3600);
+    assert_eq!(Ok (odt), OffsetDatetime::parse_git(&odt.for_git()));
+  }
+}
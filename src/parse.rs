@@ -0,0 +1,1191 @@
+//! # Parse
+//!
+//! Parsing of HTTP Date header timestamps in the three
+//! formats recipients are required to accept, i.e. the
+//! IMF-fixdate and the obsolete RFC 850 and asctime, and
+//! of other timestamps, e.g. RFC 3339 and log entries.
+
+use crate::datetime::{Datetime, MAX_SECS};
+use crate::offset::OffsetDatetime;
+use crate::date::{Date, Weekday, Month, Year, D_AS_S};
+use crate::time::{Time, M_AS_S, H_AS_S, H_AS_M, D_AS_H};
+#[cfg(feature = "simd")]
+use crate::simd;
+
+use std::ops::RangeInclusive;
+use std::fmt::{self, Display, Formatter};
+use std::error::Error;
+
+// RFC 5322 obsolete zones in hours east of GMT, with the
+// single-letter military zones treated as GMT
+const ZONES: [(&str, i32); 11] = [
+  ("GMT",  0), ("UT",   0), ("UTC",  0),
+  ("EST", -5), ("EDT", -4), ("CST", -6), ("CDT", -5),
+  ("MST", -7), ("MDT", -6), ("PST", -8), ("PDT", -7)
+];
+
+/// Configures parsing via `Datetime::parse_with_options`,
+/// i.e. the range of years accepted, by default that from
+/// the Unix epoch to the last four-digit year, i.e. 1970
+/// to 9999, with years outside the range reported with
+/// `ParseError::Year`, and whether parsing is lenient, by
+/// default not. In lenient mode, the IMF-fixdate and RFC
+/// 850 formats accept a numeric offset, e.g. `+0200`, or
+/// an obsolete named zone, e.g. `EST`, in place of `GMT`,
+/// with the result converted to GMT, the year of which is
+/// that checked against the range. Single-letter
+/// military zones are treated as GMT, per RFC 5322. Weekday,
+/// month and zone names are matched case-insensitively,
+/// e.g. `tue` or `gmt`. A bare number of seconds since the
+/// epoch, optionally prefixed with `@`, e.g. `@784111777`,
+/// is also accepted, as are days of one digit, optionally
+/// padded with a space, e.g. `Sun, 6 Nov 1994`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, ParseOptions, ParseError};
+///
+/// let options = ParseOptions::default()
+///   .years(1990..=2100);
+///
+/// let result = Datetime::parse_with_options("Thu, 01 Jan 1970 00:00:00 GMT", &options);
+///
+/// assert_eq!(Err(ParseError::Year(1970)), result);
+///
+/// let options = ParseOptions::default()
+///   .lenient(true);
+///
+/// let dt = Datetime::parse_with_options("Sun, 06 Nov 1994 10:49:37 +0200", &options).unwrap();
+///
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", dt.for_header());
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct ParseOptions {
+  pub years: RangeInclusive<u64>,
+  pub lenient: bool
+}
+
+impl Default for ParseOptions {
+
+  fn default() -> Self {
+    Self {
+      years: 1970..=9999,
+      lenient: false
+    }
+  }
+}
+
+impl ParseOptions {
+
+  pub fn years(mut self, years: RangeInclusive<u64>) -> Self {
+    self.years = years;
+    self
+  }
+
+  pub fn lenient(mut self, lenient: bool) -> Self {
+    self.lenient = lenient;
+    self
+  }
+}
+
+// ParseError
+
+/// Indicates why a timestamp could not be parsed.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ParseError {
+  Format,
+  Range,
+  Weekday,
+  Year(u64)
+}
+
+impl Display for ParseError {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Format  => write!(f, "timestamp not in an accepted format"),
+      Self::Range   => write!(f, "timestamp field out of range"),
+      Self::Weekday => write!(f, "weekday does not match date"),
+      Self::Year(y) => write!(f, "year {y} outside accepted range")
+    }
+  }
+}
+
+impl Error for ParseError {}
+
+// ParseErrorDetail
+
+/// Indicates why a timestamp could not be parsed, as for
+/// `ParseError`, with the field at fault and its byte
+/// offset, e.g. for logging malformed upstream dates.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, ParseOptions, ParseError, ParseField};
+///
+/// let detail = Datetime::parse_detailed("Sun, 31 Nov 1994 08:49:37 GMT", &ParseOptions::default()).unwrap_err();
+///
+/// assert_eq!(ParseError::Range, detail.error);
+/// assert_eq!(ParseField::Day, detail.field);
+/// assert_eq!(5, detail.pos);
+/// assert_eq!("timestamp field out of range: day at byte 5", detail.to_string());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ParseErrorDetail {
+  pub error: ParseError,
+  pub field: ParseField,
+  pub pos: usize
+}
+
+impl Display for ParseErrorDetail {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}: {} at byte {}", self.error, self.field, self.pos)
+  }
+}
+
+impl Error for ParseErrorDetail {}
+
+/// Identifies a field of a timestamp, or a separator or the
+/// end of the timestamp, as at fault in a `ParseErrorDetail`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ParseField {
+  Weekday,
+  Day,
+  Month,
+  Year,
+  Hour,
+  Minute,
+  Second,
+  Zone,
+  Separator,
+  End
+}
+
+impl Display for ParseField {
+
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let name = match self {
+      Self::Weekday   => "weekday",
+      Self::Day       => "day",
+      Self::Month     => "month",
+      Self::Year      => "year",
+      Self::Hour      => "hour",
+      Self::Minute    => "minute",
+      Self::Second    => "second",
+      Self::Zone      => "zone",
+      Self::Separator => "separator",
+      Self::End       => "end"
+    };
+    write!(f, "{name}")
+  }
+}
+
+// parse
+
+pub(crate) fn parse(s: &str, options: &ParseOptions) -> Result<Datetime, ParseError> {
+  parse_detailed(s, options).map_err(|detail| detail.error)
+}
+
+pub(crate) fn parse_detailed(s: &str, options: &ParseOptions) -> Result<Datetime, ParseErrorDetail> {
+  if options.lenient {
+    if let Some (secs) = epoch(s) {
+      return secs
+        .and_then(|secs| from_epoch(secs, options))
+        .map_err(|error| ParseErrorDetail { error, field: ParseField::Year, pos: 0 })
+    }
+  }
+  // on error, reparsed below for the detail
+  #[cfg(feature = "simd")]
+  if let Some (dt) = imf_fixdate_simd(s, options) {
+    return Ok (dt)
+  }
+  let mut c = Cursor::new(s);
+  c.fold = options.lenient;
+  let fields = match c.bytes.get(3) {
+    Some(b',') => imf_fixdate(&mut c, options),
+    Some(b' ') => asctime(&mut c, options),
+    Some(_)    => rfc850(&mut c, options),
+    None       => Err(ParseError::Format)
+  };
+  fields
+    .and_then(|fields| build(&mut c, fields, options))
+    .map_err(|error| c.detail(error))
+}
+
+// e.g. "784111777" or "@784111777"
+fn epoch(s: &str) -> Option<Result<u64, ParseError>> {
+  let digits = s.strip_prefix('@').unwrap_or(s);
+  if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+    return None
+  }
+  Some (digits.parse().map_err(|_| ParseError::Range))
+}
+
+fn from_epoch(secs: u64, options: &ParseOptions) -> Result<Datetime, ParseError> {
+  // bounded before conversion, which counts up by year
+  let end = Year((*options.years.end()).clamp(1969, 9999) + 1);
+  if secs >= end.days_before() * D_AS_S {
+    return Err(ParseError::Range)
+  }
+  let dt = Datetime::from(secs);
+  let Year(y) = dt.date.y;
+  match options.years.contains(&y) {
+    true  => Ok (dt),
+    false => Err(ParseError::Year(y))
+  }
+}
+
+struct Fields {
+  wd: Option<Weekday>,
+   d: u64,
+   m: Month,
+   y: u64,
+  hh: u64,
+  mm: u64,
+  ss: u64,
+  // seconds east of GMT
+  off: i32
+}
+
+// e.g. "Sun, 06 Nov 1994 08:49:37 GMT"
+fn imf_fixdate(c: &mut Cursor, options: &ParseOptions) -> Result<Fields, ParseError> {
+  let wd = c.weekday()?;                      c.tag(b", ")?;
+  let  d = c.day(options.lenient)?;           c.tag(b" ")?;
+  let  m = c.month()?;                        c.tag(b" ")?;
+  let  y = c.at(ParseField::Year).digits(4)?; c.tag(b" ")?;
+  let (hh, mm, ss) = c.time()?;
+  let off = c.zone(options)?;
+  c.end()?;
+  Ok (Fields { wd: Some (wd), d, m, y, hh, mm, ss, off })
+}
+
+// as for imf_fixdate, with the shape validated and the
+// digits read via simd, or None to fall back
+#[cfg(feature = "simd")]
+fn imf_fixdate_simd(s: &str, options: &ParseOptions) -> Option<Datetime> {
+  let v = simd::imf_fixdate(s.as_bytes())?;
+  let mut c = Cursor::new(s);
+  let wd = c.weekday().ok()?;
+  c.pos = 8;
+  let  m = c.month().ok()?;
+  let n = |i: usize| (v[i] * 10 + v[i + 1]) as u64;
+  let fields = Fields { wd: Some (wd), d: n(5), m, y: n(12) * 100 + n(14), hh: n(17), mm: n(20), ss: n(23), off: 0 };
+  build(&mut c, fields, options).ok()
+}
+
+// e.g. "Sunday, 06-Nov-94 08:49:37 GMT"
+fn rfc850(c: &mut Cursor, options: &ParseOptions) -> Result<Fields, ParseError> {
+  let wd = c.weekday_long()?;                 c.tag(b", ")?;
+  let  d = c.day(options.lenient)?;           c.tag(b"-")?;
+  let  m = c.month()?;                        c.tag(b"-")?;
+  let yy = c.at(ParseField::Year).digits(2)?; c.tag(b" ")?;
+  let (hh, mm, ss) = c.time()?;
+  let off = c.zone(options)?;
+  c.end()?;
+  let y = if yy < 70 { 2000 + yy } else { 1900 + yy };
+  Ok (Fields { wd: Some (wd), d, m, y, hh, mm, ss, off })
+}
+
+// e.g. "Sun Nov  6 08:49:37 1994"
+fn asctime(c: &mut Cursor, options: &ParseOptions) -> Result<Fields, ParseError> {
+  let wd = c.weekday()?;  c.tag(b" ")?;
+  let  m = c.month()?;    c.tag(b" ")?;
+  let  d = match c.peek() {
+    _ if options.lenient => c.day(true)?,
+    Some (b' ')          => { c.tag(b" ")?; c.at(ParseField::Day).digits(1)? },
+    _                    => c.at(ParseField::Day).digits(2)?
+  };
+  c.tag(b" ")?;
+  let (hh, mm, ss) = c.time()?;
+  c.tag(b" ")?;
+  let  y = c.at(ParseField::Year).digits(4)?;
+  c.end()?;
+  Ok (Fields { wd: Some (wd), d, m, y, hh, mm, ss, off: 0 })
+}
+
+// e.g. "06/Nov/1994:08:49:37 +0100"
+pub(crate) fn parse_common_log(s: &str) -> Result<OffsetDatetime, ParseError> {
+  let mut c = Cursor::new(s);
+  let  d = c.digits(2)?;  c.tag(b"/")?;
+  let  m = c.month()?;    c.tag(b"/")?;
+  let  y = c.digits(4)?;  c.tag(b":")?;
+  let (hh, mm, ss) = c.time()?;
+  c.tag(b" ")?;
+  let off = c.offset()?;
+  c.end()?;
+  let dt = build(&mut c, Fields { wd: None, d, m, y, hh, mm, ss, off }, &ParseOptions::default())?;
+  Ok (OffsetDatetime::new(dt, off))
+}
+
+// e.g. "784111777 +0100", as in git's raw format
+pub(crate) fn parse_git_raw(s: &str) -> Result<OffsetDatetime, ParseError> {
+  let (secs, offset) = s.split_once(' ').ok_or(ParseError::Format)?;
+  let secs = epoch(secs).ok_or(ParseError::Format)??;
+  let mut c = Cursor::new(offset);
+  let off = c.offset()?;
+  c.end()?;
+  let dt = from_epoch(secs, &ParseOptions::default())?;
+  Ok (OffsetDatetime::new(dt, off))
+}
+
+// e.g. "1994-11-06T08:49:37Z" or "1994-11-06T10:49:37.5+02:00"
+pub(crate) fn parse_rfc3339(s: &str) -> Result<Datetime, ParseError> {
+  let mut c = Cursor::new(s);
+  let  y = c.digits(4)?;  c.tag(b"-")?;
+  let mn = c.digits(2)?;  c.tag(b"-")?;
+  let  d = c.digits(2)?;
+  match c.take(1)? {
+    b"T" | b"t" | b" " => (),
+    _                  => return Err(ParseError::Format)
+  };
+  let (hh, mm, ss) = c.time()?;
+  // fractional seconds truncated
+  if c.peek() == Some (b'.') {
+    c.pos += 1;
+    let start = c.pos;
+    while c.peek().is_some_and(|b| b.is_ascii_digit()) { c.pos += 1 };
+    if c.pos == start { return Err(ParseError::Format) };
+  }
+  let off = match c.peek() {
+    Some (b'Z' | b'z') => { c.pos += 1; 0 },
+    _                  => {
+      let sign = match c.take(1)? {
+        b"+" =>  1,
+        b"-" => -1,
+        _    => return Err(ParseError::Format)
+      };
+      let oh = c.digits(2)?;  c.tag(b":")?;
+      let om = c.digits(2)?;
+      if oh >= D_AS_H || om >= H_AS_M { return Err(ParseError::Range) };
+      sign * (oh * H_AS_S + om * M_AS_S) as i32
+    }
+  };
+  c.end()?;
+  if !(1..=12).contains(&mn) { return Err(ParseError::Range) };
+  let m = Month::default().skip(mn - 1);
+  build(&mut c, Fields { wd: None, d, m, y, hh, mm, ss, off }, &ParseOptions::default())
+}
+
+// e.g. "Sun, 6 Nov 1994 10:49:37 +0200"
+pub(crate) fn parse_rfc2822(s: &str) -> Result<Datetime, ParseError> {
+  let mut c = Cursor::new(s);
+  c.space(0)?;
+  let wd = match c.peek() {
+    Some (b'A'..=b'Z') => {
+      let wd = c.weekday()?;
+      c.space(0)?; c.tag(b",")?; c.space(0)?;
+      Some (wd)
+    },
+    _ => None
+  };
+  let  d = c.number(1, 2)?;  c.space(1)?;
+  let  m = c.month()?;       c.space(1)?;
+  let  y = match c.number(2, 4)? {
+    yy if yy < 50   => 2000 + yy,
+    yy if yy < 1000 => 1900 + yy,
+    y               => y
+  };
+  c.space(1)?;
+  let hh = c.digits(2)?;  c.tag(b":")?;
+  let mm = c.digits(2)?;
+  let ss = match c.peek() {
+    Some (b':') => { c.pos += 1; c.digits(2)? },
+    _           => 0
+  };
+  c.space(1)?;
+  let off = match c.peek() {
+    Some (b'+' | b'-') => c.offset()?,
+    _                  => c.zone_name()?
+  };
+  c.space(0)?;
+  c.end()?;
+  build(&mut c, Fields { wd, d, m, y, hh, mm, ss, off }, &ParseOptions::default())
+}
+
+// e.g. "from a.example by b.example (envelope-from <x@a.example>);\r\n Sun, 6 Nov 1994 10:49:37 +0200 (CEST)"
+pub(crate) fn parse_rfc5322(s: &str) -> Result<Datetime, ParseError> {
+  let stripped = strip_cfws(s)?;
+  let date_time = stripped
+    .rsplit(';')
+    .next()
+    .unwrap_or_default();
+  parse_rfc2822(date_time)
+}
+
+// comments, possibly nested and with quoted pairs, and
+// line breaks each replaced with a space
+fn strip_cfws(s: &str) -> Result<String, ParseError> {
+  let mut stripped = String::with_capacity(s.len());
+  let mut depth = 0_usize;
+  let mut chars = s.chars();
+  while let Some (ch) = chars.next() {
+    match (ch, depth) {
+      ('\\',        1..) => { chars.next(); },
+      ('(',          0)  => { stripped.push(' '); depth = 1 },
+      ('(',          _)  => depth += 1,
+      (')',          0)  => return Err(ParseError::Format),
+      (')',          _)  => depth -= 1,
+      ('\r' | '\n',  0)  => stripped.push(' '),
+      (_,            0)  => stripped.push(ch),
+      _                  => ()
+    }
+  }
+  match depth {
+    0 => Ok (stripped),
+    _ => Err(ParseError::Format)
+  }
+}
+
+// e.g. "19941106-084937" with "%Y%m%d-%H%M%S"
+pub(crate) fn parse_format(fmt: &str, s: &str) -> Result<Datetime, ParseError> {
+  let mut c = Cursor::new(s);
+  let mut f = Fields { wd: None, d: 1, m: Month::default(), y: 1970, hh: 0, mm: 0, ss: 0, off: 0 };
+  let mut spec = fmt.bytes();
+  while let Some (b) = spec.next() {
+    if b != b'%' {
+      c.tag(&[b])?;
+      continue
+    }
+    match spec.next() {
+      Some (b'a') => f.wd  = Some (c.weekday()?),
+      Some (b'A') => f.wd  = Some (c.weekday_long()?),
+      Some (b'd') => f.d   = c.at(ParseField::Day).digits(2)?,
+      Some (b'e') => f.d   = c.day(true)?,
+      Some (b'b') => f.m   = c.month()?,
+      Some (b'B') => f.m   = c.month_long()?,
+      Some (b'm') => f.m   = c.month_number()?,
+      Some (b'Y') => f.y   = c.at(ParseField::Year).digits(4)?,
+      Some (b'y') => f.y   = match c.at(ParseField::Year).digits(2)? {
+        yy if yy < 70 => 2000 + yy,
+        yy            => 1900 + yy
+      },
+      Some (b'H') => f.hh  = c.at(ParseField::Hour).digits(2)?,
+      Some (b'M') => f.mm  = c.at(ParseField::Minute).digits(2)?,
+      Some (b'S') => f.ss  = c.at(ParseField::Second).digits(2)?,
+      Some (b'z') => f.off = c.at(ParseField::Zone).offset()?,
+      Some (b'Z') => f.off = c.at(ParseField::Zone).zone_name()?,
+      Some (b'%') => c.tag(b"%")?,
+      _           => return Err(ParseError::Format)
+    }
+  }
+  c.end()?;
+  build(&mut c, f, &ParseOptions::default())
+}
+
+// e.g. "06 Nov 1994" or "Sun, 06 Nov 1994"
+pub(crate) fn parse_date(s: &str) -> Result<Date, ParseError> {
+  let mut c = Cursor::new(s);
+  let wd = match c.bytes.get(3) {
+    Some (b',') => { let wd = c.weekday()?; c.tag(b", ")?; Some (wd) },
+    _           => None
+  };
+  let  d = c.digits(2)?;  c.tag(b" ")?;
+  let  m = c.month()?;    c.tag(b" ")?;
+  let  y = c.digits(4)?;
+  c.end()?;
+  let dt = build(&mut c, Fields { wd, d, m, y, hh: 0, mm: 0, ss: 0, off: 0 }, &ParseOptions::default())?;
+  Ok (dt.date)
+}
+
+// e.g. "08:49:37"
+pub(crate) fn parse_time(s: &str) -> Result<Time, ParseError> {
+  let mut c = Cursor::new(s);
+  let (hh, mm, ss) = c.time()?;
+  c.end()?;
+  if hh >= D_AS_H || mm >= H_AS_M || ss >= M_AS_S {
+    return Err(ParseError::Range)
+  }
+  Ok (Time::from(hh * H_AS_S + mm * M_AS_S + ss))
+}
+
+// with the field at fault recorded by the cursor
+fn build(c: &mut Cursor, fields: Fields, options: &ParseOptions) -> Result<Datetime, ParseError> {
+
+  let Fields { wd, d, m, y, hh, mm, ss, off } = fields;
+
+  // the years accepted checked against the year in GMT, so
+  // where offset, once the offset is applied
+  if y < 1970 || off == 0 && !options.years.contains(&y) {
+    return Err(c.fault(ParseField::Year, ParseError::Year(y)))
+  }
+  let y = Year(y);
+
+  let out_of_range = [
+    (ParseField::Day,    d < 1 || d > m.len(y.is_leap()) as u64),
+    (ParseField::Hour,   hh >= D_AS_H),
+    (ParseField::Minute, mm >= H_AS_M),
+    (ParseField::Second, ss >= M_AS_S)
+  ];
+  if let Some ((field, _)) = out_of_range.into_iter().find(|(_, is_out)| *is_out) {
+    return Err(c.fault(field, ParseError::Range))
+  }
+
+  let days = y.days_before() + m.days_before(y.is_leap()) + d - 1;
+  let days_wd = Weekday::from_days_since_epoch(days);
+  match wd {
+    Some (wd) if wd != days_wd => return Err(c.fault(ParseField::Weekday, ParseError::Weekday)),
+    _                          => ()
+  };
+
+  let secs = days * D_AS_S + hh * H_AS_S + mm * M_AS_S + ss;
+  if off != 0 {
+    let secs = secs
+      .checked_add_signed(-off as i64)
+      .filter(|secs| *secs <= MAX_SECS)
+      .ok_or_else(|| c.fault(ParseField::Zone, ParseError::Range))?;
+    let dt = Datetime::from(secs);
+    let Year(y) = dt.date.y;
+    if !options.years.contains(&y) {
+      return Err(c.fault(ParseField::Year, ParseError::Year(y)))
+    }
+    return Ok (dt)
+  }
+  let date = Date { d: d as u8, wd: days_wd, m, y };
+  let time = Time::from(secs);
+  Ok (Datetime { date, time, secs })
+}
+
+// Cursor
+
+struct Cursor<'a> {
+   bytes: &'a [u8],
+     pos: usize,
+  // the field being read and the start of each field read
+   field: ParseField,
+  starts: [usize; 10],
+  // whether names are matched case-insensitively
+    fold: bool
+}
+
+impl<'a> Cursor<'a> {
+
+  fn new(s: &'a str) -> Self {
+    Self { bytes: s.as_bytes(), pos: 0, field: ParseField::Weekday, starts: [0; 10], fold: false }
+  }
+
+  fn at(&mut self, field: ParseField) -> &mut Self {
+    self.field = field;
+    self.starts[field as usize] = self.pos;
+    self
+  }
+
+  fn fault(&mut self, field: ParseField, error: ParseError) -> ParseError {
+    self.field = field;
+    error
+  }
+
+  fn detail(&self, error: ParseError) -> ParseErrorDetail {
+    ParseErrorDetail { error, field: self.field, pos: self.starts[self.field as usize] }
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.bytes.get(self.pos).copied()
+  }
+
+  fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+    let taken = self.bytes
+      .get(self.pos..self.pos + n)
+      .ok_or(ParseError::Format)?;
+    self.pos += n;
+    Ok (taken)
+  }
+
+  fn tag(&mut self, tag: &[u8]) -> Result<(), ParseError> {
+    self.at(ParseField::Separator).literal(tag)
+  }
+
+  fn literal(&mut self, tag: &[u8]) -> Result<(), ParseError> {
+    let taken = self.take(tag.len())?;
+    match taken == tag || self.fold && taken.eq_ignore_ascii_case(tag) {
+      true  => Ok (()),
+      false => Err(ParseError::Format)
+    }
+  }
+
+  fn digits(&mut self, n: usize) -> Result<u64, ParseError> {
+    self.take(n)?
+      .iter()
+      .try_fold(0, |acc, b| match b {
+        b'0'..=b'9' => Ok (acc * 10 + (b - b'0') as u64),
+        _           => Err(ParseError::Format)
+      })
+  }
+
+  // spaces and tabs, at least min
+  fn space(&mut self, min: usize) -> Result<(), ParseError> {
+    let start = self.pos;
+    while matches!(self.peek(), Some (b' ' | b'\t')) { self.pos += 1 };
+    match self.pos - start >= min {
+      true  => Ok (()),
+      false => Err(ParseError::Format)
+    }
+  }
+
+  // from min to max digits
+  fn number(&mut self, min: usize, max: usize) -> Result<u64, ParseError> {
+    let rest = &self.bytes[self.pos..];
+    let n = rest.iter().take(max).take_while(|b| b.is_ascii_digit()).count();
+    match n >= min {
+      true  => self.digits(n),
+      false => Err(ParseError::Format)
+    }
+  }
+
+  // two digits, or in lenient mode one or two, optionally
+  // padded with a space, e.g. "6" or " 6"
+  fn day(&mut self, lenient: bool) -> Result<u64, ParseError> {
+    if lenient && self.peek() == Some (b' ') { self.pos += 1 };
+    self.at(ParseField::Day);
+    match lenient {
+      true  => self.number(1, 2),
+      false => self.digits(2)
+    }
+  }
+
+  fn time(&mut self) -> Result<(u64, u64, u64), ParseError> {
+    let hh = self.at(ParseField::Hour).digits(2)?;   self.tag(b":")?;
+    let mm = self.at(ParseField::Minute).digits(2)?; self.tag(b":")?;
+    let ss = self.at(ParseField::Second).digits(2)?;
+    Ok ((hh, mm, ss))
+  }
+
+  // e.g. "+0100"
+  fn offset(&mut self) -> Result<i32, ParseError> {
+    let sign = match self.take(1)? {
+      b"+" =>  1,
+      b"-" => -1,
+      _    => return Err(ParseError::Format)
+    };
+    let hh = self.digits(2)?;
+    let mm = self.digits(2)?;
+    if hh >= D_AS_H || mm >= H_AS_M { return Err(ParseError::Range) };
+    Ok (sign * (hh * H_AS_S + mm * M_AS_S) as i32)
+  }
+
+  // " GMT", or in lenient mode e.g. " +0100" or " EST"
+  fn zone(&mut self, options: &ParseOptions) -> Result<i32, ParseError> {
+    self.tag(b" ")?;
+    self.at(ParseField::Zone);
+    match self.peek() {
+      Some (b'+' | b'-') if options.lenient               => self.offset(),
+      Some (b'A'..=b'Z' | b'a'..=b'z') if options.lenient => self.zone_name(),
+      _                                                   => self.literal(b"GMT").map(|_| 0)
+    }
+  }
+
+  fn zone_name(&mut self) -> Result<i32, ParseError> {
+    let rest = &self.bytes[self.pos..];
+    let len = rest.iter()
+      .take_while(|b| b.is_ascii_uppercase() || self.fold && b.is_ascii_lowercase())
+      .count();
+    let token = &rest[..len];
+    self.pos += len;
+    match ZONES.into_iter().find(|(name, _)| name.as_bytes().eq_ignore_ascii_case(token)) {
+      Some ((_, hh))                                               => Ok (hh * H_AS_S as i32),
+      None if token.len() == 1 && !token.eq_ignore_ascii_case(b"J") => Ok (0),
+      None                                                         => Err(ParseError::Format)
+    }
+  }
+
+  fn weekday(&mut self) -> Result<Weekday, ParseError> {
+    self.at(ParseField::Weekday);
+    Weekday::from_abbrev(&self.token()?)
+      .ok_or(ParseError::Format)
+  }
+
+  fn weekday_long(&mut self) -> Result<Weekday, ParseError> {
+    self.at(ParseField::Weekday);
+    let rest = &self.bytes[self.pos..];
+    let wd = Weekday::iter()
+      .find(|wd| {
+        let name = wd.full_name().as_bytes();
+        rest.get(..name.len()).is_some_and(|r| r == name || self.fold && r.eq_ignore_ascii_case(name))
+      })
+      .ok_or(ParseError::Format)?;
+    self.pos += wd.full_name().len();
+    Ok (wd)
+  }
+
+  fn month_long(&mut self) -> Result<Month, ParseError> {
+    self.at(ParseField::Month);
+    let rest = &self.bytes[self.pos..];
+    let m = Month::iter()
+      .find(|m| {
+        let name = m.full_name().as_bytes();
+        rest.get(..name.len()).is_some_and(|r| r == name || self.fold && r.eq_ignore_ascii_case(name))
+      })
+      .ok_or(ParseError::Format)?;
+    self.pos += m.full_name().len();
+    Ok (m)
+  }
+
+  // two digits, from "01" for January
+  fn month_number(&mut self) -> Result<Month, ParseError> {
+    let mn = self.at(ParseField::Month).digits(2)?;
+    if !(1..=12).contains(&mn) { return Err(ParseError::Range) };
+    Ok (Month::default().skip(mn - 1))
+  }
+
+  fn month(&mut self) -> Result<Month, ParseError> {
+    self.at(ParseField::Month);
+    Month::from_abbrev(&self.token()?)
+      .ok_or(ParseError::Format)
+  }
+
+  // folded to e.g. "Sun" where matching case-insensitively
+  fn token(&mut self) -> Result<[u8; 3], ParseError> {
+    let token: [u8; 3] = self.take(3)?
+      .try_into()
+      .map_err(|_| ParseError::Format)?;
+    match self.fold {
+      true  => Ok ([token[0].to_ascii_uppercase(), token[1].to_ascii_lowercase(), token[2].to_ascii_lowercase()]),
+      false => Ok (token)
+    }
+  }
+
+  fn end(&mut self) -> Result<(), ParseError> {
+    self.at(ParseField::End);
+    match self.pos == self.bytes.len() {
+      true  => Ok (()),
+      false => Err(ParseError::Format)
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{ParseOptions, ParseError, ParseField};
+  use crate::datetime::{Datetime, MAX_SECS};
+  use crate::date::test::{M_31_AS_S, M_29_AS_S, Y_365_AS_S, Y_366_AS_S};
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn parse_imf_fixdate() {
+
+    assert_eq!(Ok (Datetime::default()), Datetime::parse("Thu, 01 Jan 1970 00:00:00 GMT"));
+
+    let dt = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    assert_eq!(NOV_06_1994_08_49_37, dt.secs);
+    assert_eq!(Datetime::default().set(NOV_06_1994_08_49_37), dt);
+
+    let dt = Datetime::parse("Tue, 29 Feb 1972 23:59:59 GMT").unwrap();
+    assert_eq!(Y_365_AS_S * 2 + M_31_AS_S + M_29_AS_S - 1, dt.secs);
+
+    let dt = Datetime::parse("Tue, 31 Dec 2024 23:59:59 GMT").unwrap();
+    assert_eq!(Y_365_AS_S * 41 + Y_366_AS_S * 14 - 1, dt.secs);
+  }
+
+  #[test]
+  fn parse_rfc850() {
+
+    let dt = Datetime::parse("Sunday, 06-Nov-94 08:49:37 GMT").unwrap();
+    assert_eq!(NOV_06_1994_08_49_37, dt.secs);
+
+    let dt = Datetime::parse("Saturday, 01-Jan-00 00:00:00 GMT").unwrap();
+    assert_eq!("Sat, 01 Jan 2000 00:00:00 GMT", dt.for_header());
+  }
+
+  #[test]
+  fn parse_asctime() {
+
+    let dt = Datetime::parse("Sun Nov  6 08:49:37 1994").unwrap();
+    assert_eq!(NOV_06_1994_08_49_37, dt.secs);
+
+    let dt = Datetime::parse("Tue Dec 31 23:59:59 2024").unwrap();
+    assert_eq!("Tue, 31 Dec 2024 23:59:59 GMT", dt.for_header());
+  }
+
+  #[test]
+  fn parse_invalid() {
+
+    assert_eq!(Err(ParseError::Format),  Datetime::parse(""));
+    assert_eq!(Err(ParseError::Format),  Datetime::parse("Sun, 06 Nov 1994 08:49:37"));
+    assert_eq!(Err(ParseError::Format),  Datetime::parse("Sun, 06 Nov 1994 08:49:37 UTC"));
+    assert_eq!(Err(ParseError::Format),  Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT "));
+    assert_eq!(Err(ParseError::Format),  Datetime::parse("Sun, 6 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format),  Datetime::parse("Sun, 06 nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format),  Datetime::parse("Sun, 06 Nov 1994 08-49-37 GMT"));
+    assert_eq!(Err(ParseError::Range),   Datetime::parse("Thu, 29 Feb 1970 00:00:00 GMT"));
+    assert_eq!(Err(ParseError::Range),   Datetime::parse("Sun, 06 Nov 1994 24:00:00 GMT"));
+    assert_eq!(Err(ParseError::Range),   Datetime::parse("Sun, 06 Nov 1994 08:60:00 GMT"));
+    assert_eq!(Err(ParseError::Weekday), Datetime::parse("Mon, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Year(1969)), Datetime::parse("Wed, 31 Dec 1969 23:59:59 GMT"));
+  }
+
+  #[test]
+  fn parse_detailed() {
+
+    let detail = |s: &str| Datetime::parse_detailed(s, &ParseOptions::default())
+      .map_err(|detail| (detail.error, detail.field, detail.pos));
+
+    assert_eq!(Err((ParseError::Format,     ParseField::Weekday,    0)), detail(""));
+    assert_eq!(Err((ParseError::Format,     ParseField::Weekday,    0)), detail("Xyz, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err((ParseError::Format,     ParseField::Month,      8)), detail("Sun, 06 nov 1994 08:49:37 GMT"));
+    assert_eq!(Err((ParseError::Format,     ParseField::Separator, 25)), detail("Sun, 06 Nov 1994 08:49:37"));
+    assert_eq!(Err((ParseError::Format,     ParseField::Zone,      26)), detail("Sun, 06 Nov 1994 08:49:37 UTC"));
+    assert_eq!(Err((ParseError::Format,     ParseField::End,       29)), detail("Sun, 06 Nov 1994 08:49:37 GMT "));
+    assert_eq!(Err((ParseError::Range,      ParseField::Day,        5)), detail("Sun, 31 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err((ParseError::Range,      ParseField::Hour,      17)), detail("Sun, 06 Nov 1994 24:49:37 GMT"));
+    assert_eq!(Err((ParseError::Range,      ParseField::Minute,    21)), detail("Sunday, 06-Nov-94 08:60:37 GMT"));
+    assert_eq!(Err((ParseError::Range,      ParseField::Second,    17)), detail("Sun Nov  6 08:49:60 1994"));
+    assert_eq!(Err((ParseError::Weekday,    ParseField::Weekday,    0)), detail("Mon, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err((ParseError::Year(1969), ParseField::Year,      12)), detail("Wed, 31 Dec 1969 23:59:59 GMT"));
+    assert_eq!(Ok (Datetime::from(NOV_06_1994_08_49_37)), Datetime::parse_detailed("Sun, 06 Nov 1994 08:49:37 GMT", &ParseOptions::default()));
+
+    assert_eq!("timestamp not in an accepted format: zone at byte 26", Datetime::parse_detailed("Sun, 06 Nov 1994 08:49:37 UTC", &ParseOptions::default()).unwrap_err().to_string());
+  }
+
+  #[test]
+  fn parse_rfc3339() {
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (Datetime::default()), Datetime::from_rfc3339("1970-01-01T00:00:00Z"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-06T08:49:37Z"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-06t08:49:37z"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-06 08:49:37Z"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-06T08:49:37.999Z"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-06T08:49:37+00:00"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-06T10:49:37+02:00"));
+    assert_eq!(Ok (dt), Datetime::from_rfc3339("1994-11-05T22:49:37-10:00"));
+
+    let dt = Datetime::from_rfc3339("2024-12-31T23:59:59Z").unwrap();
+    assert_eq!(Y_365_AS_S * 41 + Y_366_AS_S * 14 - 1, dt.secs);
+
+    assert_eq!(Err(ParseError::Format), Datetime::from_rfc3339("1994-11-06T08:49:37"));
+    assert_eq!(Err(ParseError::Format), Datetime::from_rfc3339("1994-11-06T08:49:37.Z"));
+    assert_eq!(Err(ParseError::Format), Datetime::from_rfc3339("1994-11-06T08:49:37+0200"));
+    assert_eq!(Err(ParseError::Format), Datetime::from_rfc3339("1994-11-06_08:49:37Z"));
+    assert_eq!(Err(ParseError::Format), Datetime::from_rfc3339("94-11-06T08:49:37Z"));
+    assert_eq!(Err(ParseError::Range),  Datetime::from_rfc3339("1994-13-06T08:49:37Z"));
+    assert_eq!(Err(ParseError::Range),  Datetime::from_rfc3339("1994-02-29T08:49:37Z"));
+    assert_eq!(Err(ParseError::Range),  Datetime::from_rfc3339("1994-11-06T08:49:60Z"));
+    assert_eq!(Err(ParseError::Range),  Datetime::from_rfc3339("1994-11-06T08:49:37+24:00"));
+    assert_eq!(Err(ParseError::Range),  Datetime::from_rfc3339("1970-01-01T00:00:00+01:00"));
+    assert_eq!(Err(ParseError::Year(1969)), Datetime::from_rfc3339("1969-12-31T23:59:59Z"));
+  }
+
+  #[test]
+  fn parse_rfc2822() {
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37 +0000"));
+    assert_eq!(Ok (dt), Datetime::from_rfc2822("Sun, 6 Nov 1994 10:49:37 +0200"));
+    assert_eq!(Ok (dt), Datetime::from_rfc2822("6 Nov 1994 03:49:37 -0500"));
+    assert_eq!(Ok (dt), Datetime::from_rfc2822("  Sun ,  6  Nov  1994  08:49:37  GMT  "));
+    assert_eq!(Ok (dt), Datetime::from_rfc2822("Sun, 6 Nov 94 00:49:37 PST"));
+    assert_eq!(Ok (dt), Datetime::from_rfc2822("Sun,\t6 Nov 1994 08:49:37 Z"));
+
+    let dt = Datetime::from_rfc2822("Sun, 6 Nov 1994 08:49 +0000").unwrap();
+    assert_eq!(NOV_06_1994_08_49_37 - 37, dt.secs);
+
+    let dt = Datetime::from_rfc2822("1 Jan 24 00:00:00 +0000").unwrap();
+    assert_eq!("Mon, 01 Jan 2024 00:00:00 GMT", dt.for_header());
+
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37"));
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc2822("Sun 06 Nov 1994 08:49:37 +0000"));
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37 CET"));
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37 +00:00"));
+    assert_eq!(Err(ParseError::Range),   Datetime::from_rfc2822("Sun, 31 Nov 1994 08:49:37 +0000"));
+    assert_eq!(Err(ParseError::Weekday), Datetime::from_rfc2822("Mon, 06 Nov 1994 08:49:37 +0000"));
+    assert_eq!(Err(ParseError::Range),   Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37 +2400"));
+    assert_eq!(Err(ParseError::Range),   Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37 +9959"));
+    assert_eq!(Ok (NOV_06_1994_08_49_37 - 86_340), Datetime::from_rfc2822("Sun, 06 Nov 1994 08:49:37 +2359").map(|dt| dt.secs));
+
+    // beyond 9999 once the offset is applied
+    assert_eq!(Ok (MAX_SECS),            Datetime::from_rfc2822("Fri, 31 Dec 9999 22:59:59 -0100").map(|dt| dt.secs));
+    assert_eq!(Err(ParseError::Range),   Datetime::from_rfc2822("Fri, 31 Dec 9999 23:00:00 -0100"));
+  }
+
+  #[test]
+  fn parse_rfc5322() {
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::from_rfc5322("Sun, 6 Nov 1994 10:49:37 +0200"));
+    assert_eq!(Ok (dt), Datetime::from_rfc5322("Sun, 6 Nov 1994 10:49:37 +0200 (CEST)"));
+    assert_eq!(Ok (dt), Datetime::from_rfc5322("(sent) Sun(day), 6 Nov (a (nested) comment) 1994 08:49:37 GMT"));
+    assert_eq!(Ok (dt), Datetime::from_rfc5322("Sun, 6 Nov 1994\r\n 08:49:37 +0000 (with \\) and \\( escaped)"));
+    assert_eq!(Ok (dt), Datetime::from_rfc5322("from a.example by b.example (envelope-from <x@a.example>);\r\n\tSun, 6 Nov 1994 10:49:37 +0200 (CEST)"));
+    assert_eq!(Ok (dt), Datetime::from_rfc5322("by b.example (8.8.5; id 1);  Sun, 6 Nov 1994 08:49:37 GMT"));
+
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc5322("Sun, 6 Nov 1994 08:49:37 +0000 (unclosed"));
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc5322("Sun, 6 Nov 1994 08:49:37 +0000 unopened)"));
+    assert_eq!(Err(ParseError::Format),  Datetime::from_rfc5322("from a.example; (no date)"));
+    assert_eq!(Err(ParseError::Weekday), Datetime::from_rfc5322("from a.example; Mon, 6 Nov 1994 08:49:37 GMT"));
+  }
+
+  #[test]
+  fn parse_format() {
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::parse_with("%Y%m%d-%H%M%S",               "19941106-084937"));
+    assert_eq!(Ok (dt), Datetime::parse_with("%a, %d %b %Y %H:%M:%S GMT",   "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Ok (dt), Datetime::parse_with("%A %e %B %y, %H.%M.%S %z",    "Sunday  6 November 94, 10.49.37 +0200"));
+    assert_eq!(Ok (dt), Datetime::parse_with("[%d/%m/%Y %H:%M:%S %Z]",      "[06/11/1994 00:49:37 PST]"));
+    assert_eq!(Ok (dt), Datetime::parse_with("%Y-%m-%dT%H:%M:%S 100%%",     "1994-11-06T08:49:37 100%"));
+
+    // fields absent taken from 01 Jan 1970 00:00:00
+    assert_eq!("Sun, 06 Nov 1994 00:00:00 GMT", Datetime::parse_with("%Y-%m-%d", "1994-11-06").unwrap().for_header());
+    assert_eq!("Thu, 01 Jan 1970 08:49:00 GMT", Datetime::parse_with("%H:%M",    "08:49").unwrap().for_header());
+
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_with("%Y%m%d",    "1994116"));
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_with("%Y%m%d",    "19941106 "));
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_with("%Y%m%d%Q",  "19941106Q"));
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_with("%Y%",       "1994%"));
+    assert_eq!(Err(ParseError::Range),      Datetime::parse_with("%Y%m%d",    "19941306"));
+    assert_eq!(Err(ParseError::Range),      Datetime::parse_with("%Y%m%d",    "19941131"));
+    assert_eq!(Err(ParseError::Weekday),    Datetime::parse_with("%a %Y%m%d", "Mon 19941106"));
+    assert_eq!(Err(ParseError::Year(1969)), Datetime::parse_with("%Y%m%d",    "19691231"));
+  }
+
+  #[test]
+  fn parse_date() {
+
+    use crate::date::{Date, test::{JAN_01_1970_00_00_00, FEB_29_1972_23_59_59, DEC_31_2024_23_59_59}};
+
+    assert_eq!(Ok (JAN_01_1970_00_00_00), "01 Jan 1970".parse());
+    assert_eq!(Ok (JAN_01_1970_00_00_00), "Thu, 01 Jan 1970".parse());
+    assert_eq!(Ok (FEB_29_1972_23_59_59), "29 Feb 1972".parse());
+    assert_eq!(Ok (DEC_31_2024_23_59_59), "Tue, 31 Dec 2024".parse());
+
+    assert_eq!(Err::<Date, _>(ParseError::Format),  "1 Jan 1970".parse());
+    assert_eq!(Err::<Date, _>(ParseError::Format),  "01 Jan 1970 ".parse());
+    assert_eq!(Err::<Date, _>(ParseError::Range),   "29 Feb 1970".parse());
+    assert_eq!(Err::<Date, _>(ParseError::Weekday), "Fri, 01 Jan 1970".parse());
+  }
+
+  #[test]
+  fn parse_time() {
+
+    use crate::time::Time;
+
+    assert_eq!(Ok (Time::default()),   "00:00:00".parse());
+    assert_eq!(Ok (Time::from(86_399)), "23:59:59".parse());
+    assert_eq!(Ok (Time::from(31_777)), "08:49:37".parse());
+
+    assert_eq!(Err::<Time, _>(ParseError::Format), "8:49:37".parse());
+    assert_eq!(Err::<Time, _>(ParseError::Format), "08:49".parse());
+    assert_eq!(Err::<Time, _>(ParseError::Range),  "24:00:00".parse());
+    assert_eq!(Err::<Time, _>(ParseError::Range),  "23:59:60".parse());
+  }
+
+  #[test]
+  fn parse_options_years() {
+
+    let options = ParseOptions::default()
+      .years(1990..=2100);
+
+    assert!(Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 GMT", &options).is_ok());
+    assert_eq!(Err(ParseError::Year(1970)), Datetime::parse_with_options("Thu, 01 Jan 1970 00:00:00 GMT", &options));
+    assert_eq!(Err(ParseError::Year(2101)), Datetime::parse_with_options("Sat, 01 Jan 2101 00:00:00 GMT", &options));
+
+    let options = ParseOptions::default()
+      .years(0..=9999);
+
+    assert_eq!(Err(ParseError::Year(1969)), Datetime::parse_with_options("Wed, 31 Dec 1969 23:59:59 GMT", &options));
+
+    // checked in GMT, once any offset is applied
+    let options = ParseOptions::default()
+      .years(1990..=2100)
+      .lenient(true);
+
+    assert_eq!(Ok (1990), Datetime::parse_with_options("Sun, 31 Dec 1989 23:30:00 -0100", &options).map(|dt| dt.date.y.0));
+    assert_eq!(Err(ParseError::Year(1989)), Datetime::parse_with_options("Mon, 01 Jan 1990 00:30:00 +0100", &options));
+  }
+
+  #[test]
+  fn parse_options_lenient() {
+
+    let options = ParseOptions::default()
+      .lenient(true);
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 GMT",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 +0000",  &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 10:49:37 +0200",  &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 03:49:37 -0500",  &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sunday, 06-Nov-94 14:19:37 +0530", &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun Nov  6 08:49:37 1994",         &options));
+
+    // weekday checked against the local date
+    let dt = Datetime::parse_with_options("Sat, 05 Nov 1994 22:49:37 -1000", &options).unwrap();
+    assert_eq!(NOV_06_1994_08_49_37, dt.secs);
+
+    assert_eq!(Err(ParseError::Format), Datetime::parse("Sun, 06 Nov 1994 10:49:37 +0200"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun, 06 Nov 1994 10:49:37 0200",  &options));
+    assert_eq!(Err(ParseError::Range),  Datetime::parse_with_options("Sun, 06 Nov 1994 10:49:37 +0260", &options));
+    assert_eq!(Err(ParseError::Range),  Datetime::parse_with_options("Sun, 06 Nov 1994 10:49:37 +2400", &options));
+    assert_eq!(Err(ParseError::Range),  Datetime::parse_with_options("Thu, 01 Jan 1970 00:00:00 +0100", &options));
+    assert_eq!(Err(ParseError::Range),  Datetime::parse_with_options("Fri, 31 Dec 9999 23:00:00 -0100", &options));
+  }
+
+  #[test]
+  fn parse_options_lenient_epoch() {
+
+    let options = ParseOptions::default()
+      .lenient(true);
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt),                  Datetime::parse_with_options("784111777",  &options));
+    assert_eq!(Ok (dt),                  Datetime::parse_with_options("@784111777", &options));
+    assert_eq!(Ok (Datetime::default()), Datetime::parse_with_options("0",          &options));
+    assert_eq!(Ok (dt),                  Datetime::parse_lenient("@784111777"));
+
+    let dt = Datetime::parse_lenient("253402300799").unwrap();
+    assert_eq!("Fri, 31 Dec 9999 23:59:59 GMT", dt.for_header());
+
+    assert_eq!(Err(ParseError::Format),     Datetime::parse("784111777"));
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_lenient("@"));
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_lenient("-1"));
+    assert_eq!(Err(ParseError::Format),     Datetime::parse_lenient("@@1"));
+    assert_eq!(Err(ParseError::Range),      Datetime::parse_lenient("253402300800"));
+    assert_eq!(Err(ParseError::Range),      Datetime::parse_lenient("99999999999999999999"));
+
+    let options = ParseOptions::default()
+      .years(1990..=2100)
+      .lenient(true);
+
+    assert_eq!(Err(ParseError::Year(1970)), Datetime::parse_with_options("0", &options));
+    assert_eq!(Err(ParseError::Range),      Datetime::parse_with_options("4133980800", &options));
+  }
+
+  #[test]
+  fn parse_options_lenient_case() {
+
+    let options = ParseOptions::default()
+      .lenient(true);
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::parse_with_options("sun, 06 nov 1994 08:49:37 gmt",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("SUN, 06 NOV 1994 08:49:37 Gmt",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("SUNDAY, 06-nOV-94 08:49:37 GMT",   &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("sun nov  6 08:49:37 1994",         &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 03:49:37 est",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 z",      &options));
+
+    assert_eq!(Err(ParseError::Format), Datetime::parse("sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse("Sun, 06 NOV 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse("Sun, 06 Nov 1994 08:49:37 gmt"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse("SUNDAY, 06-Nov-94 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 j", &options));
+  }
+
+  #[test]
+  fn parse_options_lenient_days() {
+
+    let options = ParseOptions::default()
+      .lenient(true);
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 6 Nov 1994 08:49:37 GMT",     &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun,  6 Nov 1994 08:49:37 GMT",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sunday, 6-Nov-94 08:49:37 GMT",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun Nov 6 08:49:37 1994",          &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun Nov  6 08:49:37 1994",         &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun Nov 06 08:49:37 1994",         &options));
+
+    let dt = Datetime::parse_with_options("Sat, 26 Nov 1994 08:49:37 GMT", &options).unwrap();
+    assert_eq!("Sat, 26 Nov 1994 08:49:37 GMT", dt.for_header());
+
+    assert_eq!(Err(ParseError::Format), Datetime::parse("Sun Nov 6 08:49:37 1994"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse("Sun,  6 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun,   6 Nov 1994 08:49:37 GMT", &options));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun, 006 Nov 1994 08:49:37 GMT", &options));
+  }
+
+  #[test]
+  fn parse_options_lenient_zones() {
+
+    let options = ParseOptions::default()
+      .lenient(true);
+
+    let dt = Datetime::default().set(NOV_06_1994_08_49_37);
+
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 UT",     &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 UTC",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 03:49:37 EST",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 04:49:37 EDT",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 02:49:37 CST",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 03:49:37 CDT",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 01:49:37 MST",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 02:49:37 MDT",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 00:49:37 PST",    &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sunday, 06-Nov-94 01:49:37 PDT",   &options));
+
+    // military zones as GMT
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 Z",      &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 A",      &options));
+    assert_eq!(Ok (dt), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 Y",      &options));
+
+    assert_eq!(Err(ParseError::Format), Datetime::parse("Sun, 06 Nov 1994 03:49:37 EST"));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 J",   &options));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 CET", &options));
+    assert_eq!(Err(ParseError::Format), Datetime::parse_with_options("Sun, 06 Nov 1994 08:49:37 ",    &options));
+  }
+}
+
+#[cfg(kani)]
+mod verification {
+
+  use super::{parse, parse_time, ParseOptions};
+  use crate::datetime::{IMF_FIXDATE_LEN, MAX_SECS};
+
+  use std::str;
+
+  // any ASCII input of the given length
+  fn ascii<const N: usize>() -> [u8; N] {
+    let bytes: [u8; N] = kani::any();
+    kani::assume(bytes.iter().all(u8::is_ascii));
+    bytes
+  }
+
+  #[kani::proof]
+  #[kani::unwind(31)]
+  fn parse_imf_fixdate() {
+
+    let bytes = ascii::<IMF_FIXDATE_LEN>();
+    let s = str::from_utf8(&bytes).unwrap();
+
+    // only the IMF-fixdate is of this length
+    if let Ok (dt) = parse(s, &ParseOptions::default()) {
+      assert!(dt.secs <= MAX_SECS);
+      assert_eq!(bytes, dt.for_header_bytes());
+    }
+  }
+
+  #[kani::proof]
+  #[kani::unwind(26)]
+  fn parse_asctime() {
+
+    let bytes = ascii::<24>();
+    let s = str::from_utf8(&bytes).unwrap();
+
+    if let Ok (dt) = parse(s, &ParseOptions::default()) {
+      assert!(dt.secs <= MAX_SECS);
+    }
+  }
+
+  #[kani::proof]
+  #[kani::unwind(10)]
+  fn parse_time_any() {
+
+    let bytes = ascii::<8>();
+    let s = str::from_utf8(&bytes).unwrap();
+
+    if let Ok (time) = parse_time(s) {
+      assert_eq!(s.as_bytes(), time.for_header().as_bytes());
+    }
+  }
+}
@@ -0,0 +1,180 @@
+//! # Queue
+//!
+//! A queue of keys ordered by expiry datetime.
+
+use crate::datetime::Datetime;
+
+use std::collections::BinaryHeap;
+use std::cmp::Ordering;
+
+/// Stores keys with their expiry datetimes in a binary
+/// heap, providing the earliest expiry (`next_deadline`)
+/// and removal of the keys expired at a given datetime
+/// (`pop_expired`), e.g. for cache eviction. A key can be
+/// inserted more than once, with each entry expiring
+/// independently.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, ExpiryQueue};
+///
+/// let mut queue = ExpiryQueue::new();
+///
+/// queue.insert("a", Datetime::from(60));
+/// queue.insert("b", Datetime::from(30));
+///
+/// assert_eq!(Some (Datetime::from(30)), queue.next_deadline());
+///
+/// let expired: Vec<&str> = queue
+///   .pop_expired(&Datetime::from(45))
+///   .collect();
+///
+/// assert_eq!(vec!["b"], expired);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpiryQueue<K> {
+  heap: BinaryHeap<Entry<K>>
+}
+
+impl<K> Default for ExpiryQueue<K> {
+
+  fn default() -> Self {
+    Self { heap: BinaryHeap::new() }
+  }
+}
+
+impl<K> ExpiryQueue<K> {
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn insert(&mut self, key: K, expiry: Datetime) {
+    self.heap.push(Entry { key, expiry });
+  }
+
+  pub fn len(&self) -> usize {
+    self.heap.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+
+  pub fn next_deadline(&self) -> Option<Datetime> {
+    self.heap.peek().map(|entry| entry.expiry)
+  }
+
+  /// Removes the keys with an expiry no later than `now`,
+  /// earliest first, as the iterator returned is advanced.
+  pub fn pop_expired<'a>(&'a mut self, now: &Datetime) -> PopExpired<'a, K> {
+    PopExpired { queue: self, now: *now }
+  }
+}
+
+// PopExpired
+
+/// Iterates over the keys expired, removing each from the
+/// queue in turn.
+#[derive(Debug)]
+pub struct PopExpired<'a, K> {
+  queue: &'a mut ExpiryQueue<K>,
+    now: Datetime
+}
+
+impl<K> Iterator for PopExpired<'_, K> {
+  type Item = K;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self.queue.next_deadline() {
+      Some (expiry) if expiry.secs <= self.now.secs => self.queue.heap.pop().map(|entry| entry.key),
+      _                                             => None
+    }
+  }
+}
+
+// Entry
+
+#[derive(Clone, Debug)]
+struct Entry<K> {
+     key: K,
+  expiry: Datetime
+}
+
+impl<K> PartialEq for Entry<K> {
+
+  fn eq(&self, other: &Self) -> bool {
+    self.expiry.secs == other.expiry.secs
+  }
+}
+
+impl<K> Eq for Entry<K> {}
+
+impl<K> PartialOrd for Entry<K> {
+
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some (self.cmp(other))
+  }
+}
+
+impl<K> Ord for Entry<K> {
+
+  // reversed for earliest expiry first
+  fn cmp(&self, other: &Self) -> Ordering {
+    other.expiry.secs.cmp(&self.expiry.secs)
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::ExpiryQueue;
+  use crate::datetime::Datetime;
+
+  #[test]
+  fn queue_next_deadline() {
+
+    let mut queue = ExpiryQueue::new();
+
+    assert_eq!(None, queue.next_deadline());
+
+    queue.insert(1, Datetime::from(300));
+    queue.insert(2, Datetime::from(100));
+    queue.insert(3, Datetime::from(200));
+
+    assert_eq!(Some (Datetime::from(100)), queue.next_deadline());
+    assert_eq!(3, queue.len());
+  }
+
+  #[test]
+  fn queue_pop_expired() {
+
+    let mut queue = ExpiryQueue::new();
+
+    queue.insert(1, Datetime::from(300));
+    queue.insert(2, Datetime::from(100));
+    queue.insert(3, Datetime::from(200));
+    queue.insert(2, Datetime::from(250));
+
+    assert_eq!(Vec::<u8>::new(), queue.pop_expired(&Datetime::from( 99)).collect::<Vec<_>>());
+    assert_eq!(vec![2],          queue.pop_expired(&Datetime::from(100)).collect::<Vec<_>>());
+    assert_eq!(vec![3, 2],       queue.pop_expired(&Datetime::from(299)).collect::<Vec<_>>());
+    assert_eq!(Some (Datetime::from(300)), queue.next_deadline());
+
+    assert_eq!(vec![1],          queue.pop_expired(&Datetime::from(999)).collect::<Vec<_>>());
+    assert!(queue.is_empty());
+  }
+
+  #[test]
+  fn queue_pop_expired_partial() {
+
+    let mut queue = ExpiryQueue::new();
+
+    queue.insert('a', Datetime::from(100));
+    queue.insert('b', Datetime::from(200));
+
+    assert_eq!(Some ('a'), queue.pop_expired(&Datetime::from(300)).next());
+    assert_eq!(1, queue.len());
+  }
+}
@@ -0,0 +1,143 @@
+//! # Scan
+//!
+//! Extraction of HTTP-dates from larger text, e.g. a raw
+//! header block or an HTML meta section.
+
+use crate::datetime::Datetime;
+use crate::date::Weekday;
+use crate::parse::{self, ParseOptions};
+
+use std::ops::Range;
+use std::str;
+
+/// Scans the bytes for HTTP-dates in any of the three
+/// formats recipients are required to accept, providing an
+/// iterator over the byte range and datetime of each, e.g.
+/// for crawlers and diagnostic tools. Only whole dates are
+/// matched, i.e. those not directly preceded or followed by
+/// a letter or digit, with any candidate failing to parse,
+/// e.g. for a mismatched weekday, skipped.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::extract_dates;
+///
+/// let block = b"HTTP/1.1 200 OK\r\nDate: Sun, 06 Nov 1994 08:49:37 GMT\r\n";
+///
+/// let (span, dt) = extract_dates(block).next().unwrap();
+///
+/// assert_eq!(23..52, span);
+/// assert_eq!(784_111_777, dt.secs());
+/// ```
+pub fn extract_dates(bytes: &[u8]) -> ExtractDates<'_> {
+  ExtractDates { bytes, pos: 0 }
+}
+
+// ExtractDates
+
+/// Iterates over the HTTP-dates found by `extract_dates`,
+/// in order of position.
+#[derive(Clone, Debug)]
+pub struct ExtractDates<'a> {
+  bytes: &'a [u8],
+  pos: usize
+}
+
+impl Iterator for ExtractDates<'_> {
+  type Item = (Range<usize>, Datetime);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.pos < self.bytes.len() {
+      let start = self.pos;
+      match self.candidate(start) {
+        Some ((end, dt)) => { self.pos = end; return Some ((start..end, dt)) },
+        None             => self.pos += 1
+      }
+    }
+    None
+  }
+}
+
+impl ExtractDates<'_> {
+
+  // the end and datetime of any date starting at the
+  // position, with the length per the format indicated by
+  // the byte after the weekday, as for StreamParser
+  fn candidate(&self, start: usize) -> Option<(usize, Datetime)> {
+    let is_word = |i: usize| self.bytes.get(i).is_some_and(|b| b.is_ascii_alphanumeric());
+    if start > 0 && is_word(start - 1) { return None };
+    let rest = &self.bytes[start..];
+    Weekday::from_abbrev(rest.get(..3)?.try_into().ok()?)?;
+    let len = match rest.get(3)? {
+      b',' => 29,
+      b' ' => 24,
+      _    => rest.iter().take(10).position(|&b| b == b',')? + 24
+    };
+    let s = str::from_utf8(rest.get(..len)?).ok()?;
+    let dt = parse::parse(s, &ParseOptions::default()).ok()?;
+    let end = start + len;
+    match is_word(end) {
+      true  => None,
+      false => Some ((end, dt))
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::extract_dates;
+  use crate::datetime::Datetime;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn scan_extract_dates() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+    let text = concat!(
+      "Date: Sun, 06 Nov 1994 08:49:37 GMT\r\n",
+      "Expires: Sunday, 06-Nov-94 08:49:37 GMT\r\n",
+      "X-Legacy: Sun Nov  6 08:49:37 1994\r\n",
+      "<meta http-equiv=\"last-modified\" content=\"Wed, 09 Nov 1994 08:49:37 GMT\">"
+    );
+
+    let found: Vec<_> = extract_dates(text.as_bytes()).collect();
+
+    assert_eq!(4, found.len());
+    assert_eq!((  6..35,  dt), found[0]);
+    assert_eq!(( 46..76,  dt), found[1]);
+    assert_eq!(( 88..112, dt), found[2]);
+    assert_eq!("Wed, 09 Nov 1994 08:49:37 GMT", &text[found[3].0.clone()]);
+    assert_eq!(dt.set(NOV_06_1994_08_49_37 + 3 * 86_400), found[3].1);
+
+    // adjacent, at the bounds
+    let text = "Sun, 06 Nov 1994 08:49:37 GMT,Sun Nov  6 08:49:37 1994";
+    assert_eq!(vec![(0..29, dt), (30..54, dt)], extract_dates(text.as_bytes()).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn scan_extract_dates_skipped() {
+
+    let texts = [
+      "",
+      "Sun",
+      "Sunday",
+      "Mon, 06 Nov 1994 08:49:37 GMT",
+      "Sun, 06 Nov 1994 08:49:37 GMTX",
+      "XSun, 06 Nov 1994 08:49:37 GMT",
+      "Sun, 06 Nov 1994 08:49:37",
+      "Sun, 06 Nov 1994 08:49:37 UTC",
+      "Sunday 06 November 1994 08:49:37 GMT"
+    ];
+
+    for text in texts {
+      assert_eq!(None, extract_dates(text.as_bytes()).next());
+    }
+
+    // a valid date following a candidate skipped
+    let text = "Mon, Sun, 06 Nov 1994 08:49:37 GMT";
+    assert_eq!(Some (5..34), extract_dates(text.as_bytes()).next().map(|(span, _)| span));
+  }
+}
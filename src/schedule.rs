@@ -0,0 +1,227 @@
+//! # Schedule
+//!
+//! Recurring times in GMT, e.g. of maintenance windows or
+//! refreshes, from which to derive Expires and Retry-After
+//! values.
+
+use crate::datetime::{Datetime, ComponentError, DayOverflow};
+use crate::date::{Weekday, D_AS_S};
+use crate::time::{M_AS_S, H_AS_S, H_AS_M, D_AS_H};
+
+const W_AS_S: u64 = D_AS_S * 7;
+
+/// Stores a recurring time, either hourly at a given
+/// minute, daily at a given hour and minute or weekly on
+/// a given weekday at a given hour and minute, with the
+/// next occurrence strictly after a datetime provided by
+/// `next_after`. Each constructor returns
+/// `ComponentError::Range` for an hour or minute out of
+/// range.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, Schedule, Weekday};
+///
+/// let dt = Datetime::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+///
+/// let hourly = Schedule::hourly(30).unwrap();
+/// let weekly = Schedule::weekly(Weekday::Tue, 2, 0).unwrap();
+///
+/// assert_eq!("Sun, 06 Nov 1994 09:30:00 GMT", hourly.next_after(&dt).for_header());
+/// assert_eq!("Tue, 08 Nov 1994 02:00:00 GMT", weekly.next_after(&dt).for_header());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Schedule {
+  period: u64,
+  offset: u64
+}
+
+impl Schedule {
+
+  pub fn hourly(m: u8) -> Result<Self, ComponentError> {
+    Self::within(H_AS_S, 0, 0, m)
+  }
+
+  pub fn daily(h: u8, m: u8) -> Result<Self, ComponentError> {
+    Self::within(D_AS_S, 0, h, m)
+  }
+
+  pub fn weekly(wd: Weekday, h: u8, m: u8) -> Result<Self, ComponentError> {
+    // days after Thursday, the weekday of the epoch
+    let days = (0..7)
+      .find(|days| Weekday::from_days_since_epoch(*days) == wd)
+      .unwrap_or_default();
+    Self::within(W_AS_S, days, h, m)
+  }
+
+  pub fn next_after(&self, dt: &Datetime) -> Datetime {
+    let Self { period, offset } = *self;
+    let n = match dt.secs.checked_sub(offset) {
+      Some (since) => since / period + 1,
+      None         => 0
+    };
+    dt.set(n * period + offset)
+  }
+
+  fn within(period: u64, days: u64, h: u8, m: u8) -> Result<Self, ComponentError> {
+    if h as u64 >= D_AS_H || m as u64 >= H_AS_M {
+      return Err(ComponentError::Range)
+    }
+    let offset = days * D_AS_S + h as u64 * H_AS_S + m as u64 * M_AS_S;
+    Ok (Self { period, offset })
+  }
+}
+
+// Recurrence
+
+/// Yields the datetimes recurring monthly or yearly from an
+/// anchor, starting with the anchor itself, e.g. midnight
+/// on the first of every month, for schedules without a
+/// fixed period, each computed from the anchor with any day
+/// absent from a month handled per the `DayOverflow` policy,
+/// and ending after the year 9999.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, Recurrence, DayOverflow};
+///
+/// let anchor = Datetime::parse("Tue, 31 Jan 1995 00:00:00 GMT").unwrap();
+///
+/// let mut monthly = Recurrence::monthly(&anchor, DayOverflow::Clamp)
+///   .skip(1);
+///
+/// assert_eq!("Tue, 28 Feb 1995 00:00:00 GMT", monthly.next().unwrap().for_header());
+/// assert_eq!("Fri, 31 Mar 1995 00:00:00 GMT", monthly.next().unwrap().for_header());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Recurrence {
+    anchor: Datetime,
+    months: u64,
+  overflow: DayOverflow,
+         n: u64
+}
+
+impl Recurrence {
+
+  pub fn monthly(anchor: &Datetime, overflow: DayOverflow) -> Self {
+    Self { anchor: *anchor, months: 1, overflow, n: 0 }
+  }
+
+  pub fn yearly(anchor: &Datetime, overflow: DayOverflow) -> Self {
+    Self { anchor: *anchor, months: 12, overflow, n: 0 }
+  }
+}
+
+impl Iterator for Recurrence {
+  type Item = Datetime;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let months = self.n.checked_mul(self.months)?;
+    let next = self.anchor.add_months(months, self.overflow).ok()?;
+    self.n += 1;
+    Some (next)
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{Schedule, Recurrence};
+  use crate::datetime::{Datetime, ComponentError, DayOverflow};
+  use crate::date::Weekday;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  fn next(schedule: Result<Schedule, ComponentError>, ts: &str) -> String {
+    schedule.unwrap().next_after(&Datetime::parse(ts).unwrap()).for_header()
+  }
+
+  #[test]
+  fn schedule_hourly() {
+
+    assert_eq!("Sun, 06 Nov 1994 08:50:00 GMT", next(Schedule::hourly(50), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Sun, 06 Nov 1994 09:49:00 GMT", next(Schedule::hourly(49), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Sun, 06 Nov 1994 09:49:00 GMT", next(Schedule::hourly(49), "Sun, 06 Nov 1994 08:49:00 GMT"));
+    assert_eq!("Mon, 07 Nov 1994 00:00:00 GMT", next(Schedule::hourly( 0), "Sun, 06 Nov 1994 23:59:59 GMT"));
+    assert_eq!("Thu, 01 Jan 1970 00:30:00 GMT", next(Schedule::hourly(30), "Thu, 01 Jan 1970 00:00:00 GMT"));
+  }
+
+  #[test]
+  fn schedule_daily() {
+
+    assert_eq!("Sun, 06 Nov 1994 09:00:00 GMT", next(Schedule::daily( 9,  0), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Mon, 07 Nov 1994 08:49:00 GMT", next(Schedule::daily( 8, 49), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Fri, 01 Mar 1996 00:00:00 GMT", next(Schedule::daily( 0,  0), "Thu, 29 Feb 1996 00:00:00 GMT"));
+    assert_eq!("Thu, 01 Jan 1970 23:59:00 GMT", next(Schedule::daily(23, 59), "Thu, 01 Jan 1970 00:00:00 GMT"));
+  }
+
+  #[test]
+  fn schedule_weekly() {
+
+    assert_eq!("Sun, 13 Nov 1994 08:49:00 GMT", next(Schedule::weekly(Weekday::Sun,  8, 49), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Sun, 06 Nov 1994 08:50:00 GMT", next(Schedule::weekly(Weekday::Sun,  8, 50), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Mon, 07 Nov 1994 00:00:00 GMT", next(Schedule::weekly(Weekday::Mon,  0,  0), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Sat, 12 Nov 1994 23:59:00 GMT", next(Schedule::weekly(Weekday::Sat, 23, 59), "Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!("Wed, 07 Jan 1970 12:00:00 GMT", next(Schedule::weekly(Weekday::Wed, 12,  0), "Thu, 01 Jan 1970 00:00:00 GMT"));
+
+    // as for advance_to_next
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+    for wd in Weekday::iter() {
+      let expected = dt.advance_to_next(wd, &"03:15:00".parse().unwrap()).unwrap();
+      assert_eq!(expected, Schedule::weekly(wd, 3, 15).unwrap().next_after(&dt));
+    }
+  }
+
+  #[test]
+  fn schedule_invalid() {
+
+    assert_eq!(Err(ComponentError::Range), Schedule::hourly(60));
+    assert_eq!(Err(ComponentError::Range), Schedule::daily(24, 0));
+    assert_eq!(Err(ComponentError::Range), Schedule::weekly(Weekday::Mon, 0, 60));
+  }
+
+  #[test]
+  fn recurrence_monthly() {
+
+    let anchor = Datetime::parse("Sat, 31 Jan 1970 00:00:00 GMT").unwrap();
+
+    let clamped: Vec<_> = Recurrence::monthly(&anchor, DayOverflow::Clamp).take(4).map(|dt| dt.for_header()).collect();
+    assert_eq!(vec![
+      "Sat, 31 Jan 1970 00:00:00 GMT", "Sat, 28 Feb 1970 00:00:00 GMT",
+      "Tue, 31 Mar 1970 00:00:00 GMT", "Thu, 30 Apr 1970 00:00:00 GMT"
+    ], clamped);
+
+    let rolled: Vec<_> = Recurrence::monthly(&anchor, DayOverflow::Roll).take(4).map(|dt| dt.for_header()).collect();
+    assert_eq!(vec![
+      "Sat, 31 Jan 1970 00:00:00 GMT", "Tue, 03 Mar 1970 00:00:00 GMT",
+      "Tue, 31 Mar 1970 00:00:00 GMT", "Fri, 01 May 1970 00:00:00 GMT"
+    ], rolled);
+
+    // ending after 9999
+    let anchor = Datetime::parse("Mon, 01 Nov 9999 00:00:00 GMT").unwrap();
+    assert_eq!(2, Recurrence::monthly(&anchor, DayOverflow::Clamp).count());
+  }
+
+  #[test]
+  fn recurrence_yearly() {
+
+    let anchor = Datetime::parse("Tue, 29 Feb 1972 08:49:37 GMT").unwrap();
+
+    let clamped: Vec<_> = Recurrence::yearly(&anchor, DayOverflow::Clamp).take(5).map(|dt| dt.for_header()).collect();
+    assert_eq!(vec![
+      "Tue, 29 Feb 1972 08:49:37 GMT", "Wed, 28 Feb 1973 08:49:37 GMT", "Thu, 28 Feb 1974 08:49:37 GMT",
+      "Fri, 28 Feb 1975 08:49:37 GMT", "Sun, 29 Feb 1976 08:49:37 GMT"
+    ], clamped);
+
+    let rolled = Recurrence::yearly(&anchor, DayOverflow::Roll).nth(1).unwrap();
+    assert_eq!("Thu, 01 Mar 1973 08:49:37 GMT", rolled.for_header());
+
+    let anchor = Datetime::parse("Fri, 31 Dec 9999 23:59:59 GMT").unwrap();
+    let mut yearly = Recurrence::yearly(&anchor, DayOverflow::Clamp);
+    assert_eq!(Some (anchor), yearly.next());
+    assert_eq!(None,          yearly.next());
+    assert_eq!(None,          yearly.next());
+  }
+}
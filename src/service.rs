@@ -0,0 +1,515 @@
+//! # Service
+//!
+//! A shared, cached datetime for HTTP clients and servers,
+//! and a shared table of Expires header timestamps.
+
+use crate::datetime::{Datetime, IMF_FIXDATE_LEN, MAX_SECS};
+
+#[cfg(not(httpdt_loom))]
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(httpdt_loom)]
+use loom::sync::{Arc, Mutex, MutexGuard};
+use std::error::Error;
+
+/// Stores a datetime and its HTTP Date header timestamp
+/// for sharing across threads, with each clone a handle
+/// to the same cache. The cache is brought up to date on
+/// access, with the datetime updated via `set` and the
+/// timestamp regenerated at most once per second.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::DateService;
+///
+/// let service = DateService::new()
+///   .unwrap();
+///
+/// let handle = service.clone();
+///
+/// let ts = handle
+///   .for_header()
+///   .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct DateService {
+  cached: Arc<Mutex<Cached>>
+}
+
+#[derive(Debug)]
+struct Cached {
+     dt: Datetime,
+  bytes: [u8; IMF_FIXDATE_LEN]
+}
+
+impl Cached {
+
+  fn update(&mut self, raw: u64) {
+    if raw == self.dt.secs { return };
+    self.dt = match raw > self.dt.secs {
+      true  => self.dt.set(raw),
+      false => Datetime::default().set(raw)
+    };
+    self.bytes = self.dt.for_header_bytes();
+  }
+}
+
+impl DateService {
+
+  pub fn new() -> Result<Self, Box<dyn Error>> {
+    let dt = Datetime::new()?;
+    let bytes = dt.for_header_bytes();
+    let cached = Arc::new(Mutex::new(Cached { dt, bytes }));
+    Ok (Self { cached })
+  }
+
+  pub fn datetime(&self) -> Result<Datetime, Box<dyn Error>> {
+    let cached = self.current()?;
+    Ok (cached.dt)
+  }
+
+  pub fn for_header_bytes(&self) -> Result<[u8; IMF_FIXDATE_LEN], Box<dyn Error>> {
+    let cached = self.current()?;
+    Ok (cached.bytes)
+  }
+
+  pub fn for_header(&self) -> Result<String, Box<dyn Error>> {
+    let bytes = self.for_header_bytes()?;
+    Ok (String::from_utf8(bytes.to_vec())?)
+  }
+
+  /// Provides a `Subscription` to changes to the shared
+  /// timestamp, e.g. for rebuilding a pre-built response
+  /// prefix exactly once per second.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::DateService;
+  ///
+  /// let service = DateService::new()
+  ///   .unwrap();
+  ///
+  /// let mut sub = service.subscribe();
+  ///
+  /// // the current timestamp, on the first call
+  /// let bytes = sub
+  ///   .changed()
+  ///   .unwrap()
+  ///   .unwrap();
+  /// ```
+  pub fn subscribe(&self) -> Subscription {
+    Subscription { service: self.clone(), seen: None }
+  }
+
+  fn current(&self) -> Result<MutexGuard<'_, Cached>, Box<dyn Error>> {
+    self.current_with(Datetime::raw)
+  }
+
+  // with the clock passed in, e.g. one driven by the test
+  fn current_with(&self, clock: impl Fn() -> Result<u64, Box<dyn Error>>) -> Result<MutexGuard<'_, Cached>, Box<dyn Error>> {
+    let raw = clock()?;
+    let mut cached = self.cached
+      .lock()
+      .unwrap_or_else(|e| e.into_inner());
+    let raw = settled(raw, cached.dt.secs, clock)?;
+    cached.update(raw);
+    Ok (cached)
+  }
+}
+
+// a read behind the cache may only predate that of another
+// handle applied first, so is repeated with the lock held,
+// a read still behind then being a step of the clock back
+fn settled(raw: u64, cached: u64, reread: impl FnOnce() -> Result<u64, Box<dyn Error>>) -> Result<u64, Box<dyn Error>> {
+  match raw < cached {
+    true  => reread(),
+    false => Ok (raw)
+  }
+}
+
+// Subscription
+
+/// Watches the shared cache of a `DateService` for changes
+/// to the timestamp, with `changed` providing the new
+/// timestamp only where later than that last seen and
+/// `wait` blocking until it is, per the clock read by
+/// `Datetime::raw`, the first call to either providing the
+/// current timestamp. A step of the clock back is not
+/// provided until the clock passes the timestamp last seen.
+#[derive(Clone, Debug)]
+pub struct Subscription {
+  service: DateService,
+  seen: Option<u64>
+}
+
+impl Subscription {
+
+  pub fn changed(&mut self) -> Result<Option<[u8; IMF_FIXDATE_LEN]>, Box<dyn Error>> {
+    self.changed_with(Datetime::raw)
+  }
+
+  fn changed_with(&mut self, clock: impl Fn() -> Result<u64, Box<dyn Error>>) -> Result<Option<[u8; IMF_FIXDATE_LEN]>, Box<dyn Error>> {
+    let cached = self.service.current_with(clock)?;
+    Ok (Self::check(&mut self.seen, &cached))
+  }
+
+  pub fn wait(&mut self) -> Result<[u8; IMF_FIXDATE_LEN], Box<dyn Error>> {
+    loop {
+      let next = {
+        let cached = self.service.current()?;
+        if let Some (bytes) = Self::check(&mut self.seen, &cached) { return Ok (bytes) };
+        cached.dt.set(self.seen.unwrap_or(cached.dt.secs) + 1)
+      };
+      next.sleep_until();
+    }
+  }
+
+  fn check(seen: &mut Option<u64>, cached: &Cached) -> Option<[u8; IMF_FIXDATE_LEN]> {
+    if seen.is_some_and(|secs| cached.dt.secs <= secs) { return None };
+    *seen = Some (cached.dt.secs);
+    Some (cached.bytes)
+  }
+}
+
+// the HTTP Date header timestamp bytes as a header value,
+// for each of the middleware features, which differ in the
+// version of the http crate used
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "warp"))]
+pub(crate) fn header_value<V>(bytes: &[u8; IMF_FIXDATE_LEN]) -> V
+where
+  V: for<'a> TryFrom<&'a [u8]>
+{
+  match V::try_from(&bytes[..]) {
+    Ok (value) => value,
+    Err(_)     => unreachable!("IMF-fixdate timestamp is visible ASCII")
+  }
+}
+
+// ExpiresTable
+
+/// Stores the Expires header timestamps for a set of TTLs
+/// in seconds, e.g. 60, 300 and 3600, for sharing across
+/// threads, with each clone a handle to the same table.
+/// The table is brought up to date on access, as for
+/// `DateService`, with the timestamps regenerated at most
+/// once per second, so that each lookup copies rather than
+/// formats. A lookup for a TTL not in the set gives `None`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::ExpiresTable;
+///
+/// let table = ExpiresTable::new(&[60, 300, 3600])
+///   .unwrap();
+///
+/// let ts = table
+///   .for_header(300)
+///   .unwrap()
+///   .unwrap();
+///
+/// assert_eq!(None, table.for_header(30).unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpiresTable {
+  table: Arc<Mutex<Table>>
+}
+
+#[derive(Debug)]
+struct Table {
+     dt: Datetime,
+   ttls: Vec<u64>,
+  bytes: Vec<[u8; IMF_FIXDATE_LEN]>
+}
+
+impl Table {
+
+  fn new(dt: Datetime, ttls: &[u64]) -> Self {
+    let mut table = Self { dt, ttls: ttls.to_vec(), bytes: Vec::new() };
+    table.fill();
+    table
+  }
+
+  fn update(&mut self, raw: u64) {
+    if raw == self.dt.secs { return };
+    self.dt = match raw > self.dt.secs {
+      true  => self.dt.set(raw),
+      false => Datetime::default().set(raw)
+    };
+    self.fill();
+  }
+
+  // each TTL after the datetime, capped at the latest
+  // datetime supported
+  fn fill(&mut self) {
+    let dt = self.dt;
+    let expires = |ttl: &u64| dt.set(dt.secs.saturating_add(*ttl).min(MAX_SECS).max(dt.secs)).for_header_bytes();
+    self.bytes = self.ttls.iter().map(expires).collect();
+  }
+
+  fn get(&self, ttl: u64) -> Option<[u8; IMF_FIXDATE_LEN]> {
+    let i = self.ttls.iter().position(|t| *t == ttl)?;
+    Some (self.bytes[i])
+  }
+}
+
+impl ExpiresTable {
+
+  pub fn new(ttls: &[u64]) -> Result<Self, Box<dyn Error>> {
+    let table = Table::new(Datetime::new()?, ttls);
+    Ok (Self { table: Arc::new(Mutex::new(table)) })
+  }
+
+  pub fn for_header_bytes(&self, ttl: u64) -> Result<Option<[u8; IMF_FIXDATE_LEN]>, Box<dyn Error>> {
+    let raw = Datetime::raw()?;
+    let mut table = self.table
+      .lock()
+      .unwrap_or_else(|e| e.into_inner());
+    let raw = settled(raw, table.dt.secs, Datetime::raw)?;
+    table.update(raw);
+    Ok (table.get(ttl))
+  }
+
+  pub fn for_header(&self, ttl: u64) -> Result<Option<String>, Box<dyn Error>> {
+    let Some (bytes) = self.for_header_bytes(ttl)? else { return Ok (None) };
+    Ok (Some (String::from_utf8(bytes.to_vec())?))
+  }
+}
+
+#[cfg(all(test, not(httpdt_loom)))]
+mod test {
+
+  use super::{DateService, Subscription, Cached, ExpiresTable, Table, settled};
+  use crate::datetime::{Datetime, MAX_SECS};
+  use crate::date::test::Y_365_AS_S;
+
+  #[test]
+  fn service_datetime() {
+
+    let service = DateService::new().unwrap();
+    let raw = Datetime::raw().unwrap();
+    let dt = service.datetime().unwrap();
+
+    assert!(dt.secs == raw || dt.secs == raw + 1);
+    assert_eq!(Datetime::default().set(dt.secs), dt);
+  }
+
+  #[test]
+  fn service_for_header() {
+
+    let service = DateService::new().unwrap();
+    let ts = service.for_header().unwrap();
+    let dt = Datetime::parse(&ts).unwrap();
+
+    assert_eq!(ts.as_bytes(), &service.for_header_bytes().unwrap());
+    assert_eq!(ts, dt.for_header());
+  }
+
+  #[test]
+  fn service_clone() {
+
+    let service = DateService::new().unwrap();
+    let handle = service.clone();
+
+    assert_eq!(service.datetime().unwrap(), handle.datetime().unwrap());
+  }
+
+  #[test]
+  fn subscription_changed() {
+
+    let dt = Datetime::default();
+    let mut cached = Cached { dt, bytes: dt.for_header_bytes() };
+    let mut seen = None;
+
+    assert_eq!(Some (cached.bytes), Subscription::check(&mut seen, &cached));
+    assert_eq!(None,                Subscription::check(&mut seen, &cached));
+
+    cached.update(1);
+    assert_eq!(Some (*b"Thu, 01 Jan 1970 00:00:01 GMT"), Subscription::check(&mut seen, &cached));
+    assert_eq!(None,                                     Subscription::check(&mut seen, &cached));
+
+    // behind that last seen, then past it
+    cached.update(0);
+    assert_eq!(None,                                     Subscription::check(&mut seen, &cached));
+    cached.update(1);
+    assert_eq!(None,                                     Subscription::check(&mut seen, &cached));
+    cached.update(2);
+    assert_eq!(Some (*b"Thu, 01 Jan 1970 00:00:02 GMT"), Subscription::check(&mut seen, &cached));
+
+    // from the shared cache
+    let bytes = DateService::new().unwrap().subscribe().changed().unwrap().unwrap();
+    assert!(Datetime::parse(std::str::from_utf8(&bytes).unwrap()).is_ok());
+  }
+
+  #[test]
+  fn subscription_wait() {
+
+    let service = DateService::new().unwrap();
+    let mut sub = service.subscribe();
+
+    let first = sub.wait().unwrap();
+    let second = sub.wait().unwrap();
+    let parse = |bytes: &[u8]| Datetime::parse(std::str::from_utf8(bytes).unwrap()).unwrap();
+
+    assert!(parse(&second).secs > parse(&first).secs);
+    assert!(parse(&second).secs <= Datetime::raw().unwrap());
+  }
+
+  #[test]
+  fn cached_update() {
+
+    let dt = Datetime::default();
+    let mut cached = Cached { dt, bytes: dt.for_header_bytes() };
+
+    cached.update(Y_365_AS_S);
+    assert_eq!(*b"Fri, 01 Jan 1971 00:00:00 GMT", cached.bytes);
+
+    cached.update(Y_365_AS_S - 1);
+    assert_eq!(*b"Thu, 31 Dec 1970 23:59:59 GMT", cached.bytes);
+    assert_eq!(Datetime::default().set(Y_365_AS_S - 1), cached.dt);
+  }
+
+  #[test]
+  fn cached_update_out_of_order() {
+
+    let dt = Datetime::default();
+    let mut cached = Cached { dt, bytes: dt.for_header_bytes() };
+
+    // read by two handles as 100 and 101, the 101 applied first
+    let (first, second) = (100, 101);
+    cached.update(settled(second, cached.dt.secs, || Ok (second)).unwrap());
+    cached.update(settled(first,  cached.dt.secs, || Ok (second)).unwrap());
+    assert_eq!(*b"Thu, 01 Jan 1970 00:01:41 GMT", cached.bytes);
+
+    // a step of the clock back
+    cached.update(settled(first,  cached.dt.secs, || Ok (first)).unwrap());
+    assert_eq!(*b"Thu, 01 Jan 1970 00:01:40 GMT", cached.bytes);
+  }
+
+  #[test]
+  fn expires_table_for_header() {
+
+    let table = ExpiresTable::new(&[0, 60, 3600]).unwrap();
+    let raw = Datetime::raw().unwrap();
+
+    for ttl in [0, 60, 3600] {
+      let secs = Datetime::parse(&table.for_header(ttl).unwrap().unwrap()).unwrap().secs;
+      assert!(secs >= raw + ttl && secs <= Datetime::raw().unwrap() + ttl);
+    }
+
+    assert_eq!(None, table.for_header(300).unwrap());
+  }
+
+  #[test]
+  fn table_update() {
+
+    let mut table = Table::new(Datetime::default(), &[60, 86_400, u64::MAX]);
+
+    assert_eq!(Some (*b"Thu, 01 Jan 1970 00:01:00 GMT"), table.get(60));
+    assert_eq!(Some (*b"Fri, 02 Jan 1970 00:00:00 GMT"), table.get(86_400));
+    assert_eq!(Some (*b"Fri, 31 Dec 9999 23:59:59 GMT"), table.get(u64::MAX));
+    assert_eq!(None,                                     table.get(0));
+
+    table.update(Y_365_AS_S);
+    assert_eq!(Some (*b"Fri, 01 Jan 1971 00:01:00 GMT"), table.get(60));
+
+    table.update(Y_365_AS_S - 60);
+    assert_eq!(Some (*b"Fri, 01 Jan 1971 00:00:00 GMT"), table.get(60));
+
+    table.update(MAX_SECS);
+    assert_eq!(Some (*b"Fri, 31 Dec 9999 23:59:59 GMT"), table.get(60));
+  }
+}
+
+// run via RUSTFLAGS="--cfg httpdt_loom" cargo test --release --lib loom
+#[cfg(all(test, httpdt_loom))]
+mod loom_test {
+
+  use super::{DateService, Cached};
+  use crate::datetime::Datetime;
+
+  use loom::sync::{Arc, Mutex};
+  use loom::sync::atomic::{AtomicU64, Ordering};
+  use loom::thread;
+
+  use std::error::Error;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  // a clock advanced by the test, in place of the system
+  // clock, so that the refresh of the cache is explored
+  type Clock = Arc<AtomicU64>;
+
+  fn service(clock: &Clock) -> DateService {
+    let dt = Datetime::from(clock.load(Ordering::SeqCst));
+    let cached = Cached { dt, bytes: dt.for_header_bytes() };
+    DateService { cached: Arc::new(Mutex::new(cached)) }
+  }
+
+  fn reader(clock: &Clock) -> impl Fn() -> Result<u64, Box<dyn Error>> + '_ {
+    || Ok (clock.load(Ordering::SeqCst))
+  }
+
+  // the bytes consistent with the datetime and the datetime
+  // neither behind the clock as read before nor ahead of it
+  fn check(service: &DateService, clock: &Clock) {
+    let before = clock.load(Ordering::SeqCst);
+    let (dt, bytes) = {
+      let cached = service.current_with(reader(clock)).unwrap();
+      (cached.dt, cached.bytes)
+    };
+    let after = clock.load(Ordering::SeqCst);
+    assert_eq!(dt.for_header_bytes(), bytes);
+    assert!(dt.secs >= before && dt.secs <= after);
+  }
+
+  #[test]
+  fn loom_service_refresh_read() {
+
+    loom::model(|| {
+      let clock = Arc::new(AtomicU64::new(NOV_06_1994_08_49_37));
+      let service = service(&clock);
+      let writer = {
+        let clock = clock.clone();
+        thread::spawn(move || { clock.fetch_add(1, Ordering::SeqCst); })
+      };
+      let handle = {
+        let (service, clock) = (service.clone(), clock.clone());
+        thread::spawn(move || check(&service, &clock))
+      };
+      check(&service, &clock);
+      writer.join().unwrap();
+      handle.join().unwrap();
+      check(&service, &clock);
+      assert_eq!(NOV_06_1994_08_49_37 + 1, service.cached.lock().unwrap().dt.secs);
+    });
+  }
+
+  #[test]
+  fn loom_subscription_changed() {
+
+    loom::model(|| {
+      let clock = Arc::new(AtomicU64::new(NOV_06_1994_08_49_37));
+      let service = service(&clock);
+      let mut sub = service.subscribe();
+      let first = sub.changed_with(reader(&clock)).unwrap().unwrap();
+      let handle = {
+        let (service, clock) = (service.clone(), clock.clone());
+        thread::spawn(move || {
+          clock.fetch_add(1, Ordering::SeqCst);
+          check(&service, &clock);
+        })
+      };
+      // the later timestamp provided once only, if at all before the join
+      let during = sub.changed_with(reader(&clock)).unwrap();
+      handle.join().unwrap();
+      let after = sub.changed_with(reader(&clock)).unwrap();
+      assert!(during.is_some() != after.is_some());
+      let later = during.or(after).unwrap();
+      assert_eq!(Datetime::from(NOV_06_1994_08_49_37).for_header_bytes(), first);
+      assert_eq!(Datetime::from(NOV_06_1994_08_49_37 + 1).for_header_bytes(), later);
+      assert_eq!(None, sub.changed_with(reader(&clock)).unwrap());
+    });
+  }
+}
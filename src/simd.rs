@@ -0,0 +1,222 @@
+//! # Simd
+//!
+//! Vectorized formatting and validation of the IMF-fixdate,
+//! available with the `simd` feature, using SSE2 on x86_64
+//! and NEON on aarch64, each part of the baseline for its
+//! target, with a scalar fallback elsewhere.
+
+use crate::datetime::{Datetime, IMF_FIXDATE_LEN};
+use crate::date::Year;
+
+const LEN: usize = 32;
+
+type Block = [u8; LEN];
+
+// the IMF-fixdate with zero digits, padded to two vectors
+const TEMPLATE: Block = *b"Thu, 00 Jan 0000 00:00:00 GMT\0\0\0";
+const DIGITS: Block = mask(&[(5, 7), (12, 16), (17, 19), (20, 22), (23, 25)]);
+const NAMES: Block = mask(&[(0, 3), (8, 11)]);
+
+const fn mask(ranges: &[(usize, usize)]) -> Block {
+  let mut mask = [0; LEN];
+  let mut i = 0;
+  while i < ranges.len() {
+    let (start, end) = ranges[i];
+    let mut j = start;
+    while j < end { mask[j] = 0xFF; j += 1 };
+    i += 1;
+  }
+  mask
+}
+
+/// Outputs the timestamp as for `Datetime::for_header_bytes`,
+/// adding the digit values to the template in one vector
+/// operation per half.
+pub(crate) fn format(dt: &Datetime) -> [u8; IMF_FIXDATE_LEN] {
+
+  let Year(y) = dt.date.y;
+  let mut values = [0; LEN];
+  for (pos, n) in [(5, dt.date.d as u64), (12, y / 100 % 100), (14, y % 100), (17, dt.time.h as u64), (20, dt.time.m as u64), (23, dt.time.s as u64)] {
+    values[pos    ] = (n / 10 % 10) as u8;
+    values[pos + 1] = (n      % 10) as u8;
+  }
+
+  let block = arch::add(&TEMPLATE, &values);
+  let mut bytes = [0; IMF_FIXDATE_LEN];
+  bytes.copy_from_slice(&block[..IMF_FIXDATE_LEN]);
+  bytes[0.. 3].copy_from_slice(dt.date.wd.abbrev().as_bytes());
+  bytes[8..11].copy_from_slice(dt.date.m.abbrev().as_bytes());
+  bytes
+}
+
+/// Validates the shape of an IMF-fixdate, i.e. its digits
+/// and separators, leaving the weekday and month names to
+/// the caller, and provides the digit values at their
+/// positions, or `None` where the shape is invalid.
+pub(crate) fn imf_fixdate(bytes: &[u8]) -> Option<Block> {
+  if bytes.len() != IMF_FIXDATE_LEN { return None };
+  let mut block = [0; LEN];
+  block[..IMF_FIXDATE_LEN].copy_from_slice(bytes);
+  arch::values(&block)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod arch {
+
+  use super::{Block, TEMPLATE, DIGITS, NAMES};
+
+  use std::arch::x86_64::*;
+
+  // SAFETY: SSE2 is part of the x86_64 baseline and each
+  // half of a block is 16 bytes, read and written unaligned
+
+  unsafe fn load(block: &Block, half: usize) -> __m128i {
+    _mm_loadu_si128(block[half * 16..].as_ptr() as *const __m128i)
+  }
+
+  unsafe fn store(block: &mut Block, half: usize, v: __m128i) {
+    _mm_storeu_si128(block[half * 16..].as_mut_ptr() as *mut __m128i, v)
+  }
+
+  pub(super) fn add(a: &Block, b: &Block) -> Block {
+    let mut out = [0; 32];
+    for half in 0..2 {
+      unsafe { store(&mut out, half, _mm_add_epi8(load(a, half), load(b, half))) };
+    }
+    out
+  }
+
+  pub(super) fn values(block: &Block) -> Option<Block> {
+    let mut out = [0; 32];
+    for half in 0..2 {
+      unsafe {
+        let b  = load(block,     half);
+        let dm = load(&DIGITS,   half);
+        let nm = load(&NAMES,    half);
+        let eq = _mm_cmpeq_epi8(b, load(&TEMPLATE, half));
+        let v  = _mm_sub_epi8(b, _mm_set1_epi8(b'0' as i8));
+        // digit where the unsigned value is at most 9
+        let is_digit = _mm_cmpeq_epi8(_mm_min_epu8(v, _mm_set1_epi8(9)), v);
+        let ok = _mm_or_si128(_mm_or_si128(_mm_and_si128(dm, is_digit), _mm_andnot_si128(dm, eq)), nm);
+        if _mm_movemask_epi8(ok) != 0xFFFF { return None };
+        store(&mut out, half, _mm_and_si128(v, dm));
+      }
+    }
+    Some (out)
+  }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+
+  use super::{Block, TEMPLATE, DIGITS, NAMES};
+
+  use std::arch::aarch64::*;
+
+  // SAFETY: NEON is part of the aarch64 baseline and each
+  // half of a block is 16 bytes, read and written unaligned
+
+  unsafe fn load(block: &Block, half: usize) -> uint8x16_t {
+    vld1q_u8(block[half * 16..].as_ptr())
+  }
+
+  unsafe fn store(block: &mut Block, half: usize, v: uint8x16_t) {
+    vst1q_u8(block[half * 16..].as_mut_ptr(), v)
+  }
+
+  pub(super) fn add(a: &Block, b: &Block) -> Block {
+    let mut out = [0; 32];
+    for half in 0..2 {
+      unsafe { store(&mut out, half, vaddq_u8(load(a, half), load(b, half))) };
+    }
+    out
+  }
+
+  pub(super) fn values(block: &Block) -> Option<Block> {
+    let mut out = [0; 32];
+    for half in 0..2 {
+      unsafe {
+        let b  = load(block,     half);
+        let dm = load(&DIGITS,   half);
+        let nm = load(&NAMES,    half);
+        let eq = vceqq_u8(b, load(&TEMPLATE, half));
+        let v  = vsubq_u8(b, vdupq_n_u8(b'0'));
+        let is_digit = vcltq_u8(v, vdupq_n_u8(10));
+        let ok = vorrq_u8(vorrq_u8(vandq_u8(dm, is_digit), vbicq_u8(eq, dm)), nm);
+        if vminvq_u8(ok) != 0xFF { return None };
+        store(&mut out, half, vandq_u8(v, dm));
+      }
+    }
+    Some (out)
+  }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod arch {
+
+  use super::{Block, TEMPLATE, DIGITS, NAMES};
+
+  pub(super) fn add(a: &Block, b: &Block) -> Block {
+    let mut out = [0; 32];
+    for i in 0..32 { out[i] = a[i].wrapping_add(b[i]) };
+    out
+  }
+
+  pub(super) fn values(block: &Block) -> Option<Block> {
+    let mut out = [0; 32];
+    for i in 0..32 {
+      let v = block[i].wrapping_sub(b'0');
+      let ok = match (DIGITS[i], NAMES[i]) {
+        (0xFF, _) => v <= 9,
+        (_, 0xFF) => true,
+        _         => block[i] == TEMPLATE[i]
+      };
+      if !ok { return None };
+      out[i] = v & DIGITS[i];
+    }
+    Some (out)
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{format, imf_fixdate};
+  use crate::datetime::Datetime;
+  use crate::date::D_AS_S;
+
+  #[test]
+  fn simd_format() {
+
+    let mut secs = 0;
+    while secs < 253_402_300_800 {
+      let dt = Datetime::from(secs);
+      assert_eq!(dt.for_header().as_bytes(), format(&dt));
+      secs += D_AS_S * 397 + 3_607;
+    }
+  }
+
+  #[test]
+  fn simd_imf_fixdate() {
+
+    let values = imf_fixdate(b"Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    assert_eq!([0, 6], values[ 5.. 7]);
+    assert_eq!([1, 9, 9, 4], values[12..16]);
+    assert_eq!([0, 8], values[17..19]);
+    assert_eq!([4, 9], values[20..22]);
+    assert_eq!([3, 7], values[23..25]);
+    assert_eq!([0; 3], values[ 0.. 3]);
+
+    // names left to the caller
+    assert!(imf_fixdate(b"Xyz, 06 Abc 1994 08:49:37 GMT").is_some());
+
+    assert!(imf_fixdate(b"Sun, 06 Nov 1994 08:49:37 UTC").is_none());
+    assert!(imf_fixdate(b"Sun, 06 Nov 1994 08:49:3/ GMT").is_none());
+    assert!(imf_fixdate(b"Sun, 06 Nov 1994 08:49::7 GMT").is_none());
+    assert!(imf_fixdate(b"Sun,  6 Nov 1994 08:49:37 GMT").is_none());
+    assert!(imf_fixdate(b"Sun, 06 Nov 1994 08-49-37 GMT").is_none());
+    assert!(imf_fixdate("Sun, 06 Nov 1994 08:49:\u{e9} GMT".as_bytes()).is_none());
+    assert!(imf_fixdate(b"Sun, 06 Nov 1994 08:49:37 GMT ").is_none());
+    assert!(imf_fixdate(b"").is_none());
+  }
+}
@@ -0,0 +1,176 @@
+//! # Skew
+//!
+//! Estimation of the skew between a peer's clock and the
+//! local clock.
+
+use crate::datetime::Datetime;
+
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::error::Error;
+
+/// Accumulates pairs of the local datetime at which a
+/// response was received and the datetime of its Date
+/// header, retaining up to `capacity` of the most recent,
+/// and estimates the skew of the peer's clock relative to
+/// the local clock as the median difference in seconds,
+/// positive where the peer's clock is ahead. The `to_local`
+/// method applies the estimate to a datetime from the peer,
+/// e.g. that of an Expires header.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, SkewEstimator};
+///
+/// let mut skew = SkewEstimator::new(16);
+///
+/// skew.add(&Datetime::from(1000), &Datetime::from(1030));
+/// skew.add(&Datetime::from(2000), &Datetime::from(2031));
+/// skew.add(&Datetime::from(3000), &Datetime::from(3029));
+///
+/// assert_eq!(Some (30), skew.estimate());
+/// assert_eq!(Datetime::from(4000), skew.to_local(&Datetime::from(4030)));
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct SkewEstimator {
+  samples: VecDeque<i64>,
+  capacity: usize
+}
+
+impl SkewEstimator {
+
+  pub fn new(capacity: usize) -> Self {
+    let samples = VecDeque::with_capacity(capacity);
+    Self { samples, capacity }
+  }
+
+  pub fn add(&mut self, local: &Datetime, peer: &Datetime) {
+    if self.capacity == 0 { return };
+    if self.samples.len() == self.capacity {
+      self.samples.pop_front();
+    }
+    self.samples.push_back(peer.secs as i64 - local.secs as i64);
+  }
+
+  pub fn len(&self) -> usize {
+    self.samples.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.samples.is_empty()
+  }
+
+  pub fn estimate(&self) -> Option<i64> {
+    if self.samples.is_empty() { return None };
+    let mut sorted: Vec<i64> = self.samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    match sorted.len() % 2 {
+      0 => Some ((sorted[mid - 1] + sorted[mid]) / 2),
+      _ => Some (sorted[mid])
+    }
+  }
+
+  pub fn to_local(&self, peer: &Datetime) -> Datetime {
+    let skew = self.estimate().unwrap_or(0);
+    let secs = (peer.secs as i64 - skew).max(0) as u64;
+    Datetime::from(secs)
+  }
+}
+
+/// Parses a peer's Date header value and validates it
+/// against the local clock, providing the datetime where
+/// it is within `tolerance` of the current datetime and
+/// an error where it is implausible or cannot be parsed.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, validate_peer_date};
+/// use std::time::Duration;
+///
+/// let date = Datetime::new().unwrap().for_header();
+///
+/// assert!(validate_peer_date(&date, Duration::from_secs(300)).is_ok());
+/// assert!(validate_peer_date("Sun, 06 Nov 1994 08:49:37 GMT", Duration::from_secs(300)).is_err());
+/// ```
+pub fn validate_peer_date(date: &str, tolerance: Duration) -> Result<Datetime, Box<dyn Error>> {
+  let peer = Datetime::parse(date)?;
+  let now = Datetime::new()?;
+  match peer.within_skew(&now, tolerance) {
+    true  => Ok (peer),
+    false => Err(format!("date {date:?} not within {}s of local clock", tolerance.as_secs()).into())
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{SkewEstimator, validate_peer_date};
+  use crate::datetime::Datetime;
+
+  use std::time::Duration;
+
+  #[test]
+  fn skew_estimate() {
+
+    let mut skew = SkewEstimator::new(4);
+
+    assert_eq!(None, skew.estimate());
+
+    skew.add(&Datetime::from(100), &Datetime::from( 90));
+    assert_eq!(Some (-10), skew.estimate());
+
+    skew.add(&Datetime::from(100), &Datetime::from(110));
+    assert_eq!(Some (  0), skew.estimate());
+
+    skew.add(&Datetime::from(100), &Datetime::from(104));
+    skew.add(&Datetime::from(100), &Datetime::from(105));
+    assert_eq!(Some (  4), skew.estimate());
+
+    // outlier
+    skew.add(&Datetime::from(100), &Datetime::from(9999));
+    assert_eq!(4, skew.len());
+    assert_eq!(Some (  7), skew.estimate());
+  }
+
+  #[test]
+  fn skew_to_local() {
+
+    let mut skew = SkewEstimator::new(4);
+
+    assert_eq!(Datetime::from(100), skew.to_local(&Datetime::from(100)));
+
+    skew.add(&Datetime::from(100), &Datetime::from(160));
+    assert_eq!(Datetime::from( 40), skew.to_local(&Datetime::from(100)));
+    assert_eq!(Datetime::from(  0), skew.to_local(&Datetime::from( 30)));
+
+    skew.add(&Datetime::from(100), &Datetime::from( 40));
+    skew.add(&Datetime::from(100), &Datetime::from( 40));
+    assert_eq!(Datetime::from(160), skew.to_local(&Datetime::from(100)));
+  }
+
+  #[test]
+  fn skew_capacity() {
+
+    let mut skew = SkewEstimator::new(0);
+    skew.add(&Datetime::from(100), &Datetime::from(160));
+
+    assert!(skew.is_empty());
+    assert_eq!(None, skew.estimate());
+  }
+
+  #[test]
+  fn skew_validate_peer_date() {
+
+    let tolerance = Duration::from_secs(300);
+    let now = Datetime::new().unwrap();
+
+    assert_eq!(now.set(now.secs + 60), validate_peer_date(&now.set(now.secs + 60).for_header(), tolerance).unwrap());
+    assert!(validate_peer_date(&Datetime::from(now.secs - 60).for_header(), tolerance).is_ok());
+    assert!(validate_peer_date(&now.set(now.secs + 3600).for_header(), tolerance).is_err());
+    assert!(validate_peer_date("Sun, 06 Nov 1994 08:49:37 GMT", tolerance).is_err());
+    assert!(validate_peer_date("yesterday", tolerance).is_err());
+  }
+}
@@ -0,0 +1,242 @@
+//! # SNTP
+//!
+//! A minimal SNTP client per RFC 4330, usable as a `Clock`
+//! where the system clock is unreliable, available with the
+//! `sntp` feature.
+
+use crate::clock::Clock;
+
+use std::net::{UdpSocket, SocketAddr, ToSocketAddrs, Ipv4Addr, Ipv6Addr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use std::error::Error;
+
+const PACKET_LEN: usize = 48;
+// leap indicator 0, version 4, mode 3 (client)
+const CLIENT: u8 = 0b00_100_011;
+const SERVER_MODE: u8 = 4;
+// seconds from 01 Jan 1900, the NTP era 0 epoch, to the
+// Unix epoch
+const NTP_TO_UNIX_S: u64 = 2_208_988_800;
+const ERA_AS_S: u64 = 1 << 32;
+
+/// Reads the current time from an NTP server, e.g.
+/// `pool.ntp.org:123`, for embedded or containerized
+/// servers without a reliable system clock. The server is
+/// queried on first use and again once the resync interval
+/// has passed, by default 1024 seconds, with the time in
+/// between extrapolated from the monotonic clock, and any
+/// failed resync falling back to extrapolation from the
+/// last response. Each query waits up to the timeout, by
+/// default one second.
+///
+/// # Example
+///
+/// ```no_run
+/// use httpdt::{SntpClock, set_global_clock};
+///
+/// let clock = SntpClock::new("pool.ntp.org:123")
+///   .resync(std::time::Duration::from_secs(3600));
+///
+/// set_global_clock(clock);
+/// ```
+#[derive(Debug)]
+pub struct SntpClock {
+   server: String,
+  timeout: Duration,
+   resync: Duration,
+  // the monotonic time of the last response and the time
+  // since the epoch it gave
+   synced: Mutex<Option<(Instant, Duration)>>
+}
+
+impl SntpClock {
+
+  pub fn new(server: &str) -> Self {
+    Self {
+       server: server.to_string(),
+      timeout: Duration::from_secs(1),
+       resync: Duration::from_secs(1024),
+       synced: Mutex::new(None)
+    }
+  }
+
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  pub fn resync(mut self, resync: Duration) -> Self {
+    self.resync = resync;
+    self
+  }
+
+  /// Queries the server once, providing the time since the
+  /// epoch given by the response.
+  pub fn query(&self) -> Result<Duration, Box<dyn Error>> {
+    let server = self.server
+      .to_socket_addrs()?
+      .next()
+      .ok_or_else(|| format!("server {:?} not resolved", self.server))?;
+    let local: SocketAddr = match server {
+      SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+      SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.set_read_timeout(Some (self.timeout))?;
+    socket.connect(server)?;
+    let request = request(SystemTime::now());
+    socket.send(&request)?;
+    let mut response = [0; PACKET_LEN];
+    let len = socket.recv(&mut response)?;
+    transmitted(&request, &response[..len])
+  }
+}
+
+impl Clock for SntpClock {
+
+  fn raw(&self) -> Result<u64, Box<dyn Error>> {
+    let mut synced = self.synced.lock().unwrap_or_else(|e| e.into_inner());
+    let is_due = synced.is_none_or(|(at, _)| at.elapsed() >= self.resync);
+    if is_due {
+      match self.query() {
+        Ok (since_epoch)           => *synced = Some ((Instant::now(), since_epoch)),
+        Err(e) if synced.is_none() => return Err(e),
+        Err(_)                     => ()
+      }
+    }
+    let (at, since_epoch) = synced.ok_or("no response from server")?;
+    Ok ((since_epoch + at.elapsed()).as_secs())
+  }
+}
+
+// a client request with the transmit timestamp set from
+// the local clock, however unreliable, to be echoed by the
+// server as the originate timestamp
+fn request(now: SystemTime) -> [u8; PACKET_LEN] {
+  let mut request = [0; PACKET_LEN];
+  request[0] = CLIENT;
+  let since_epoch = now
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .unwrap_or_default();
+  let secs = (since_epoch.as_secs() + NTP_TO_UNIX_S) as u32;
+  let frac = ((since_epoch.subsec_nanos() as u64) << 32) / 1_000_000_000;
+  request[40..44].copy_from_slice(&secs.to_be_bytes());
+  request[44..48].copy_from_slice(&(frac as u32).to_be_bytes());
+  request
+}
+
+// the transmit timestamp of a response to the request, as
+// the time since the Unix epoch
+fn transmitted(request: &[u8; PACKET_LEN], response: &[u8]) -> Result<Duration, Box<dyn Error>> {
+  let response: &[u8; PACKET_LEN] = response
+    .try_into()
+    .map_err(|_| format!("response of {} bytes, not {PACKET_LEN}", response.len()))?;
+  if response[0] & 0b111 != SERVER_MODE {
+    return Err(format!("response in mode {}, not {SERVER_MODE}", response[0] & 0b111).into())
+  }
+  if response[1] == 0 {
+    return Err("kiss-o'-death response".into())
+  }
+  if response[24..32] != request[40..48] {
+    return Err("response not to the request sent".into())
+  }
+  let word = |i: usize| u32::from_be_bytes([response[i], response[i + 1], response[i + 2], response[i + 3]]) as u64;
+  let (secs, frac) = (word(40), word(44));
+  if secs == 0 && frac == 0 {
+    return Err("response without a transmit timestamp".into())
+  }
+  // era 1 from 2036, where the most significant bit is 0
+  let secs = match secs & (1 << 31) {
+    0 => secs + ERA_AS_S - NTP_TO_UNIX_S,
+    _ => secs - NTP_TO_UNIX_S
+  };
+  let nanos = (frac * 1_000_000_000) >> 32;
+  Ok (Duration::new(secs, nanos as u32))
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{SntpClock, request, transmitted, PACKET_LEN, NTP_TO_UNIX_S};
+  use crate::clock::Clock;
+
+  use std::time::{Duration, SystemTime};
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  fn response(request: &[u8; PACKET_LEN], secs: u32, frac: u32) -> [u8; PACKET_LEN] {
+    let mut response = [0; PACKET_LEN];
+    response[0] = 0b00_100_100;
+    response[1] = 2;
+    response[24..32].copy_from_slice(&request[40..48]);
+    response[40..44].copy_from_slice(&secs.to_be_bytes());
+    response[44..48].copy_from_slice(&frac.to_be_bytes());
+    response
+  }
+
+  #[test]
+  fn sntp_request() {
+
+    let request = request(SystemTime::UNIX_EPOCH + Duration::new(NOV_06_1994_08_49_37, 500_000_000));
+
+    assert_eq!(0x23, request[0]);
+    assert_eq!((NOV_06_1994_08_49_37 + NTP_TO_UNIX_S) as u32, u32::from_be_bytes(request[40..44].try_into().unwrap()));
+    assert_eq!(1 << 31,                                       u32::from_be_bytes(request[44..48].try_into().unwrap()));
+  }
+
+  #[test]
+  fn sntp_transmitted() {
+
+    let request = request(SystemTime::UNIX_EPOCH);
+    let secs = (NOV_06_1994_08_49_37 + NTP_TO_UNIX_S) as u32;
+
+    assert_eq!(Duration::new(NOV_06_1994_08_49_37, 500_000_000), transmitted(&request, &response(&request, secs, 1 << 31)).unwrap());
+
+    // era 1, from 07 Feb 2036 06:28:16
+    assert_eq!(Duration::from_secs(2_085_978_496), transmitted(&request, &response(&request, 0, 1)).unwrap());
+    assert_eq!(Duration::from_secs(2_085_978_497), transmitted(&request, &response(&request, 1, 0)).unwrap());
+
+    let mut invalid = response(&request, secs, 0);
+    invalid[0] = 0b00_100_011;
+    assert!(transmitted(&request, &invalid).is_err());
+
+    let mut invalid = response(&request, secs, 0);
+    invalid[1] = 0;
+    assert!(transmitted(&request, &invalid).is_err());
+
+    let mut invalid = response(&request, secs, 0);
+    invalid[31] ^= 1;
+    assert!(transmitted(&request, &invalid).is_err());
+
+    assert!(transmitted(&request, &response(&request, 0, 0)).is_err());
+    assert!(transmitted(&request, &response(&request, secs, 0)[..47]).is_err());
+  }
+
+  // against a local server, where sockets are supported
+  #[cfg(not(target_os = "wasi"))]
+  #[test]
+  fn sntp_clock_raw() {
+
+    use std::net::UdpSocket;
+
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    let handle = std::thread::spawn(move || {
+      let mut request = [0; PACKET_LEN];
+      let (_, peer) = server.recv_from(&mut request).unwrap();
+      let secs = (NOV_06_1994_08_49_37 + NTP_TO_UNIX_S) as u32;
+      server.send_to(&response(&request, secs, 0), peer).unwrap();
+    });
+
+    let clock = SntpClock::new(&addr);
+    assert_eq!(NOV_06_1994_08_49_37, clock.raw().unwrap());
+    handle.join().unwrap();
+
+    // extrapolated until resync, with the server now gone
+    assert_eq!(NOV_06_1994_08_49_37, clock.raw().unwrap());
+
+    let clock = SntpClock::new(&addr).timeout(Duration::from_millis(10));
+    assert!(clock.raw().is_err());
+  }
+}
@@ -0,0 +1,153 @@
+//! # Stream
+//!
+//! Incremental parsing of HTTP Date header timestamps fed
+//! in chunks, e.g. from non-contiguous network buffers.
+
+use crate::datetime::Datetime;
+use crate::parse::{self, ParseOptions, ParseError};
+
+use std::str;
+
+// the longest of the three formats, i.e. RFC 850 with
+// "Wednesday", as against 29 for the IMF-fixdate and 24
+// for asctime
+const MAX_LEN: usize = 33;
+
+/// Accumulates the bytes of an HTTP-date in any of the three
+/// formats recipients are required to accept, fed in chunks
+/// via `feed`, without allocation, indicating that more are
+/// needed until the full timestamp is available, then
+/// providing the datetime with the number of bytes of the
+/// last chunk consumed, any remainder, e.g. a CRLF, left to
+/// the caller. The parser is reset on completion or error,
+/// for reuse.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{StreamParser, Feed};
+///
+/// let mut parser = StreamParser::new();
+///
+/// assert_eq!(Ok (Feed::NeedMore), parser.feed(b"Sun, 06 Nov 19"));
+///
+/// let Ok (Feed::Done(dt, consumed)) = parser.feed(b"94 08:49:37 GMT\r\n") else { panic!() };
+///
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", dt.for_header());
+/// assert_eq!(15, consumed);
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct StreamParser {
+  buf: [u8; MAX_LEN],
+  len: usize
+}
+
+impl Default for StreamParser {
+
+  fn default() -> Self {
+    Self { buf: [0; MAX_LEN], len: 0 }
+  }
+}
+
+impl StreamParser {
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn feed(&mut self, chunk: &[u8]) -> Result<Feed, ParseError> {
+    for (i, &b) in chunk.iter().enumerate() {
+      self.buf[self.len] = b;
+      self.len += 1;
+      let result = match self.expected_len() {
+        Some (len) if len == self.len => self.parse().map(|dt| Feed::Done(dt, i + 1)),
+        Some (len) if len > MAX_LEN   => Err(ParseError::Format),
+        None if self.len == MAX_LEN   => Err(ParseError::Format),
+        _                             => continue
+      };
+      self.reset();
+      return result
+    }
+    Ok (Feed::NeedMore)
+  }
+
+  pub fn reset(&mut self) {
+    self.len = 0;
+  }
+
+  // per the format indicated by the fourth byte, with the
+  // length of RFC 850 given by the comma after the weekday
+  fn expected_len(&self) -> Option<usize> {
+    let bytes = &self.buf[..self.len];
+    match bytes.get(3) {
+      Some (b',') => Some (29),
+      Some (b' ') => Some (24),
+      Some (_)    => bytes.iter().position(|&b| b == b',').map(|pos| pos + 24),
+      None        => None
+    }
+  }
+
+  fn parse(&self) -> Result<Datetime, ParseError> {
+    let s = str::from_utf8(&self.buf[..self.len]).map_err(|_| ParseError::Format)?;
+    parse::parse(s, &ParseOptions::default())
+  }
+}
+
+// Feed
+
+/// Indicates either that more bytes are needed or that the
+/// timestamp is complete, with the datetime and the number
+/// of bytes of the last chunk consumed.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Feed {
+  NeedMore,
+  Done(Datetime, usize)
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{StreamParser, Feed};
+  use crate::datetime::Datetime;
+  use crate::parse::ParseError;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn stream_feed() {
+
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    for s in ["Sun, 06 Nov 1994 08:49:37 GMT", "Sunday, 06-Nov-94 08:49:37 GMT", "Sun Nov  6 08:49:37 1994"] {
+      for split in 0..s.len() {
+        let mut parser = StreamParser::new();
+        assert_eq!(Ok (Feed::NeedMore), parser.feed(&s.as_bytes()[..split]));
+        assert_eq!(Ok (Feed::Done(dt, s.len() - split)), parser.feed(&s.as_bytes()[split..]));
+      }
+    }
+
+    // byte by byte, with a remainder and reuse
+    let mut parser = StreamParser::new();
+    for b in b"Wednesday, 09-Nov-94 08:49:37 GM" {
+      assert_eq!(Ok (Feed::NeedMore), parser.feed(&[*b]));
+    }
+    assert_eq!(Ok (Feed::Done(dt.set(NOV_06_1994_08_49_37 + 3 * 86_400), 1)), parser.feed(b"T\r\n"));
+    assert_eq!(Ok (Feed::Done(dt, 29)), parser.feed(b"Sun, 06 Nov 1994 08:49:37 GMT"));
+  }
+
+  #[test]
+  fn stream_feed_invalid() {
+
+    let mut parser = StreamParser::new();
+
+    assert_eq!(Err(ParseError::Weekday), parser.feed(b"Mon, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format), parser.feed(b"Sun, 06 Nov 1994 08:49:37 UTC"));
+    assert_eq!(Err(ParseError::Format), parser.feed(b"Sunday 06 November 1994 08:49:37 GMT"));
+    assert_eq!(Err(ParseError::Format), parser.feed("Sun, 06 Nov 1994 08:49:37 G\u{e9}".as_bytes()));
+
+    // reset after error
+    assert_eq!(Ok (Feed::NeedMore), parser.feed(b"Sun, 06 Nov 1994"));
+    parser.reset();
+    assert_eq!(Ok (Feed::NeedMore), parser.feed(b"Sun"));
+  }
+}
@@ -0,0 +1,120 @@
+//! # Test vectors
+//!
+//! A curated corpus of valid and malformed HTTP-dates with
+//! the results expected of a conforming recipient, per RFC
+//! 9110, section 5.6.7, available with the `test-vectors`
+//! feature, for reuse in the test suites of downstream
+//! parsers and middleware.
+
+use crate::parse::ParseError;
+
+/// Pairs an input with the result expected of
+/// `Datetime::parse`, i.e. the number of seconds since the
+/// epoch or the `ParseError`, and a note on what it covers.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::Datetime;
+/// use httpdt::test_vectors::{VALID, MALFORMED};
+///
+/// for v in VALID.iter().chain(MALFORMED) {
+///   let parsed = Datetime::parse(v.input).map(|dt| dt.secs());
+///   assert_eq!(v.expected, parsed, "{}", v.note);
+/// }
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Vector {
+  pub input: &'static str,
+  pub expected: Result<u64, ParseError>,
+  pub note: &'static str
+}
+
+const fn ok(input: &'static str, secs: u64, note: &'static str) -> Vector {
+  Vector { input, expected: Ok (secs), note }
+}
+
+const fn err(input: &'static str, e: ParseError, note: &'static str) -> Vector {
+  Vector { input, expected: Err(e), note }
+}
+
+/// Inputs in each of the three formats recipients are
+/// required to accept, at the bounds of each field.
+pub const VALID: &[Vector] = &[
+  ok("Sun, 06 Nov 1994 08:49:37 GMT",                   784_111_777, "IMF-fixdate, from RFC 9110"),
+  ok("Sunday, 06-Nov-94 08:49:37 GMT",                  784_111_777, "RFC 850, from RFC 9110"),
+  ok("Sun Nov  6 08:49:37 1994",                        784_111_777, "asctime, from RFC 9110"),
+  ok("Thu, 01 Jan 1970 00:00:00 GMT",                             0, "IMF-fixdate, the epoch"),
+  ok("Thursday, 01-Jan-70 00:00:00 GMT",                          0, "RFC 850, the epoch, a two-digit year of 70 in the 1900s"),
+  ok("Thu Jan  1 00:00:00 1970",                                  0, "asctime, the epoch"),
+  ok("Fri, 31 Dec 9999 23:59:59 GMT",               253_402_300_799, "IMF-fixdate, the latest"),
+  ok("Tue, 29 Feb 2000 12:00:00 GMT",                   951_825_600, "29 Feb of a leap century"),
+  ok("Thu, 29 Feb 2024 00:00:00 GMT",                 1_709_164_800, "29 Feb of a leap year"),
+  ok("Tuesday, 31-Dec-69 23:59:59 GMT",             3_155_759_999, "RFC 850, a two-digit year below 70 in the 2000s"),
+  ok("Fri Dec 31 23:59:59 1999",                        946_684_799, "asctime, a two-digit day"),
+  ok("Sun Nov 06 08:49:37 1994",                        784_111_777, "asctime, a single-digit day zero-padded, as the grammar allows"),
+  ok("Sat, 01 Jan 2000 00:00:00 GMT",                   946_684_800, "IMF-fixdate, the turn of the century")
+];
+
+/// Inputs a conforming recipient rejects, incl. those in
+/// formats some senders produce but the grammar excludes.
+pub const MALFORMED: &[Vector] = &[
+  err("",                                      ParseError::Format,     "empty"),
+  err("Sun, 06 Nov 1994 08:49:37",             ParseError::Format,     "zone missing"),
+  err("Sun, 06 Nov 1994 08:49:37 UTC",         ParseError::Format,     "zone other than GMT"),
+  err("Sun, 06 Nov 1994 08:49:37 +0000",       ParseError::Format,     "numeric zone"),
+  err("Sun, 06 Nov 1994 08:49:37 gmt",         ParseError::Format,     "zone in lower case"),
+  err("sun, 06 Nov 1994 08:49:37 GMT",         ParseError::Format,     "weekday in lower case"),
+  err("Sun, 06 nov 1994 08:49:37 GMT",         ParseError::Format,     "month in lower case"),
+  err("Sun, 6 Nov 1994 08:49:37 GMT",          ParseError::Format,     "single-digit day"),
+  err("Sun, 06 Nov 94 08:49:37 GMT",           ParseError::Format,     "two-digit year in the IMF-fixdate"),
+  err("Sun, 06 November 1994 08:49:37 GMT",    ParseError::Format,     "full month name"),
+  err("Sun,06 Nov 1994 08:49:37 GMT",          ParseError::Format,     "space missing"),
+  err("Sun,  06 Nov 1994 08:49:37 GMT",        ParseError::Format,     "space doubled"),
+  err(" Sun, 06 Nov 1994 08:49:37 GMT",        ParseError::Format,     "leading whitespace"),
+  err("Sun, 06 Nov 1994 08:49:37 GMT ",        ParseError::Format,     "trailing whitespace"),
+  err("Sun, 06 Nov 1994 8:49:37 GMT",          ParseError::Format,     "single-digit hour"),
+  err("Sun, 06 Nov 1994 08:49 GMT",            ParseError::Format,     "seconds missing"),
+  err("Sun, 06-Nov-1994 08:49:37 GMT",         ParseError::Format,     "hyphens in the IMF-fixdate"),
+  err("Sun, 06 Nov 1994 08:49:37.5 GMT",       ParseError::Format,     "fraction of a second"),
+  err("1994-11-06T08:49:37Z",                  ParseError::Format,     "RFC 3339"),
+  err("784111777",                             ParseError::Format,     "seconds since the epoch"),
+  err("Sun Nov 6 08:49:37 1994",               ParseError::Format,     "asctime, day not padded"),
+  err("Sun, 06-Nov-94 08:49:37 GMT",           ParseError::Format,     "RFC 850, short weekday"),
+  err("Mon, 06 Nov 1994 08:49:37 GMT",         ParseError::Weekday,    "weekday mismatched"),
+  err("Monday, 06-Nov-94 08:49:37 GMT",        ParseError::Weekday,    "RFC 850, weekday mismatched"),
+  err("Mon Nov  6 08:49:37 1994",              ParseError::Weekday,    "asctime, weekday mismatched"),
+  err("Sun, 31 Nov 1994 08:49:37 GMT",         ParseError::Range,      "day beyond the month"),
+  err("Sat, 00 Nov 1994 08:49:37 GMT",         ParseError::Range,      "day 0"),
+  err("Sun, 29 Feb 1970 08:49:37 GMT",         ParseError::Range,      "29 Feb of a common year"),
+  err("Mon, 29 Feb 2100 08:49:37 GMT",         ParseError::Range,      "29 Feb of a common century"),
+  err("Sun, 06 Nov 1994 24:00:00 GMT",         ParseError::Range,      "hour 24"),
+  err("Sun, 06 Nov 1994 08:60:37 GMT",         ParseError::Range,      "minute 60"),
+  err("Sun, 06 Nov 1994 23:59:60 GMT",         ParseError::Range,      "leap second"),
+  err("Wed, 31 Dec 1969 23:59:59 GMT",         ParseError::Year(1969), "before the epoch"),
+  err("Sat, 01 Jan 10000 00:00:00 GMT",        ParseError::Format,     "five-digit year")
+];
+
+#[cfg(test)]
+mod test {
+
+  use super::{VALID, MALFORMED};
+  use crate::datetime::Datetime;
+
+  #[test]
+  fn test_vectors_parse() {
+
+    for v in VALID.iter().chain(MALFORMED) {
+      assert_eq!(v.expected, Datetime::parse(v.input).map(|dt| dt.secs()), "{}: {:?}", v.note, v.input);
+    }
+  }
+
+  #[test]
+  fn test_vectors_round_trip() {
+
+    for v in VALID {
+      let dt = Datetime::from(v.expected.unwrap());
+      assert_eq!(v.expected, Datetime::parse(&dt.for_header()).map(|dt| dt.secs()));
+    }
+  }
+}
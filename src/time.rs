@@ -1,17 +1,21 @@
+use crate::parse::{self, ParseError};
+use crate::date::D_AS_S;
+
 use std::convert::From;
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
 pub const M_AS_S: u64 =              60;
 pub const H_AS_M: u64 =              60;
 pub const H_AS_S: u64 = M_AS_S * H_AS_M;
 pub const D_AS_H: u64 =              24;
 
-#[derive(Default, PartialEq, Debug)]
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Time {
   pub  h: u8,
   pub  m: u8,
-  pub  s: u8,
-  pub xs: u64
+  pub  s: u8
 }
 
 impl From<u64> for Time {
@@ -20,12 +24,10 @@ impl From<u64> for Time {
     let  s = (init_s                  )             % M_AS_S;
     let  m = (init_s -  s             ) /    M_AS_S % H_AS_M;
     let  h = (init_s -  s - m * M_AS_S) /    H_AS_S % D_AS_H;
-    let xs =  init_s - (s + m * M_AS_S + h * H_AS_S);
     Self {
-       h: h as u8,
-       m: m as u8,
-       s: s as u8,
-      xs
+      h: h as u8,
+      m: m as u8,
+      s: s as u8
     }
   }
 }
@@ -35,11 +37,64 @@ impl Time {
   pub fn for_header(&self) -> String {
     ImfFixdateTime(self).to_string()
   }
+
+  /// Provides the number of seconds since the start of the
+  /// day, i.e. 00:00:00.
+  pub fn seconds_since_midnight(&self) -> u64 {
+    self.h as u64 * H_AS_S + self.m as u64 * M_AS_S + self.s as u64
+  }
+
+  /// Provides the number of seconds since the start of the
+  /// day, as for `seconds_since_midnight`, in place of the
+  /// `xs` field formerly held, as for `Date::xs`.
+  pub fn xs(&self) -> u64 {
+    self.seconds_since_midnight()
+  }
+
+  /// Provides the time of day the given number of seconds
+  /// later, wrapping past midnight, with the number of days
+  /// overflowed, e.g. for shifting a daily schedule.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use httpdt::Time;
+  ///
+  /// let time: Time = "22:30:00".parse().unwrap();
+  /// let (time, days) = time.wrapping_add_secs(3 * 3600);
+  ///
+  /// assert_eq!(("01:30:00", 1), (time.for_header().as_str(), days));
+  /// ```
+  pub fn wrapping_add_secs(&self, secs: u64) -> (Self, u64) {
+    let total = self.seconds_since_midnight() + secs % D_AS_S;
+    (Self::from(total), secs / D_AS_S + total / D_AS_S)
+  }
+
+  /// Provides the time of day the given number of seconds
+  /// earlier, wrapping past midnight, with the number of
+  /// days underflowed.
+  pub fn wrapping_sub_secs(&self, secs: u64) -> (Self, u64) {
+    let since_midnight = self.seconds_since_midnight();
+    if secs <= since_midnight {
+      return (Self::from(since_midnight - secs), 0)
+    }
+    let deficit = secs - since_midnight;
+    (Self::from(D_AS_S - 1 - (deficit - 1) % D_AS_S), deficit.div_ceil(D_AS_S))
+  }
+}
+
+impl FromStr for Time {
+  type Err = ParseError;
+
+  /// Parses a time as in the IMF-fixdate, e.g. `08:49:37`.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse::parse_time(s)
+  }
 }
 
 // ImfFixdateTime
 
-pub struct ImfFixdateTime<'a>(&'a Time);
+pub(crate) struct ImfFixdateTime<'a>(pub(crate) &'a Time);
 
 impl Display for ImfFixdateTime<'_> {
 
@@ -55,13 +110,13 @@ pub mod test {
   use super::{Time, M_AS_S, H_AS_S, H_AS_M, D_AS_H};
   use crate::date::D_AS_S;
 
-  pub const JAN_01_1970_00_00_00: Time = Time { h:                  0, m:                  0, s:                  0, xs:      0 };
-      const JAN_01_1970_00_00_59: Time = Time { h:                  0, m:                  0, s: (M_AS_S - 1) as u8, xs:      0 };
-      const JAN_01_1970_00_01_00: Time = Time { h:                  0, m:                  1, s:                  0, xs:      0 };
-      const JAN_01_1970_00_59_59: Time = Time { h:                  0, m: (H_AS_M - 1) as u8, s: (M_AS_S - 1) as u8, xs:      0 };
-      const JAN_01_1970_01_00_00: Time = Time { h:                  1, m:                  0, s:                  0, xs:      0 };
-      const JAN_01_1970_23_59_59: Time = Time { h: (D_AS_H - 1) as u8, m: (H_AS_M - 1) as u8, s: (M_AS_S - 1) as u8, xs:      0 };
-      const JAN_02_1970_00_00_00: Time = Time { h:                  0, m:                  0, s:                  0, xs: D_AS_S };
+  pub const JAN_01_1970_00_00_00: Time = Time { h:                  0, m:                  0, s:                  0 };
+      const JAN_01_1970_00_00_59: Time = Time { h:                  0, m:                  0, s: (M_AS_S - 1) as u8 };
+      const JAN_01_1970_00_01_00: Time = Time { h:                  0, m:                  1, s:                  0 };
+      const JAN_01_1970_00_59_59: Time = Time { h:                  0, m: (H_AS_M - 1) as u8, s: (M_AS_S - 1) as u8 };
+      const JAN_01_1970_01_00_00: Time = Time { h:                  1, m:                  0, s:                  0 };
+      const JAN_01_1970_23_59_59: Time = Time { h: (D_AS_H - 1) as u8, m: (H_AS_M - 1) as u8, s: (M_AS_S - 1) as u8 };
+      const JAN_02_1970_00_00_00: Time = Time { h:                  0, m:                  0, s:                  0 };
 
   #[test]
   fn time_default() {
@@ -81,6 +136,42 @@ pub mod test {
     assert_eq!(JAN_02_1970_00_00_00, Time::from(D_AS_S    ));
   }
 
+  #[test]
+  fn time_seconds_since_midnight() {
+
+    assert_eq!(         0, JAN_01_1970_00_00_00.seconds_since_midnight());
+    assert_eq!(M_AS_S - 1, JAN_01_1970_00_00_59.seconds_since_midnight());
+    assert_eq!(M_AS_S    , JAN_01_1970_00_01_00.seconds_since_midnight());
+    assert_eq!(H_AS_S - 1, JAN_01_1970_00_59_59.seconds_since_midnight());
+    assert_eq!(H_AS_S    , JAN_01_1970_01_00_00.seconds_since_midnight());
+    assert_eq!(D_AS_S - 1, JAN_01_1970_23_59_59.seconds_since_midnight());
+    assert_eq!(         0, JAN_02_1970_00_00_00.seconds_since_midnight());
+    assert_eq!(D_AS_S - 1, JAN_01_1970_23_59_59.xs());
+  }
+
+  #[test]
+  fn time_wrapping_add_sub_secs() {
+
+    let cases = [
+      (JAN_01_1970_00_00_00,          0, JAN_01_1970_00_00_00, 0),
+      (JAN_01_1970_00_00_00, M_AS_S - 1, JAN_01_1970_00_00_59, 0),
+      (JAN_01_1970_00_59_59,          1, JAN_01_1970_01_00_00, 0),
+      (JAN_01_1970_23_59_59,          1, JAN_02_1970_00_00_00, 1),
+      (JAN_01_1970_00_01_00, D_AS_S    , JAN_01_1970_00_01_00, 1),
+      (JAN_01_1970_23_59_59, D_AS_S * 3, JAN_01_1970_23_59_59, 3),
+      (JAN_01_1970_01_00_00, D_AS_S - 1, JAN_01_1970_00_59_59, 1)
+    ];
+
+    for (time, secs, later, days) in cases {
+      assert_eq!((later, days), time.wrapping_add_secs(secs));
+      assert_eq!((time,  days), later.wrapping_sub_secs(secs));
+    }
+
+    assert_eq!((JAN_01_1970_00_00_00, 0), JAN_01_1970_01_00_00.wrapping_sub_secs(H_AS_S));
+    assert_eq!((JAN_01_1970_23_59_59, 1), JAN_01_1970_00_00_00.wrapping_sub_secs(1));
+    assert_eq!((Time::from(u64::MAX), u64::MAX / D_AS_S), JAN_01_1970_00_00_00.wrapping_add_secs(u64::MAX));
+  }
+
   #[test]
   fn time_for_header() {
 
@@ -93,3 +184,20 @@ pub mod test {
     assert_eq!(String::from("00:00:00"), JAN_02_1970_00_00_00.for_header());
   }
 }
+
+#[cfg(kani)]
+mod verification {
+
+  use super::{Time, M_AS_S, H_AS_M, D_AS_H};
+  use crate::date::D_AS_S;
+
+  #[kani::proof]
+  fn time_from() {
+
+    let secs: u64 = kani::any();
+    let time = Time::from(secs);
+
+    assert!((time.h as u64) < D_AS_H && (time.m as u64) < H_AS_M && (time.s as u64) < M_AS_S);
+    assert_eq!(secs % D_AS_S, time.seconds_since_midnight());
+  }
+}
@@ -0,0 +1,144 @@
+//! # Timestamp
+//!
+//! A typed number of whole seconds since the Unix epoch.
+
+use crate::datetime::{Datetime, MAX_SECS};
+
+use std::time::{SystemTime, SystemTimeError, Duration};
+use std::error::Error;
+
+/// Holds a number of whole seconds since the epoch, e.g.
+/// the `u64` read by `Datetime::raw` and taken by `set`,
+/// for use in place of a bare `u64` in struct fields and
+/// signatures, with conversions to and from `Datetime`,
+/// `SystemTime` and `Duration`. The conversions to
+/// `Datetime` and `SystemTime` are clamped as for
+/// `From<u64>`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, Timestamp};
+/// use std::time::Duration;
+///
+/// let ts = Timestamp::from(Duration::from_secs(784_111_777));
+/// let dt = Datetime::from(ts);
+///
+/// assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", dt.for_header());
+/// assert_eq!(ts, Timestamp::from(dt));
+/// ```
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+
+  /// Reads the current time, as for `Datetime::raw`.
+  pub fn now() -> Result<Self, Box<dyn Error>> {
+    Ok (Self(Datetime::raw()?))
+  }
+
+  pub fn secs(&self) -> u64 {
+    self.0
+  }
+}
+
+impl From<Datetime> for Timestamp {
+
+  fn from(dt: Datetime) -> Self {
+    Self(dt.secs)
+  }
+}
+
+// clamped at the latest datetime supported
+impl From<Timestamp> for Datetime {
+
+  fn from(ts: Timestamp) -> Self {
+    Self::from(ts.0)
+  }
+}
+
+impl TryFrom<SystemTime> for Timestamp {
+  type Error = SystemTimeError;
+
+  fn try_from(st: SystemTime) -> Result<Self, Self::Error> {
+    let secs = st
+      .duration_since(SystemTime::UNIX_EPOCH)?
+      .as_secs();
+    Ok (Self(secs))
+  }
+}
+
+// clamped as for the conversion to Datetime, so that any
+// value can be added to the epoch
+impl From<Timestamp> for SystemTime {
+
+  fn from(ts: Timestamp) -> Self {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(ts.0.min(MAX_SECS))
+  }
+}
+
+// as the time since the epoch, truncated to whole seconds
+impl From<Duration> for Timestamp {
+
+  fn from(since_epoch: Duration) -> Self {
+    Self(since_epoch.as_secs())
+  }
+}
+
+impl From<Timestamp> for Duration {
+
+  fn from(ts: Timestamp) -> Self {
+    Duration::from_secs(ts.0)
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::Timestamp;
+  use crate::datetime::{Datetime, MAX_SECS};
+
+  use std::time::{SystemTime, Duration};
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+  #[test]
+  fn timestamp_now() {
+
+    let before = Datetime::raw().unwrap();
+    let now = Timestamp::now().unwrap();
+
+    assert!(now.secs() >= before && now.secs() <= Datetime::raw().unwrap());
+  }
+
+  #[test]
+  fn timestamp_datetime() {
+
+    let ts = Timestamp(NOV_06_1994_08_49_37);
+    let dt = Datetime::from(NOV_06_1994_08_49_37);
+
+    assert_eq!(dt, Datetime::from(ts));
+    assert_eq!(ts, Timestamp::from(dt));
+    assert_eq!(Datetime::default(), Datetime::from(Timestamp::default()));
+    assert_eq!(Datetime::from(MAX_SECS), Datetime::from(Timestamp(u64::MAX)));
+  }
+
+  #[test]
+  fn timestamp_system_time() {
+
+    let st = SystemTime::UNIX_EPOCH + Duration::new(NOV_06_1994_08_49_37, 999_999_999);
+
+    assert_eq!(Timestamp(NOV_06_1994_08_49_37), Timestamp::try_from(st).unwrap());
+    assert_eq!(SystemTime::UNIX_EPOCH + Duration::from_secs(NOV_06_1994_08_49_37), SystemTime::from(Timestamp(NOV_06_1994_08_49_37)));
+    assert!(Timestamp::try_from(SystemTime::UNIX_EPOCH - Duration::from_secs(1)).is_err());
+    assert_eq!(SystemTime::UNIX_EPOCH + Duration::from_secs(MAX_SECS), SystemTime::from(Timestamp(u64::MAX)));
+  }
+
+  #[test]
+  fn timestamp_duration() {
+
+    assert_eq!(Timestamp(NOV_06_1994_08_49_37), Timestamp::from(Duration::new(NOV_06_1994_08_49_37, 500_000_000)));
+    assert_eq!(Duration::from_secs(NOV_06_1994_08_49_37), Duration::from(Timestamp(NOV_06_1994_08_49_37)));
+    assert!(Timestamp(0) < Timestamp(1));
+  }
+}
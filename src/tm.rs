@@ -0,0 +1,120 @@
+//! # Tm
+//!
+//! Conversion between `Datetime` and the C `struct tm`, in
+//! UTC, for FFI layers, available with the `libc` feature.
+
+use crate::datetime::{Datetime, ComponentError};
+use crate::date::{Weekday, Month, Year};
+
+use libc::{tm, c_int};
+
+// as broken down by gmtime, with tm_wday counting from
+// Sunday, tm_yday and tm_mon from 0 and tm_year from 1900
+impl From<Datetime> for tm {
+
+  fn from(dt: Datetime) -> Self {
+    // zeroed for any platform-specific fields, e.g. tm_gmtoff
+    // and tm_zone, with tm_isdst in turn 0, as for UTC
+    let mut tm: tm = unsafe { std::mem::zeroed() };
+    let y = dt.date.y;
+    tm.tm_sec  = dt.time.s as c_int;
+    tm.tm_min  = dt.time.m as c_int;
+    tm.tm_hour = dt.time.h as c_int;
+    tm.tm_mday = dt.date.d as c_int;
+    tm.tm_mon  = Month::iter().position(|m| m == dt.date.m).unwrap_or_default() as c_int;
+    tm.tm_year = (y.0 - 1900) as c_int;
+    tm.tm_wday = Weekday::iter().position(|wd| wd == dt.date.wd).map_or(0, |i| (i + 1) % 7) as c_int;
+    tm.tm_yday = (dt.date.days_since_epoch() - y.days_before()) as c_int;
+    tm
+  }
+}
+
+// as read by timegm, with tm_wday, tm_yday and tm_isdst
+// disregarded, but without normalization, each field out
+// of range giving ComponentError::Range, incl. leap seconds
+impl TryFrom<tm> for Datetime {
+  type Error = ComponentError;
+
+  fn try_from(tm: tm) -> Result<Self, Self::Error> {
+    let y = u64::try_from(tm.tm_year as i64 + 1900).map_err(|_| ComponentError::Range)?;
+    let m = usize::try_from(tm.tm_mon).ok()
+      .and_then(|i| Month::iter().nth(i))
+      .ok_or(ComponentError::Range)?;
+    let is_leap_year = Year(y).is_leap();
+    let d = u8::try_from(tm.tm_mday).ok()
+      .filter(|d| (1..=m.len(is_leap_year)).contains(d))
+      .ok_or(ComponentError::Range)?;
+    let part = |n: c_int| u8::try_from(n).map_err(|_| ComponentError::Range);
+    let day = m.days_before(is_leap_year) as u16 + d as u16;
+    Self::from_ordinal_date(y, day, part(tm.tm_hour)?, part(tm.tm_min)?, part(tm.tm_sec)?)
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use crate::datetime::{Datetime, ComponentError};
+
+  use libc::tm;
+
+  const NOV_06_1994_08_49_37: u64 = 784_111_777;
+  const DEC_31_2024_23_59_59: u64 = 1_735_689_599;
+
+  #[test]
+  fn tm_from_datetime() {
+
+    let tm = tm::from(Datetime::from(NOV_06_1994_08_49_37));
+
+    assert_eq!((37, 49, 8), (tm.tm_sec, tm.tm_min, tm.tm_hour));
+    assert_eq!((6, 10, 94), (tm.tm_mday, tm.tm_mon, tm.tm_year));
+    assert_eq!((0, 309, 0), (tm.tm_wday, tm.tm_yday, tm.tm_isdst));
+
+    let tm = tm::from(Datetime::from(DEC_31_2024_23_59_59));
+
+    assert_eq!((31, 11, 124), (tm.tm_mday, tm.tm_mon, tm.tm_year));
+    assert_eq!(( 2, 365,   0), (tm.tm_wday, tm.tm_yday, tm.tm_isdst));
+
+    let tm = tm::from(Datetime::default());
+
+    assert_eq!((1, 0, 70), (tm.tm_mday, tm.tm_mon, tm.tm_year));
+    assert_eq!((4, 0,  0), (tm.tm_wday, tm.tm_yday, tm.tm_isdst));
+  }
+
+  #[test]
+  fn datetime_try_from_tm() {
+
+    for secs in [0, NOV_06_1994_08_49_37, DEC_31_2024_23_59_59] {
+      let dt = Datetime::from(secs);
+      assert_eq!(Ok (dt), Datetime::try_from(tm::from(dt)));
+    }
+
+    // fields disregarded
+    let mut tm = tm::from(Datetime::from(NOV_06_1994_08_49_37));
+    (tm.tm_wday, tm.tm_yday, tm.tm_isdst) = (3, 0, 1);
+    assert_eq!(Ok (Datetime::from(NOV_06_1994_08_49_37)), Datetime::try_from(tm));
+
+    let valid = tm::from(Datetime::from(NOV_06_1994_08_49_37));
+    let cases: [fn(&mut tm); 8] = [
+      |tm| tm.tm_sec  = 60,
+      |tm| tm.tm_min  = -1,
+      |tm| tm.tm_hour = 24,
+      |tm| tm.tm_mday = 31,
+      |tm| tm.tm_mday = 0,
+      |tm| tm.tm_mon  = 12,
+      |tm| tm.tm_year = 69,
+      |tm| tm.tm_year = 8100
+    ];
+    for case in cases {
+      let mut tm = valid;
+      case(&mut tm);
+      assert_eq!(Err(ComponentError::Range), Datetime::try_from(tm));
+    }
+
+    // 29 Feb in leap years only
+    let mut tm = valid;
+    (tm.tm_mday, tm.tm_mon, tm.tm_year) = (29, 1, 72);
+    assert!(Datetime::try_from(tm).is_ok());
+    tm.tm_year = 73;
+    assert_eq!(Err(ComponentError::Range), Datetime::try_from(tm));
+  }
+}
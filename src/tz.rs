@@ -0,0 +1,344 @@
+//! # Tz
+//!
+//! POSIX TZ specifications, for the display of datetimes
+//! in local time, available with the `posix-tz` feature.
+
+use crate::datetime::Datetime;
+use crate::offset::OffsetDatetime;
+use crate::parse::ParseError;
+use crate::date::{Month, Year, D_AS_S};
+use crate::time::{M_AS_S, H_AS_S};
+
+/// Stores a POSIX TZ specification, e.g. that for Central
+/// European Time, `CET-1CEST,M3.5.0,M10.5.0/3`, i.e. the
+/// standard time name and offset with any daylight saving
+/// time name, offset and rules for its start and end,
+/// providing the offset in effect at a given datetime
+/// (`offset_at`) and the datetime in local time as an
+/// `OffsetDatetime` (`to_local`). Offsets are stored in
+/// seconds east of GMT, the reverse of the specification.
+/// A daylight saving time given without rules follows the
+/// current US rules, `M3.2.0,M11.1.0`.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::{Datetime, PosixTz};
+///
+/// let tz = PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3")
+///   .unwrap();
+///
+/// let winter = Datetime::parse("Mon, 01 Jan 2024 12:00:00 GMT").unwrap();
+/// let summer = Datetime::parse("Mon, 01 Jul 2024 12:00:00 GMT").unwrap();
+///
+/// assert_eq!("01/Jan/2024:13:00:00 +0100", tz.to_local(&winter).for_log());
+/// assert_eq!("01/Jul/2024:14:00:00 +0200", tz.to_local(&summer).for_log());
+/// assert_eq!("CEST", tz.name_at(&summer));
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct PosixTz {
+  std_name: String,
+  std_offset: i32,
+  dst: Option<Dst>
+}
+
+#[derive(PartialEq, Clone, Debug)]
+struct Dst {
+  name: String,
+  offset: i32,
+  start: Rule,
+  end: Rule
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct Rule {
+  day: RuleDay,
+  time: i32
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum RuleDay {
+  // Jn, 1 to 365, ignoring 29 February
+  Julian(u16),
+  // n, 0 to 365, counting 29 February
+  Zero(u16),
+  // Mm.w.d, week 5 the last, weekday 0 Sunday
+  MonthWeekDay(u8, u8, u8)
+}
+
+impl PosixTz {
+
+  pub fn parse(s: &str) -> Result<Self, ParseError> {
+
+    let mut c = TzCursor { bytes: s.as_bytes(), pos: 0 };
+
+    let std_name = c.name()?;
+    let std_offset = -c.signed_time()?;
+
+    let dst = match c.done() {
+      true  => None,
+      false => {
+        let name = c.name()?;
+        let offset = match c.peek() {
+          Some (b',') | None => std_offset + H_AS_S as i32,
+          Some (_)           => -c.signed_time()?
+        };
+        let (start, end) = match c.done() {
+          true  => (
+            Rule { day: RuleDay::MonthWeekDay( 3, 2, 0), time: 2 * H_AS_S as i32 },
+            Rule { day: RuleDay::MonthWeekDay(11, 1, 0), time: 2 * H_AS_S as i32 }
+          ),
+          false => {
+            c.tag(b',')?;
+            let start = c.rule()?;
+            c.tag(b',')?;
+            let end = c.rule()?;
+            (start, end)
+          }
+        };
+        Some (Dst { name, offset, start, end })
+      }
+    };
+
+    match c.done() {
+      true  => Ok (Self { std_name, std_offset, dst }),
+      false => Err(ParseError::Format)
+    }
+  }
+
+  pub fn offset_at(&self, dt: &Datetime) -> i32 {
+    match &self.dst {
+      Some (dst) if self.is_dst(dst, dt) => dst.offset,
+      _                                  => self.std_offset
+    }
+  }
+
+  pub fn name_at(&self, dt: &Datetime) -> &str {
+    match &self.dst {
+      Some (dst) if self.is_dst(dst, dt) => &dst.name,
+      _                                  => &self.std_name
+    }
+  }
+
+  pub fn to_local(&self, dt: &Datetime) -> OffsetDatetime {
+    OffsetDatetime::new(*dt, self.offset_at(dt))
+  }
+
+  fn is_dst(&self, dst: &Dst, dt: &Datetime) -> bool {
+    let local = dt.secs as i64 + self.std_offset as i64;
+    let y = Datetime::from(local.max(0) as u64).date.y;
+    let start = dst.start.local_secs(y) - self.std_offset as i64;
+    let end = dst.end.local_secs(y) - dst.offset as i64;
+    let secs = dt.secs as i64;
+    match start < end {
+      true  =>   start <= secs && secs < end,
+      false => !(end   <= secs && secs < start)
+    }
+  }
+}
+
+impl Rule {
+
+  fn local_secs(&self, y: Year) -> i64 {
+    let days = y.days_before() + self.day.day_of_year(y);
+    (days * D_AS_S) as i64 + self.time as i64
+  }
+}
+
+impl RuleDay {
+
+  fn day_of_year(&self, y: Year) -> u64 {
+    match *self {
+      Self::Julian(n)              => n as u64 - 1 + (y.is_leap() && n >= 60) as u64,
+      Self::Zero(n)                => n as u64,
+      Self::MonthWeekDay(m, w, d)  => {
+        let month = Month::default().skip(m as u64 - 1);
+        let first = month.days_before(y.is_leap());
+        // the epoch is a Thursday, i.e. weekday 4
+        let first_wd = (y.days_before() + first + 4) % 7;
+        let mut day = (d as u64 + 7 - first_wd) % 7 + (w as u64 - 1) * 7;
+        while day >= month.len(y.is_leap()) as u64 { day -= 7 };
+        first + day
+      }
+    }
+  }
+}
+
+// TzCursor
+
+struct TzCursor<'a> {
+  bytes: &'a [u8],
+    pos: usize
+}
+
+impl TzCursor<'_> {
+
+  fn peek(&self) -> Option<u8> {
+    self.bytes.get(self.pos).copied()
+  }
+
+  fn done(&self) -> bool {
+    self.pos == self.bytes.len()
+  }
+
+  fn tag(&mut self, tag: u8) -> Result<(), ParseError> {
+    match self.peek() == Some (tag) {
+      true  => { self.pos += 1; Ok (()) },
+      false => Err(ParseError::Format)
+    }
+  }
+
+  fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &[u8] {
+    let start = self.pos;
+    while self.peek().is_some_and(&f) { self.pos += 1 };
+    &self.bytes[start..self.pos]
+  }
+
+  // e.g. "CET" or "<+0530>"
+  fn name(&mut self) -> Result<String, ParseError> {
+    let name = match self.peek() {
+      Some (b'<') => {
+        self.pos += 1;
+        let name = self.take_while(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-').to_vec();
+        self.tag(b'>')?;
+        name
+      },
+      _ => self.take_while(|b| b.is_ascii_alphabetic()).to_vec()
+    };
+    match name.len() >= 3 {
+      true  => String::from_utf8(name).map_err(|_| ParseError::Format),
+      false => Err(ParseError::Format)
+    }
+  }
+
+  fn number(&mut self) -> Result<u64, ParseError> {
+    let digits = self.take_while(|b| b.is_ascii_digit());
+    if digits.is_empty() || digits.len() > 3 { return Err(ParseError::Format) };
+    Ok (digits.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u64))
+  }
+
+  // e.g. "-1" or "5:30", in seconds
+  fn signed_time(&mut self) -> Result<i32, ParseError> {
+    let sign = match self.peek() {
+      Some (b'-') => { self.pos += 1; -1 },
+      Some (b'+') => { self.pos += 1;  1 },
+      _           => 1
+    };
+    let hh = self.number()?;
+    let mm = match self.peek() == Some (b':') { true => { self.pos += 1; self.number()? }, false => 0 };
+    let ss = match self.peek() == Some (b':') { true => { self.pos += 1; self.number()? }, false => 0 };
+    if hh > 167 || mm >= 60 || ss >= 60 { return Err(ParseError::Range) };
+    Ok (sign * (hh * H_AS_S + mm * M_AS_S + ss) as i32)
+  }
+
+  // e.g. "M3.5.0/3"
+  fn rule(&mut self) -> Result<Rule, ParseError> {
+    let day = match self.peek() {
+      Some (b'J') => {
+        self.pos += 1;
+        let n = self.number()?;
+        if !(1..=365).contains(&n) { return Err(ParseError::Range) };
+        RuleDay::Julian(n as u16)
+      },
+      Some (b'M') => {
+        self.pos += 1;
+        let m = self.number()?; self.tag(b'.')?;
+        let w = self.number()?; self.tag(b'.')?;
+        let d = self.number()?;
+        if !(1..=12).contains(&m) || !(1..=5).contains(&w) || d > 6 { return Err(ParseError::Range) };
+        RuleDay::MonthWeekDay(m as u8, w as u8, d as u8)
+      },
+      _ => {
+        let n = self.number()?;
+        if n > 365 { return Err(ParseError::Range) };
+        RuleDay::Zero(n as u16)
+      }
+    };
+    let time = match self.peek() == Some (b'/') {
+      true  => { self.pos += 1; self.signed_time()? },
+      false => 2 * H_AS_S as i32
+    };
+    Ok (Rule { day, time })
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::PosixTz;
+  use crate::datetime::Datetime;
+  use crate::parse::ParseError;
+
+  fn at(s: &str) -> Datetime {
+    Datetime::parse(s).unwrap()
+  }
+
+  #[test]
+  fn tz_fixed() {
+
+    let tz = PosixTz::parse("JST-9").unwrap();
+    assert_eq!(9 * 3600, tz.offset_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+    assert_eq!("JST", tz.name_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+
+    let tz = PosixTz::parse("<+0530>-5:30").unwrap();
+    assert_eq!("01/Jan/2024:17:30:00 +0530", tz.to_local(&at("Mon, 01 Jan 2024 12:00:00 GMT")).for_log());
+    assert_eq!("+0530", tz.name_at(&at("Mon, 01 Jan 2024 12:00:00 GMT")));
+  }
+
+  #[test]
+  fn tz_northern() {
+
+    let tz = PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+
+    assert_eq!(3600, tz.offset_at(&at("Sun, 31 Mar 2024 00:59:59 GMT")));
+    assert_eq!(7200, tz.offset_at(&at("Sun, 31 Mar 2024 01:00:00 GMT")));
+    assert_eq!(7200, tz.offset_at(&at("Sun, 27 Oct 2024 00:59:59 GMT")));
+    assert_eq!(3600, tz.offset_at(&at("Sun, 27 Oct 2024 01:00:00 GMT")));
+
+    let tz = PosixTz::parse("EST5EDT").unwrap();
+
+    assert_eq!(-5 * 3600, tz.offset_at(&at("Sun, 10 Mar 2024 06:59:59 GMT")));
+    assert_eq!(-4 * 3600, tz.offset_at(&at("Sun, 10 Mar 2024 07:00:00 GMT")));
+    assert_eq!(-4 * 3600, tz.offset_at(&at("Sun, 03 Nov 2024 05:59:59 GMT")));
+    assert_eq!(-5 * 3600, tz.offset_at(&at("Sun, 03 Nov 2024 06:00:00 GMT")));
+    assert_eq!("EDT", tz.name_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+  }
+
+  #[test]
+  fn tz_southern() {
+
+    let tz = PosixTz::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+
+    assert_eq!(11 * 3600, tz.offset_at(&at("Mon, 01 Jan 2024 12:00:00 GMT")));
+    assert_eq!(11 * 3600, tz.offset_at(&at("Sat, 06 Apr 2024 15:59:59 GMT")));
+    assert_eq!(10 * 3600, tz.offset_at(&at("Sat, 06 Apr 2024 16:00:00 GMT")));
+    assert_eq!(10 * 3600, tz.offset_at(&at("Sat, 05 Oct 2024 15:59:59 GMT")));
+    assert_eq!(11 * 3600, tz.offset_at(&at("Sat, 05 Oct 2024 16:00:00 GMT")));
+  }
+
+  #[test]
+  fn tz_julian() {
+
+    // 1 March to 1 November, each at 02:00 local time
+    let tz = PosixTz::parse("XST3XDT,J60,J305").unwrap();
+
+    assert_eq!(-3 * 3600, tz.offset_at(&at("Fri, 01 Mar 2024 04:59:59 GMT")));
+    assert_eq!(-2 * 3600, tz.offset_at(&at("Fri, 01 Mar 2024 05:00:00 GMT")));
+
+    let tz = PosixTz::parse("XST3XDT,59,304").unwrap();
+
+    assert_eq!(-3 * 3600, tz.offset_at(&at("Thu, 29 Feb 2024 04:59:59 GMT")));
+    assert_eq!(-2 * 3600, tz.offset_at(&at("Thu, 29 Feb 2024 05:00:00 GMT")));
+  }
+
+  #[test]
+  fn tz_invalid() {
+
+    assert_eq!(Err(ParseError::Format), PosixTz::parse(""));
+    assert_eq!(Err(ParseError::Format), PosixTz::parse("CE-1"));
+    assert_eq!(Err(ParseError::Format), PosixTz::parse("CET"));
+    assert_eq!(Err(ParseError::Format), PosixTz::parse("CET-1CEST,M3.5.0"));
+    assert_eq!(Err(ParseError::Range),  PosixTz::parse("CET-1CEST,M13.5.0,M10.5.0/3"));
+    assert_eq!(Err(ParseError::Range),  PosixTz::parse("CET-1:60"));
+  }
+}
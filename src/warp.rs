@@ -0,0 +1,89 @@
+//! # warp
+//!
+//! Integration with warp, available with the `warp` feature.
+
+use crate::datetime::Datetime;
+use crate::service::{self, DateService};
+
+use ::warp::{Filter, Reply};
+use ::warp::reply::Response;
+use ::warp::http::HeaderValue;
+use ::warp::http::header::DATE;
+
+/// Wraps a filter, stamping each reply with a Date header
+/// from the shared cache of a `DateService`, unless the
+/// reply already has one, for use directly or via
+/// `warp::wrap_fn`.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+/// use httpdt::{DateService, warp::with_date};
+///
+/// let service = DateService::new()
+///   .unwrap();
+///
+/// let routes = warp::path("hello")
+///   .map(|| "Hello")
+///   .with(warp::wrap_fn(move |filter| with_date(filter, service.clone())));
+/// ```
+pub fn with_date<F, R>(filter: F, service: DateService) -> impl Filter<Extract = (Response,), Error = F::Error> + Clone
+where
+  F: Filter<Extract = (R,)> + Clone,
+  R: Reply
+{
+  filter.map(move |reply: R| {
+    let mut res = reply.into_response();
+    if let Ok (bytes) = service.for_header_bytes() {
+      res.headers_mut()
+        .entry(DATE)
+        .or_insert_with(|| service::header_value(&bytes));
+    }
+    res
+  })
+}
+
+/// Provides the HTTP Date header timestamp as a header value.
+pub fn header_value(dt: &Datetime) -> HeaderValue {
+  service::header_value(&dt.for_header_bytes())
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::with_date;
+  use crate::datetime::Datetime;
+  use crate::service::DateService;
+
+  use ::warp::Filter;
+  use ::warp::http::header::DATE;
+
+  fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+    tokio::runtime::Builder::new_current_thread()
+      .build()
+      .unwrap()
+      .block_on(future)
+  }
+
+  #[test]
+  fn warp_with_date() {
+
+    let filter = with_date(::warp::any().map(|| "Hello"), DateService::new().unwrap());
+    let res = block_on(::warp::test::request().reply(&filter));
+    let value = res.headers().get(DATE).unwrap().to_str().unwrap();
+
+    assert!(Datetime::parse(value).is_ok());
+    assert_eq!("Hello", res.body());
+  }
+
+  #[test]
+  fn warp_with_date_existing() {
+
+    let reply = ::warp::any().map(|| ::warp::reply::with_header("Hello", "date", "Thu, 01 Jan 1970 00:00:00 GMT"));
+    let filter = with_date(reply, DateService::new().unwrap());
+    let res = block_on(::warp::test::request().reply(&filter));
+
+    assert_eq!("Thu, 01 Jan 1970 00:00:00 GMT", res.headers().get(DATE).unwrap());
+  }
+}
@@ -0,0 +1,101 @@
+//! # wasm
+//!
+//! JavaScript bindings via wasm-bindgen, available with the
+//! `wasm-bindgen` feature, for generating and validating
+//! HTTP dates in browser and edge code consistently with
+//! a Rust backend. Times are exchanged as milliseconds
+//! since the epoch, as given by `Date.now()` and
+//! `Date.prototype.getTime()`, with any fraction of a
+//! second disregarded.
+
+use crate::datetime::{Datetime, MAX_SECS};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+const S_AS_MS: f64 = 1000.0;
+
+/// Provides the HTTP Date header timestamp for the given
+/// number of milliseconds since the epoch, or `undefined`
+/// where it is negative, not finite or beyond year 9999.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::wasm::format_http_date;
+///
+/// assert_eq!(Some (String::from("Sun, 06 Nov 1994 08:49:37 GMT")), format_http_date(784_111_777_500.0));
+/// assert_eq!(None, format_http_date(-1.0));
+/// ```
+#[wasm_bindgen(js_name = formatHttpDate)]
+pub fn format_http_date(ms: f64) -> Option<String> {
+  if !ms.is_finite() || ms < 0.0 {
+    return None
+  }
+  let secs = (ms / S_AS_MS).floor() as u64;
+  match secs <= MAX_SECS {
+    true  => Some (Datetime::from(secs).for_header()),
+    false => None
+  }
+}
+
+/// Parses an HTTP-date in any of the three formats
+/// recipients are required to accept, as for
+/// `Datetime::parse`, providing the number of milliseconds
+/// since the epoch, or `undefined` where invalid.
+///
+/// # Example
+///
+/// ```
+/// use httpdt::wasm::parse_http_date;
+///
+/// assert_eq!(Some (784_111_777_000.0), parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// assert_eq!(None, parse_http_date("Mon, 06 Nov 1994 08:49:37 GMT"));
+/// ```
+#[wasm_bindgen(js_name = parseHttpDate)]
+pub fn parse_http_date(s: &str) -> Option<f64> {
+  Datetime::parse(s)
+    .ok()
+    .map(|dt| dt.secs as f64 * S_AS_MS)
+}
+
+/// Indicates whether the given string is a valid HTTP-date,
+/// as for `parse_http_date`.
+#[wasm_bindgen(js_name = isValidHttpDate)]
+pub fn is_valid_http_date(s: &str) -> bool {
+  Datetime::parse(s).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::{format_http_date, parse_http_date, is_valid_http_date};
+
+  #[test]
+  fn wasm_format_http_date() {
+
+    assert_eq!(Some (String::from("Thu, 01 Jan 1970 00:00:00 GMT")), format_http_date(0.0));
+    assert_eq!(Some (String::from("Thu, 01 Jan 1970 00:00:00 GMT")), format_http_date(999.9));
+    assert_eq!(Some (String::from("Fri, 31 Dec 9999 23:59:59 GMT")), format_http_date(253_402_300_799_999.0));
+
+    assert_eq!(None, format_http_date(253_402_300_800_000.0));
+    assert_eq!(None, format_http_date(-0.5));
+    assert_eq!(None, format_http_date(f64::NAN));
+    assert_eq!(None, format_http_date(f64::INFINITY));
+  }
+
+  #[test]
+  fn wasm_parse_http_date() {
+
+    for s in ["Sun, 06 Nov 1994 08:49:37 GMT", "Sunday, 06-Nov-94 08:49:37 GMT", "Sun Nov  6 08:49:37 1994"] {
+      assert_eq!(Some (784_111_777_000.0), parse_http_date(s));
+      assert!(is_valid_http_date(s));
+    }
+
+    assert_eq!(None, parse_http_date("Sun, 06 Nov 1994 08:49:37 UTC"));
+    assert!(!is_valid_http_date(""));
+
+    // round trip
+    let ms = parse_http_date("Fri, 31 Dec 9999 23:59:59 GMT").unwrap();
+    assert_eq!(Some (String::from("Fri, 31 Dec 9999 23:59:59 GMT")), format_http_date(ms));
+  }
+}
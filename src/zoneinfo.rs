@@ -0,0 +1,343 @@
+//! # Zoneinfo
+//!
+//! Named timezones loaded at runtime from compiled
+//! zoneinfo (TZif) files, for the display of datetimes in
+//! local time, available with the `tzif` feature.
+
+use crate::datetime::Datetime;
+use crate::offset::OffsetDatetime;
+use crate::parse::ParseError;
+use crate::tz::PosixTz;
+
+use std::path::{Path, PathBuf, Component};
+use std::error::Error;
+use std::env;
+use std::fs;
+
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// Stores the transitions and local time types of a named
+/// timezone, read from a TZif file per RFC 8536, either by
+/// name from the directory in the `TZDIR` environment
+/// variable or `/usr/share/zoneinfo` (`named`), from a
+/// given path (`load`) or from bytes (`parse`). Datetimes
+/// after the last transition are handled by the POSIX TZ
+/// specification in the file footer, where present. The
+/// offset in effect at a given datetime is provided by
+/// `offset_at` and the datetime in local time as an
+/// `OffsetDatetime` by `to_local`.
+///
+/// # Example
+///
+/// ```no_run
+/// use httpdt::{Datetime, Zone};
+///
+/// let zone = Zone::named("Europe/Paris").unwrap();
+///
+/// let ts_log = zone.to_local(&Datetime::new().unwrap()).for_log();
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Zone {
+  transitions: Vec<Transition>,
+        types: Vec<LocalType>,
+       footer: Option<PosixTz>
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct Transition {
+  secs: i64,
+  idx: usize
+}
+
+#[derive(PartialEq, Clone, Debug)]
+struct LocalType {
+  offset: i32,
+    name: String
+}
+
+impl Zone {
+
+  pub fn named(name: &str) -> Result<Self, Box<dyn Error>> {
+    let is_relative = Path::new(name).components()
+      .all(|c| matches!(c, Component::Normal(_)));
+    if name.is_empty() || !is_relative {
+      return Err(format!("invalid zone name {name:?}").into())
+    }
+    let dir = env::var_os("TZDIR")
+      .map(PathBuf::from)
+      .unwrap_or(PathBuf::from(ZONEINFO_DIR));
+    Self::load(dir.join(name))
+  }
+
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+    let bytes = fs::read(path)?;
+    Ok (Self::parse(&bytes)?)
+  }
+
+  pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+
+    let mut r = Reader { bytes, pos: 0 };
+
+    // version 1 data block, skipped where a later block follows
+    let header = r.header()?;
+    if header.version == 0 {
+      return r.block(&header, 4)
+    }
+    r.skip(header.block_len(4))?;
+
+    let header = r.header()?;
+    let mut zone = r.block(&header, 8)?;
+
+    // footer, e.g. "\nCET-1CEST,M3.5.0,M10.5.0/3\n"
+    r.tag(b"\n")?;
+    let rest = &r.bytes[r.pos..];
+    let len = rest.iter().position(|&b| b == b'\n').ok_or(ParseError::Format)?;
+    let footer = std::str::from_utf8(&rest[..len]).map_err(|_| ParseError::Format)?;
+    if !footer.is_empty() {
+      zone.footer = Some (PosixTz::parse(footer)?);
+    }
+    Ok (zone)
+  }
+
+  pub fn offset_at(&self, dt: &Datetime) -> i32 {
+    match self.local_type(dt) {
+      Ok (lt)  => lt.offset,
+      Err(tz)  => tz.offset_at(dt)
+    }
+  }
+
+  pub fn name_at(&self, dt: &Datetime) -> &str {
+    match self.local_type(dt) {
+      Ok (lt)  => &lt.name,
+      Err(tz)  => tz.name_at(dt)
+    }
+  }
+
+  pub fn to_local(&self, dt: &Datetime) -> OffsetDatetime {
+    OffsetDatetime::new(*dt, self.offset_at(dt))
+  }
+
+  // the local time type in effect, or the footer where
+  // the datetime is after the last transition
+  fn local_type(&self, dt: &Datetime) -> Result<&LocalType, &PosixTz> {
+    let secs = dt.secs.min(i64::MAX as u64) as i64;
+    let n = self.transitions.partition_point(|t| t.secs <= secs);
+    match (n, &self.footer) {
+      (n, Some (tz)) if n == self.transitions.len() => Err(tz),
+      (0, _)                                        => Ok (&self.types[0]),
+      (n, _)                                        => Ok (&self.types[self.transitions[n - 1].idx])
+    }
+  }
+}
+
+// Header
+
+struct Header {
+  version: u8,
+  isutcnt: usize,
+  isstdcnt: usize,
+  leapcnt: usize,
+  timecnt: usize,
+  typecnt: usize,
+  charcnt: usize
+}
+
+impl Header {
+
+  fn block_len(&self, time_len: usize) -> usize {
+    self.timecnt * time_len
+      + self.timecnt
+      + self.typecnt * 6
+      + self.charcnt
+      + self.leapcnt * (time_len + 4)
+      + self.isstdcnt
+      + self.isutcnt
+  }
+}
+
+// Reader
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Reader<'a> {
+
+  fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+    let taken = self.bytes
+      .get(self.pos..self.pos.saturating_add(n))
+      .ok_or(ParseError::Format)?;
+    self.pos += n;
+    Ok (taken)
+  }
+
+  fn skip(&mut self, n: usize) -> Result<(), ParseError> {
+    self.take(n).map(|_| ())
+  }
+
+  fn tag(&mut self, tag: &[u8]) -> Result<(), ParseError> {
+    match self.take(tag.len())? == tag {
+      true  => Ok (()),
+      false => Err(ParseError::Format)
+    }
+  }
+
+  fn int(&mut self, len: usize) -> Result<i64, ParseError> {
+    let taken = self.take(len)?;
+    let mut buf = [0; 8];
+    buf[8 - len..].copy_from_slice(taken);
+    // sign-extended from the leading byte
+    Ok (i64::from_be_bytes(buf) << (64 - len * 8) >> (64 - len * 8))
+  }
+
+  fn count(&mut self) -> Result<usize, ParseError> {
+    Ok (self.int(4)? as u32 as usize)
+  }
+
+  fn header(&mut self) -> Result<Header, ParseError> {
+    self.tag(b"TZif")?;
+    let version = match self.take(1)?[0] {
+      0    => 0,
+      b'2' => 2,
+      b'3' => 3,
+      b'4' => 4,
+      _    => return Err(ParseError::Format)
+    };
+    self.skip(15)?;
+    Ok (Header {
+      version,
+      isutcnt:  self.count()?,
+      isstdcnt: self.count()?,
+      leapcnt:  self.count()?,
+      timecnt:  self.count()?,
+      typecnt:  self.count()?,
+      charcnt:  self.count()?
+    })
+  }
+
+  fn block(&mut self, header: &Header, time_len: usize) -> Result<Zone, ParseError> {
+
+    if header.typecnt == 0 { return Err(ParseError::Format) };
+
+    let secs = (0..header.timecnt)
+      .map(|_| self.int(time_len))
+      .collect::<Result<Vec<_>, _>>()?;
+    let idxs = self.take(header.timecnt)?;
+    let transitions = secs.into_iter()
+      .zip(idxs)
+      .map(|(secs, &idx)| match (idx as usize) < header.typecnt {
+        true  => Ok (Transition { secs, idx: idx as usize }),
+        false => Err(ParseError::Format)
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let ttinfos = (0..header.typecnt)
+      .map(|_| Ok ((self.int(4)? as i32, self.take(2)?[1] as usize)))
+      .collect::<Result<Vec<_>, _>>()?;
+    let chars = self.take(header.charcnt)?;
+    let types = ttinfos.into_iter()
+      .map(|(offset, idx)| {
+        let rest = chars.get(idx..).ok_or(ParseError::Format)?;
+        let len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        let name = String::from_utf8(rest[..len].to_vec()).map_err(|_| ParseError::Format)?;
+        Ok (LocalType { offset, name })
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    self.skip(header.leapcnt * (time_len + 4) + header.isstdcnt + header.isutcnt)?;
+    Ok (Zone { transitions, types, footer: None })
+  }
+}
+
+#[cfg(test)]
+mod test {
+
+  use super::Zone;
+  use crate::datetime::Datetime;
+  use crate::parse::ParseError;
+
+  // version 2 file with an empty version 1 block
+  fn tzif(transitions: &[(i64, u8)], types: &[(i32, &str)], footer: &str) -> Vec<u8> {
+
+    let mut chars = Vec::new();
+    let mut ttinfos = Vec::new();
+    for (offset, name) in types {
+      ttinfos.extend_from_slice(&offset.to_be_bytes());
+      ttinfos.extend_from_slice(&[0, chars.len() as u8]);
+      chars.extend_from_slice(name.as_bytes());
+      chars.push(0);
+    }
+
+    let header = |counts: [usize; 6]| {
+      let mut bytes = b"TZif2".to_vec();
+      bytes.extend_from_slice(&[0; 15]);
+      for count in counts { bytes.extend_from_slice(&(count as u32).to_be_bytes()) };
+      bytes
+    };
+
+    let mut bytes = header([0, 0, 0, 0, 1, 1]);
+    bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0]);
+    bytes.extend(header([0, 0, 0, transitions.len(), types.len(), chars.len()]));
+    for (secs, _) in transitions { bytes.extend_from_slice(&secs.to_be_bytes()) };
+    for (_, idx) in transitions { bytes.push(*idx) };
+    bytes.extend(ttinfos);
+    bytes.extend(chars);
+    bytes.extend_from_slice(format!("\n{footer}\n").as_bytes());
+    bytes
+  }
+
+  fn at(s: &str) -> Datetime {
+    Datetime::parse(s).unwrap()
+  }
+
+  #[test]
+  fn zoneinfo_parse() {
+
+    // LMT to CET in 1970, CET and CEST per the footer from 1980
+    let bytes = tzif(
+      &[(-1_000_000, 1), (1_000_000, 0), (315_532_800, 0)],
+      &[(3600, "CET"), (600, "LMT")],
+      "CET-1CEST,M3.5.0,M10.5.0/3"
+    );
+    let zone = Zone::parse(&bytes).unwrap();
+
+    assert_eq!( 600, zone.offset_at(&Datetime::default()));
+    assert_eq!("LMT", zone.name_at(&Datetime::default()));
+    assert_eq!(3600, zone.offset_at(&Datetime::from(1_000_000)));
+    assert_eq!(7200, zone.offset_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+    assert_eq!("CEST", zone.name_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+    assert_eq!("01/Jan/2024:13:00:00 +0100", zone.to_local(&at("Mon, 01 Jan 2024 12:00:00 GMT")).for_log());
+  }
+
+  #[test]
+  fn zoneinfo_parse_no_footer() {
+
+    let bytes = tzif(&[(1_000_000, 1)], &[(0, "AAA"), (-3600, "BBB")], "");
+    let zone = Zone::parse(&bytes).unwrap();
+
+    assert_eq!(0, zone.offset_at(&Datetime::default()));
+    assert_eq!(-3600, zone.offset_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+    assert_eq!("BBB", zone.name_at(&at("Mon, 01 Jul 2024 12:00:00 GMT")));
+  }
+
+  #[test]
+  fn zoneinfo_parse_invalid() {
+
+    let bytes = tzif(&[(1_000_000, 2)], &[(0, "AAA"), (-3600, "BBB")], "");
+    assert_eq!(Err(ParseError::Format), Zone::parse(&bytes));
+
+    let bytes = tzif(&[], &[(0, "AAA")], "");
+    assert_eq!(Err(ParseError::Format), Zone::parse(&bytes[..bytes.len() - 1]));
+    assert_eq!(Err(ParseError::Format), Zone::parse(b"TZif"));
+    assert_eq!(Err(ParseError::Format), Zone::parse(b""));
+  }
+
+  #[test]
+  fn zoneinfo_named() {
+
+    assert!(Zone::named("../etc/passwd").is_err());
+    assert!(Zone::named("/etc/passwd").is_err());
+    assert!(Zone::named("").is_err());
+  }
+}
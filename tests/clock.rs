@@ -0,0 +1,34 @@
+#![cfg(feature = "clock-override")]
+
+use httpdt::{Datetime, DateService, set_global_clock, reset_global_clock};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+const NOV_06_1994_08_49_37: u64 = 784_111_777;
+
+// in a single test, the global clock being shared
+#[test]
+fn clock_global() {
+
+  let secs = Arc::new(AtomicU64::new(NOV_06_1994_08_49_37));
+  let source = secs.clone();
+  set_global_clock(move || source.load(Ordering::SeqCst));
+
+  assert_eq!(NOV_06_1994_08_49_37, Datetime::raw().unwrap());
+  assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", Datetime::new().unwrap().for_header());
+
+  let service = DateService::new().unwrap();
+  secs.fetch_add(1, Ordering::SeqCst);
+  assert_eq!("Sun, 06 Nov 1994 08:49:38 GMT", service.for_header().unwrap());
+
+  // across threads, where supported
+  #[cfg(not(target_os = "wasi"))]
+  {
+    let raw = std::thread::spawn(|| Datetime::raw().unwrap()).join().unwrap();
+    assert_eq!(NOV_06_1994_08_49_37 + 1, raw);
+  }
+
+  reset_global_clock();
+  assert!(Datetime::raw().unwrap() > NOV_06_1994_08_49_37);
+}
@@ -0,0 +1,75 @@
+// the example servers, each run against a real connection
+
+#[path = "../examples/std_server.rs"]
+#[allow(dead_code)]
+mod std_server;
+#[path = "../examples/hyper_server.rs"]
+#[allow(dead_code)]
+mod hyper_server;
+
+use httpdt::{Datetime, DateService};
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+const REQUEST: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+// the Date header value, checked against the clock
+fn date(response: &str) -> Datetime {
+  let before = Datetime::raw().unwrap();
+  let value = response
+    .lines()
+    .find_map(|line| line.strip_prefix("date: ").or_else(|| line.strip_prefix("Date: ")))
+    .unwrap();
+  let dt = Datetime::parse(value).unwrap();
+  assert!(dt.secs() + 2 >= before && dt.secs() <= before);
+  dt
+}
+
+#[test]
+fn examples_std_server() {
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let addr = listener.local_addr().unwrap();
+  thread::spawn(move || std_server::serve(listener, DateService::new().unwrap()));
+
+  for _ in 0..2 {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(REQUEST).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("\r\n\r\nHello\n"));
+    date(&response);
+  }
+}
+
+#[test]
+fn examples_hyper_server() {
+
+  let runtime = tokio::runtime::Builder::new_current_thread()
+    .enable_all()
+    .build()
+    .unwrap();
+
+  let response = runtime.block_on(async {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(hyper_server::serve(listener, DateService::new().unwrap()));
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream.write_all(REQUEST).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+  });
+
+  assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+  assert!(response.ends_with("\r\n\r\nHello\n"));
+  assert_eq!(1, response.matches("date: ").count());
+  date(&response);
+}